@@ -0,0 +1,56 @@
+"""从 CMUdict 生成内置发音表 cmudict.tsv.gz
+
+用法：python cmudict.py cmudict.dict LICENSE
+
+只保留 vocab.tsv.gz 中的单词及现有发音表中的单词，多音词只取第一个读音，
+CMUdict 的许可声明写入文件头部的注释。
+"""
+import gzip
+import os
+import sys
+
+DIR = os.path.dirname(os.path.abspath(__file__))
+OUTPUT = os.path.join(DIR, 'cmudict.tsv.gz')
+
+
+def read_words(path):
+    words = set()
+    with gzip.open(path, 'rt', encoding='utf-8') as f:
+        for line in f:
+            if line.strip() and not line.startswith('#'):
+                words.add(line.split('\t')[0].strip().lower())
+    return words
+
+
+def main(dict_path, license_path):
+    words = read_words(os.path.join(DIR, 'vocab.tsv.gz'))
+    if os.path.exists(OUTPUT):
+        words |= read_words(OUTPUT)
+    table = {}
+    with open(dict_path, 'rb') as f:
+        for line in f.read().decode('latin-1').splitlines():
+            line = line.split('#')[0].strip()
+            if not line or line.startswith(';;;'):
+                continue
+            word, phones = line.split(None, 1)
+            word = word.lower()
+            if word.endswith(')') or word not in words or word in table:
+                continue
+            table[word] = ' '.join(phones.split())
+    with open(license_path, encoding='utf-8') as f:
+        notice = ['# ' + x.rstrip() if x.strip() else '#' for x in f]
+    lines = [
+        '# word\tphones',
+        '# 由 data/cmudict.py 从 CMUdict（https://github.com/cmusphinx/cmudict ，BSD 许可）生成，格式与 cmudict.dict 相同',
+        *notice,
+    ]
+    lines += [f'{w}\t{p}' for w, p in sorted(table.items())]
+    with open(OUTPUT, 'wb') as f:
+        f.write(gzip.compress(('\n'.join(lines) + '\n').encode('utf-8'), 9, mtime=0))
+    print(f'{len(table)} words, {len(words) - len(table)} not found')
+
+
+if __name__ == '__main__':
+    if len(sys.argv) != 3:
+        sys.exit(__doc__)
+    main(sys.argv[1], sys.argv[2])
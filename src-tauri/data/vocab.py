@@ -0,0 +1,61 @@
+"""生成内置词表 vocab.tsv.gz
+
+用法：python vocab.py cefr.tsv [标签=词表文件 ...]
+
+cefr.tsv 每行为 单词\tCEFR等级；标签词表每行一个单词，如 cet4=cet4.txt、ox3000=oxford3000.txt。
+输入文件开头以 # 开头的行应写明来源和许可，会原样写入输出文件头部，没有来源说明的词表不会被收录。
+"""
+import gzip
+import os
+import sys
+
+DIR = os.path.dirname(os.path.abspath(__file__))
+OUTPUT = os.path.join(DIR, 'vocab.tsv.gz')
+LEVELS = ('A1', 'A2', 'B1', 'B2', 'C1', 'C2')
+
+
+def read_list(path):
+    notice, rows = [], []
+    with open(path, encoding='utf-8') as f:
+        for line in f:
+            line = line.rstrip('\n')
+            if line.startswith('#'):
+                if not rows:
+                    notice.append(line.lstrip('#').strip())
+                continue
+            if line.strip():
+                rows.append([x.strip() for x in line.split('\t')])
+    if not any(notice):
+        sys.exit(f'{path} 缺少来源说明')
+    return notice, rows
+
+
+def main(cefr_path, tag_args):
+    notice, rows = read_list(cefr_path)
+    header = ['# word\tcefr\ttags', f'# cefr：{os.path.basename(cefr_path)}', *('#   ' + x for x in notice if x)]
+    table = {}
+    for row in rows:
+        word, level = row[0].lower(), row[1].upper() if len(row) > 1 else ''
+        if level not in LEVELS:
+            sys.exit(f'{word} 的等级 {level} 无效')
+        # 同一单词有多个等级时取最低的等级
+        if word not in table or LEVELS.index(level) < LEVELS.index(table[word][0]):
+            table[word] = (level, [])
+    for arg in tag_args:
+        tag, path = arg.split('=', 1)
+        notice, rows = read_list(path)
+        header += [f'# {tag}：{os.path.basename(path)}', *('#   ' + x for x in notice if x)]
+        for row in rows:
+            level, tags = table.setdefault(row[0].lower(), ('', []))
+            if tag not in tags:
+                tags.append(tag)
+    lines = header + [f'{w}\t{l}\t{",".join(t)}'.rstrip('\t') for w, (l, t) in sorted(table.items())]
+    with open(OUTPUT, 'wb') as f:
+        f.write(gzip.compress(('\n'.join(lines) + '\n').encode('utf-8'), 9, mtime=0))
+    print(f'{len(table)} words')
+
+
+if __name__ == '__main__':
+    if len(sys.argv) < 2:
+        sys.exit(__doc__)
+    main(sys.argv[1], sys.argv[2:])
@@ -1,4 +1,4 @@
-use crate::global::JIEBA;
+use crate::segment::with_jieba;
 
 #[tauri::command]
 pub fn set_proxy(proxy: &str, no_proxy: &str) -> Result<bool, ()> {
@@ -20,8 +20,7 @@ pub fn unset_proxy() -> Result<bool, ()> {
 
 #[tauri::command]
 pub fn cut(text: &str) -> Result<Vec<&str>, ()> {
-    let jieba = JIEBA.lock().unwrap();
-    let words = jieba.cut(text.trim(), false);
+    let words = with_jieba(|jieba| jieba.cut(text.trim(), false));
     Ok(words
         .iter()
         .filter(|x| x.trim().len() > 1)
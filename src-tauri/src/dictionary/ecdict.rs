@@ -0,0 +1,201 @@
+use super::{dictionaries_dir, DictResult, Explanation, Pronunciation};
+use crate::lemmatize::{self, WordForm};
+use log::{info, warn};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use rusqlite::{Connection, OpenFlags, OptionalExtension};
+use std::{path::PathBuf, sync::Mutex};
+
+pub static ECDICT_FILE_NAME: &str = "ecdict.db";
+pub static ECDICT_NAME: &str = "ECDICT";
+
+// 释义行开头的词性或领域，如 n. 或 [计]
+static TRAIT_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^((?:[a-z]+\.)+|\[[^\]]+\])\s*(.*)$").unwrap());
+
+static CONNECTION: Lazy<Mutex<Option<Connection>>> = Lazy::new(|| Mutex::new(None));
+
+struct Row {
+    word: String,
+    phonetic: String,
+    definition: String,
+    translation: String,
+    tag: String,
+    exchange: String,
+    oxford: i64,
+}
+
+fn db_path() -> PathBuf {
+    dictionaries_dir().join(ECDICT_FILE_NAME)
+}
+
+/// 词典数据由用户提供：ECDICT发布的 stardict.db 重命名后放入词典目录
+fn open() -> Result<Connection, String> {
+    let path = db_path();
+    if !path.is_file() {
+        return Err(format!(
+            "未找到本地词典，请将ECDICT的stardict.db重命名为{}放入词典目录",
+            ECDICT_FILE_NAME
+        ));
+    }
+    let conn = Connection::open_with_flags(&path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+        .map_err(|e| e.to_string())?;
+    info!("ecdict opened: {:?}", path);
+    Ok(conn)
+}
+
+fn query(conn: &Connection, word: &str) -> rusqlite::Result<Option<Row>> {
+    conn.query_row(
+        "SELECT word, phonetic, definition, translation, tag, exchange, oxford \
+         FROM stardict WHERE word = ?1 COLLATE NOCASE \
+         ORDER BY word = ?1 DESC LIMIT 1",
+        [word],
+        |r| {
+            Ok(Row {
+                word: r.get(0)?,
+                phonetic: r.get::<_, Option<String>>(1)?.unwrap_or_default(),
+                definition: r.get::<_, Option<String>>(2)?.unwrap_or_default(),
+                translation: r.get::<_, Option<String>>(3)?.unwrap_or_default(),
+                tag: r.get::<_, Option<String>>(4)?.unwrap_or_default(),
+                exchange: r.get::<_, Option<String>>(5)?.unwrap_or_default(),
+                oxford: r.get::<_, Option<i64>>(6)?.unwrap_or_default(),
+            })
+        },
+    )
+    .optional()
+}
+
+/// 按行解析释义，行首为词性时作为 trait，同一行的多个义项以逗号、分号分隔
+fn explanations(text: &str) -> Vec<Explanation> {
+    let mut list: Vec<Explanation> = Vec::new();
+    // 数据库中的换行保存为字面的 \n
+    for line in text.replace("\\n", "\n").lines().map(|l| l.trim()) {
+        if line.is_empty() {
+            continue;
+        }
+        let (trait_, content) = match TRAIT_RE.captures(line) {
+            Some(c) => (c[1].to_string(), c[2].to_string()),
+            None => (String::new(), line.to_string()),
+        };
+        let explains: Vec<String> = content
+            .split([',', ';', '；', '，'])
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+            .collect();
+        if explains.is_empty() {
+            continue;
+        }
+        match list.iter_mut().find(|e| e.trait_ == trait_) {
+            Some(e) => e.explains.extend(explains),
+            None => list.push(Explanation { trait_, explains }),
+        }
+    }
+    list
+}
+
+/// exchange字段格式为 类型:词形/类型:词形，0为原形，1为当前词相对原形的变化类型
+fn word_forms(exchange: &str) -> Vec<WordForm> {
+    exchange
+        .split('/')
+        .filter_map(|item| item.split_once(':'))
+        .filter_map(|(kind, value)| {
+            let name = match kind {
+                "p" => "过去式",
+                "d" => "过去分词",
+                "i" => "现在分词",
+                "3" => "第三人称单数",
+                "r" => "比较级",
+                "t" => "最高级",
+                "s" => "复数",
+                "0" => "原形",
+                _ => return None,
+            };
+            Some(WordForm {
+                name: name.into(),
+                value: value.to_string(),
+            })
+        })
+        .filter(|f| !f.value.is_empty())
+        .collect()
+}
+
+fn exam_tags(tag: &str, oxford: i64) -> Vec<String> {
+    let mut tags: Vec<String> = tag
+        .split_whitespace()
+        .map(|t| {
+            match t {
+                "zk" => "中考",
+                "gk" => "高考",
+                "cet4" => "四级",
+                "cet6" => "六级",
+                "ky" => "考研",
+                "toefl" => "托福",
+                "ielts" => "雅思",
+                "gre" => "GRE",
+                t => t,
+            }
+            .to_string()
+        })
+        .collect();
+    if oxford > 0 {
+        tags.push("牛津3000".to_string());
+    }
+    tags
+}
+
+fn to_result(row: Row) -> DictResult {
+    let mut result = DictResult::new(&row.word, ECDICT_NAME);
+    if !row.phonetic.is_empty() {
+        result.pronunciations.push(Pronunciation {
+            region: String::new(),
+            symbol: row.phonetic.clone(),
+            voice: None,
+        });
+        result.phonetic = Some(row.phonetic);
+    }
+    // 没有中文释义时使用英文释义
+    result.explanations = explanations(&row.translation);
+    if result.explanations.is_empty() {
+        result.explanations = explanations(&row.definition);
+    }
+    result.wfs = word_forms(&row.exchange);
+    result.tags = exam_tags(&row.tag, row.oxford);
+    result
+}
+
+/// 查询单词，不存在时依次使用还原后的原形查询
+pub fn lookup(word: &str) -> Result<Option<DictResult>, String> {
+    let word = word.trim();
+    if word.is_empty() {
+        return Ok(None);
+    }
+    let mut guard = CONNECTION.lock().unwrap();
+    if guard.is_none() {
+        *guard = Some(open()?);
+    }
+    let conn = guard.as_ref().unwrap();
+    let mut candidates = vec![word.to_string()];
+    if word.chars().all(|c| c.is_ascii_alphabetic()) {
+        for lemma in lemmatize::lemmatize_word(word) {
+            if !candidates.contains(&lemma.lemma) {
+                candidates.push(lemma.lemma);
+            }
+        }
+    }
+    for candidate in candidates {
+        match query(conn, &candidate) {
+            Ok(Some(row)) => return Ok(Some(to_result(row))),
+            Ok(None) => {}
+            Err(e) => {
+                warn!("ecdict query {} error: {}", candidate, e);
+                return Err(e.to_string());
+            }
+        }
+    }
+    Ok(None)
+}
+
+#[tauri::command(async)]
+pub fn ecdict_lookup(text: &str) -> Result<DictResult, String> {
+    lookup(text)?.ok_or_else(|| format!("{} not found", text.trim()))
+}
@@ -0,0 +1,359 @@
+use super::{parse, DictResult, IndexEntry};
+use encoding_rs::{Encoding, GB18030, UTF_16LE, UTF_8};
+use flate2::read::ZlibDecoder;
+use ripemd::{Digest, Ripemd128};
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::{Read, Seek, SeekFrom},
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+/// 词条内容为该前缀时表示跳转到另一个词条
+pub const LINK_PREFIX: &str = "@@@LINK=";
+
+/// MDict词典（.mdx）或资源包（.mdd），支持2.0及1.2版本，不支持LZO压缩和需要注册码的加密
+pub struct MDict {
+    pub name: String,
+    pub path: PathBuf,
+    encoding: &'static Encoding,
+    /// 是否是资源包，资源包的内容为二进制数据
+    is_resource: bool,
+    record_blocks: Vec<RecordBlock>,
+    /// 最近一次解压的记录块，连续查询同一区域时不用重复解压
+    last_block: Mutex<Option<(usize, Vec<u8>)>>,
+}
+
+struct RecordBlock {
+    /// 压缩数据在文件中的位置
+    file_offset: u64,
+    compressed_size: usize,
+    /// 解压后在所有记录中的位置
+    offset: u64,
+    size: usize,
+}
+
+/// 按大端序读取数字，2.0版本为8字节，1.2版本为4字节
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+    wide: bool,
+}
+
+impl<'a> Reader<'a> {
+    fn new(data: &'a [u8], wide: bool) -> Self {
+        Reader { data, pos: 0, wide }
+    }
+
+    fn bytes(&mut self, len: usize) -> Result<&'a [u8], String> {
+        let end = self.pos + len;
+        if end > self.data.len() {
+            return Err("词典文件已损坏".to_string());
+        }
+        let bytes = &self.data[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn number(&mut self) -> Result<u64, String> {
+        if self.wide {
+            Ok(u64::from_be_bytes(self.bytes(8)?.try_into().unwrap()))
+        } else {
+            Ok(u32::from_be_bytes(self.bytes(4)?.try_into().unwrap()) as u64)
+        }
+    }
+
+    fn short(&mut self) -> Result<usize, String> {
+        if self.wide {
+            Ok(u16::from_be_bytes(self.bytes(2)?.try_into().unwrap()) as usize)
+        } else {
+            Ok(self.bytes(1)?[0] as usize)
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.pos >= self.data.len()
+    }
+}
+
+fn read_exact(file: &mut File, len: usize) -> Result<Vec<u8>, String> {
+    let mut buf = vec![0; len];
+    file.read_exact(&mut buf).map_err(|e| e.to_string())?;
+    Ok(buf)
+}
+
+/// 解析头部的XML属性，只需要属性值，不必完整解析XML
+fn parse_header(xml: &str) -> HashMap<String, String> {
+    let re = regex::Regex::new(r#"(\w+)="([^"]*)""#).unwrap();
+    re.captures_iter(xml)
+        .map(|c| (c[1].to_string(), parse::decode_entities(&c[2])))
+        .collect()
+}
+
+/// 关键词信息块的解密：密钥为块中4~8字节与0x3695的RIPEMD-128摘要
+fn decrypt_key_info(block: &[u8]) -> Result<Vec<u8>, String> {
+    if block.len() < 8 {
+        return Err("词典关键词信息块已损坏".to_string());
+    }
+    let mut hasher = Ripemd128::new();
+    hasher.update(&block[4..8]);
+    hasher.update(0x3695u32.to_le_bytes());
+    let key = hasher.finalize();
+    let mut out = block[..8].to_vec();
+    let mut previous = 0x36u8;
+    for (i, b) in block[8..].iter().enumerate() {
+        let t = b.rotate_left(4) ^ previous ^ (i as u8) ^ key[i % key.len()];
+        previous = *b;
+        out.push(t);
+    }
+    Ok(out)
+}
+
+/// 解压数据块：前4字节为压缩方式，随后4字节为校验码
+fn decompress_block(block: &[u8], size_hint: usize) -> Result<Vec<u8>, String> {
+    if block.len() < 8 {
+        return Err("词典数据块已损坏".to_string());
+    }
+    match block[0] {
+        0 => Ok(block[8..].to_vec()),
+        1 => Err("不支持LZO压缩的词典，请使用新版MdxBuilder重新生成".to_string()),
+        2 => {
+            let mut out = Vec::with_capacity(size_hint);
+            ZlibDecoder::new(&block[8..])
+                .read_to_end(&mut out)
+                .map_err(|e| e.to_string())?;
+            Ok(out)
+        }
+        t => Err(format!("未知的压缩方式：{}", t)),
+    }
+}
+
+impl MDict {
+    /// 打开词典并读取全部关键词，返回的索引未排序，offset、size为词条在解压后记录中的位置
+    pub fn open(path: &Path) -> Result<(MDict, Vec<IndexEntry>), String> {
+        let is_resource = path
+            .extension()
+            .map_or(false, |e| e.eq_ignore_ascii_case("mdd"));
+        let mut file = File::open(path).map_err(|e| e.to_string())?;
+
+        // 头部：4字节长度 + UTF-16LE的XML + 4字节校验码
+        let header_len = u32::from_be_bytes(read_exact(&mut file, 4)?.try_into().unwrap());
+        let header_bytes = read_exact(&mut file, header_len as usize)?;
+        read_exact(&mut file, 4)?;
+        let (xml, _, _) = UTF_16LE.decode(&header_bytes);
+        let header = parse_header(&xml);
+
+        let version: f32 = header
+            .get("GeneratedByEngineVersion")
+            .and_then(|v| v.trim().parse().ok())
+            .unwrap_or(2.0);
+        if version >= 3.0 {
+            return Err("暂不支持3.0版本的MDict词典".to_string());
+        }
+        let wide = version >= 2.0;
+        let encrypted = match header.get("Encrypted").map(|e| e.as_str()) {
+            None | Some("") | Some("No") => 0,
+            Some("Yes") => 1,
+            Some(e) => e.parse::<u8>().unwrap_or(0),
+        };
+        if encrypted & 1 != 0 {
+            return Err("词典已加密，需要注册码".to_string());
+        }
+        let encoding = if is_resource {
+            UTF_16LE
+        } else {
+            match header.get("Encoding").map(|e| e.to_uppercase()) {
+                Some(e) if e == "GBK" || e == "GB2312" => GB18030,
+                Some(e) if !e.is_empty() => Encoding::for_label(e.as_bytes()).unwrap_or(UTF_8),
+                _ => UTF_8,
+            }
+        };
+        let utf16 = encoding == UTF_16LE;
+        let name = header
+            .get("Title")
+            .map(|t| t.trim().to_string())
+            .filter(|t| !t.is_empty() && t != "Title (No HTML code allowed)")
+            .unwrap_or_else(|| {
+                path.file_stem()
+                    .map(|s| s.to_string_lossy().to_string())
+                    .unwrap_or_default()
+            });
+
+        // 关键词区
+        let head = read_exact(&mut file, if wide { 40 } else { 16 })?;
+        let mut r = Reader::new(&head, wide);
+        let key_block_count = r.number()?;
+        r.number()?;
+        if wide {
+            r.number()?;
+        }
+        let key_info_size = r.number()? as usize;
+        let key_blocks_size = r.number()? as usize;
+        if wide {
+            read_exact(&mut file, 4)?;
+        }
+        let mut key_info = read_exact(&mut file, key_info_size)?;
+        if wide {
+            if encrypted & 2 != 0 {
+                key_info = decrypt_key_info(&key_info)?;
+            }
+            key_info = decompress_block(&key_info, 0)?;
+        }
+        let mut key_block_sizes = Vec::new();
+        let mut r = Reader::new(&key_info, wide);
+        // 2.0版本的首尾关键词以\0结尾，UTF-16时长度按字符计
+        let term = if wide { 1 } else { 0 };
+        let char_width = if utf16 { 2 } else { 1 };
+        while !r.is_empty() && (key_block_sizes.len() as u64) < key_block_count {
+            r.number()?;
+            let head_len = r.short()?;
+            r.bytes((head_len + term) * char_width)?;
+            let tail_len = r.short()?;
+            r.bytes((tail_len + term) * char_width)?;
+            let compressed = r.number()? as usize;
+            let decompressed = r.number()? as usize;
+            key_block_sizes.push((compressed, decompressed));
+        }
+
+        let key_blocks = read_exact(&mut file, key_blocks_size)?;
+        let mut keys: Vec<(u64, String)> = Vec::new();
+        let mut pos = 0;
+        for (compressed, decompressed) in key_block_sizes {
+            let block = key_blocks
+                .get(pos..pos + compressed)
+                .ok_or("词典关键词区已损坏")?;
+            pos += compressed;
+            let block = decompress_block(block, decompressed)?;
+            let mut r = Reader::new(&block, wide);
+            while !r.is_empty() {
+                let offset = r.number()?;
+                let rest = &block[r.pos..];
+                let end = if utf16 {
+                    rest.chunks(2).position(|c| c == [0, 0]).map(|p| p * 2)
+                } else {
+                    rest.iter().position(|b| *b == 0)
+                }
+                .unwrap_or(rest.len());
+                let (key, _, _) = encoding.decode(&rest[..end]);
+                keys.push((offset, key.trim().to_string()));
+                r.pos += (end + char_width).min(rest.len());
+            }
+        }
+
+        // 记录区
+        let head = read_exact(&mut file, if wide { 32 } else { 16 })?;
+        let mut r = Reader::new(&head, wide);
+        let record_block_count = r.number()? as usize;
+        r.number()?;
+        let record_info_size = r.number()? as usize;
+        r.number()?;
+        let record_info = read_exact(&mut file, record_info_size)?;
+        let mut file_offset = file.stream_position().map_err(|e| e.to_string())?;
+        let mut r = Reader::new(&record_info, wide);
+        let mut record_blocks = Vec::with_capacity(record_block_count);
+        let mut offset = 0;
+        for _ in 0..record_block_count {
+            let compressed_size = r.number()? as usize;
+            let size = r.number()? as usize;
+            record_blocks.push(RecordBlock {
+                file_offset,
+                compressed_size,
+                offset,
+                size,
+            });
+            file_offset += compressed_size as u64;
+            offset += size as u64;
+        }
+
+        // 词条的长度为到下一个词条开始的位置
+        let total = offset;
+        let mut entries = Vec::with_capacity(keys.len());
+        for i in 0..keys.len() {
+            let start = keys[i].0;
+            let end = keys.get(i + 1).map_or(total, |k| k.0).max(start);
+            entries.push(IndexEntry::new(
+                std::mem::take(&mut keys[i].1),
+                start,
+                end - start,
+            ));
+        }
+
+        let dict = MDict {
+            name,
+            path: path.to_path_buf(),
+            encoding,
+            is_resource,
+            record_blocks,
+            last_block: Mutex::new(None),
+        };
+        Ok((dict, entries))
+    }
+
+    fn block(&self, index: usize) -> Result<Vec<u8>, String> {
+        if let Some((i, data)) = self.last_block.lock().unwrap().as_ref() {
+            if *i == index {
+                return Ok(data.clone());
+            }
+        }
+        let block = &self.record_blocks[index];
+        let mut file = File::open(&self.path).map_err(|e| e.to_string())?;
+        file.seek(SeekFrom::Start(block.file_offset))
+            .map_err(|e| e.to_string())?;
+        let data = decompress_block(&read_exact(&mut file, block.compressed_size)?, block.size)?;
+        *self.last_block.lock().unwrap() = Some((index, data.clone()));
+        Ok(data)
+    }
+
+    pub fn read(&self, offset: u64, size: u64) -> Result<Vec<u8>, String> {
+        let end = offset + size;
+        let first = self
+            .record_blocks
+            .partition_point(|b| b.offset + b.size as u64 <= offset);
+        let mut out = Vec::with_capacity(size as usize);
+        // 词条一般在一个记录块内，资源文件可能跨越多个记录块
+        for index in first..self.record_blocks.len() {
+            let block = &self.record_blocks[index];
+            if block.offset >= end {
+                break;
+            }
+            let data = self.block(index)?;
+            let from = offset.saturating_sub(block.offset) as usize;
+            let to = ((end - block.offset) as usize).min(data.len());
+            if from < to {
+                out.extend_from_slice(&data[from..to]);
+            }
+        }
+        Ok(out)
+    }
+
+    pub fn decode(&self, data: &[u8]) -> String {
+        let (text, _, _) = self.encoding.decode(data);
+        text.trim_end_matches('\0').to_string()
+    }
+
+    pub fn to_result(&self, word: &str, data: &[u8]) -> DictResult {
+        let mut result = DictResult::new(word, &self.name);
+        if self.is_resource {
+            return result;
+        }
+        let html = self.decode(data);
+        parse::fill_from_text(&mut result, &parse::html_to_text(&html));
+        result.html = Some(html);
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn short_key_info() {
+        assert!(decrypt_key_info(&[2, 0, 0, 0, 1]).is_err());
+        assert_eq!(
+            decrypt_key_info(&[2, 0, 0, 0, 1, 2, 3, 4]).unwrap().len(),
+            8
+        );
+    }
+}
@@ -0,0 +1,396 @@
+use super::{
+    edit_distance, parse::html_to_text, DictResult, Explanation, Pronunciation, WebPhrase,
+};
+use crate::{config::get_or_array, lemmatize::WordForm};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+// 释义内容以词性开头，如 n. 苹果
+static LEADING_POS_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^([A-Za-z]{1,12})\.\s*(\S.*)$").unwrap());
+// 比较释义时忽略括号中的注释，如 （计）、[美]
+static BRACKET_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\([^)]*\)|（[^）]*）|\[[^\]]*\]|【[^】]*】|<[^>]*>").unwrap());
+
+/// 单个服务返回的查词结果
+#[derive(Debug, Clone, Deserialize)]
+pub struct SourceResult {
+    /// 服务名称，用于匹配优先级
+    pub source: String,
+    /// 显示名称，为空时使用 source
+    #[serde(default)]
+    pub label: String,
+    pub result: DictResult,
+}
+
+/// 合并结果中每一项的来源，与 DictResult 中对应列表的下标一致
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Provenance {
+    pub pronunciations: Vec<Vec<String>>,
+    /// explanations[i].explains[j] 的来源
+    pub explanations: Vec<Vec<Vec<String>>>,
+    pub sentence: Vec<Vec<String>>,
+    pub wfs: Vec<Vec<String>>,
+    pub web: Vec<Vec<String>>,
+    pub tags: Vec<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MergedDictResult {
+    #[serde(flatten)]
+    pub result: DictResult,
+    /// 参与合并的服务，按优先级排序
+    pub sources: Vec<String>,
+    pub provenance: Provenance,
+}
+
+struct Entry<T> {
+    value: T,
+    key: String,
+    sources: Vec<String>,
+}
+
+impl<T> Entry<T> {
+    fn new(value: T, key: String, source: &str) -> Self {
+        Entry {
+            value,
+            key,
+            sources: vec![source.to_string()],
+        }
+    }
+
+    fn add_source(&mut self, source: &str) {
+        if !self.sources.iter().any(|s| s == source) {
+            self.sources.push(source.to_string());
+        }
+    }
+}
+
+struct Group {
+    trait_: String,
+    explains: Vec<Entry<String>>,
+}
+
+/// 统一词性写法，如 noun、名词 统一为 n.，无法识别的保持原样
+fn normalize_trait(trait_: &str) -> String {
+    let trimmed = trait_.trim();
+    let key = trimmed.trim_end_matches(['.', '．']).to_lowercase();
+    let normalized = match key.as_str() {
+        "" => "",
+        "n" | "noun" | "名" | "名词" => "n.",
+        "v" | "verb" | "动" | "动词" => "v.",
+        "vt" | "及物动词" => "vt.",
+        "vi" | "不及物动词" => "vi.",
+        "a" | "adj" | "adjective" | "形" | "形容词" => "adj.",
+        "ad" | "adv" | "adverb" | "副" | "副词" => "adv.",
+        "prep" | "preposition" | "介" | "介词" => "prep.",
+        "conj" | "conjunction" | "连" | "连词" => "conj.",
+        "pron" | "pronoun" | "代" | "代词" => "pron.",
+        "int" | "interj" | "interjection" | "叹" | "感叹词" => "int.",
+        "num" | "numeral" | "数" | "数词" => "num.",
+        "art" | "article" | "冠词" => "art.",
+        "aux" | "auxiliary" | "助动词" => "aux.",
+        "abbr" | "abbreviation" | "缩写" => "abbr.",
+        "pl" | "plural" | "复数" => "pl.",
+        _ => return trimmed.to_string(),
+    };
+    normalized.to_string()
+}
+
+fn normalize_region(region: &str) -> String {
+    match region.trim().to_lowercase().as_str() {
+        "美" | "美式" | "us" | "american" => "美".to_string(),
+        "英" | "英式" | "uk" | "british" => "英".to_string(),
+        r => r.to_string(),
+    }
+}
+
+/// 比较用的键：去掉标签、括号注释、标点和空白，统一小写
+fn compare_key(text: &str) -> String {
+    let key: String = BRACKET_RE
+        .replace_all(text, "")
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect();
+    if key.is_empty() {
+        text.trim().to_lowercase()
+    } else {
+        key
+    }
+}
+
+/// 模糊匹配：编辑距离不超过较长一方长度的四分之一
+fn similar(a: &str, b: &str) -> bool {
+    if a == b {
+        return true;
+    }
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let max = a.len().max(b.len()) / 4;
+    max > 0 && edit_distance(&a, &b, max).is_some()
+}
+
+/// 拆分义项，英文释义中的逗号不作为分隔
+fn split_explain(text: &str) -> Vec<String> {
+    text.split(['；', ';'])
+        .flat_map(|part| {
+            if part.chars().any(|c| c.is_ascii_alphabetic()) {
+                vec![part]
+            } else {
+                part.split(['，', ',']).collect()
+            }
+        })
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+fn find_similar<T>(list: &[Entry<T>], key: &str) -> Option<usize> {
+    list.iter().position(|e| similar(&e.key, key))
+}
+
+fn add_explain(groups: &mut Vec<Group>, trait_: &str, explain: String, source: &str) {
+    let key = compare_key(&explain);
+    // 无词性的义项与所有词性比较，有词性时只与同词性比较
+    let found = groups
+        .iter_mut()
+        .filter(|g| trait_.is_empty() || g.trait_ == trait_)
+        .find_map(|g| find_similar(&g.explains, &key).map(|i| &mut g.explains[i]));
+    if let Some(entry) = found {
+        entry.add_source(source);
+        return;
+    }
+    let entry = Entry::new(explain, key, source);
+    match groups.iter_mut().find(|g| g.trait_ == trait_) {
+        Some(g) => g.explains.push(entry),
+        None => groups.push(Group {
+            trait_: trait_.to_string(),
+            explains: vec![entry],
+        }),
+    }
+}
+
+fn add_explanation(groups: &mut Vec<Group>, explanation: &Explanation, source: &str) {
+    let trait_ = normalize_trait(&explanation.trait_);
+    for text in &explanation.explains {
+        // 部分服务把词性放在释义内容中
+        let (trait_, text) = match LEADING_POS_RE.captures(text.trim()) {
+            Some(c) if trait_.is_empty() && normalize_trait(&c[1]).ends_with('.') => {
+                (normalize_trait(&c[1]), c[2].to_string())
+            }
+            _ => (trait_.clone(), text.to_string()),
+        };
+        for explain in split_explain(&text) {
+            add_explain(groups, &trait_, explain, source);
+        }
+    }
+}
+
+fn add_pronunciation(list: &mut Vec<Entry<Pronunciation>>, p: &Pronunciation, source: &str) {
+    let symbol = p.symbol.trim().trim_matches(['/', '[', ']']).trim();
+    if symbol.is_empty() && p.voice.as_deref().map_or(true, str::is_empty) {
+        return;
+    }
+    let region = normalize_region(&p.region);
+    // 部分词典用 ' 代替重音符号
+    let key: String = symbol
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(|c| if c == '\'' { 'ˈ' } else { c })
+        .collect();
+    let key = format!("{}|{}", region, key);
+    match list.iter_mut().find(|e| e.key == key) {
+        Some(e) => {
+            if e.value.voice.as_deref().map_or(true, str::is_empty) {
+                e.value.voice = p.voice.clone();
+            }
+            e.add_source(source);
+        }
+        None => list.push(Entry::new(
+            Pronunciation {
+                region,
+                symbol: symbol.to_string(),
+                voice: p.voice.clone(),
+            },
+            key,
+            source,
+        )),
+    }
+}
+
+/// 同名的词形合并为一项，多个值以 / 分隔
+fn add_word_form(list: &mut Vec<Entry<WordForm>>, wf: &WordForm, source: &str) {
+    let key = wf.name.trim().to_string();
+    let entry = match list.iter_mut().position(|e| e.key == key) {
+        Some(i) => {
+            list[i].add_source(source);
+            &mut list[i]
+        }
+        None => {
+            list.push(Entry::new(
+                WordForm {
+                    name: key.clone().into(),
+                    value: String::new(),
+                },
+                key,
+                source,
+            ));
+            list.last_mut().unwrap()
+        }
+    };
+    for value in wf.value.split('/').map(str::trim).filter(|v| !v.is_empty()) {
+        if !entry
+            .value
+            .value
+            .split('/')
+            .any(|v| v.eq_ignore_ascii_case(value))
+        {
+            if !entry.value.value.is_empty() {
+                entry.value.value.push('/');
+            }
+            entry.value.value.push_str(value);
+        }
+    }
+}
+
+fn add_sentence(list: &mut Vec<Entry<String>>, sentence: &str, source: &str) {
+    let key = compare_key(&html_to_text(sentence));
+    match find_similar(list, &key) {
+        Some(i) => list[i].add_source(source),
+        None => list.push(Entry::new(sentence.to_string(), key, source)),
+    }
+}
+
+fn add_web(list: &mut Vec<Entry<WebPhrase>>, phrase: &WebPhrase, source: &str) {
+    let key = phrase.name.trim().to_lowercase();
+    let entry = match list.iter_mut().position(|e| e.key == key) {
+        Some(i) => {
+            list[i].add_source(source);
+            &mut list[i]
+        }
+        None => {
+            list.push(Entry::new(
+                WebPhrase {
+                    name: phrase.name.trim().to_string(),
+                    list: Vec::new(),
+                },
+                key,
+                source,
+            ));
+            list.last_mut().unwrap()
+        }
+    };
+    for item in phrase
+        .list
+        .iter()
+        .map(|s| s.trim())
+        .filter(|s| !s.is_empty())
+    {
+        if !entry.value.list.iter().any(|s| s == item) {
+            entry.value.list.push(item.to_string());
+        }
+    }
+}
+
+fn add_tag(list: &mut Vec<Entry<String>>, tag: &str, source: &str) {
+    let tag = tag.trim();
+    if tag.is_empty() {
+        return;
+    }
+    match list.iter_mut().find(|e| e.key == tag) {
+        Some(e) => e.add_source(source),
+        None => list.push(Entry::new(tag.to_string(), tag.to_string(), source)),
+    }
+}
+
+fn unzip<T>(list: Vec<Entry<T>>) -> (Vec<T>, Vec<Vec<String>>) {
+    list.into_iter().map(|e| (e.value, e.sources)).unzip()
+}
+
+/// 按优先级合并多个服务的查词结果，优先级靠前的服务的内容排在前面，重复内容只记录来源
+pub fn merge(mut results: Vec<SourceResult>, priority: &[String]) -> Option<MergedDictResult> {
+    results.retain(|r| !r.result.text.trim().is_empty());
+    // 稳定排序，不在优先级列表中的服务保持原有顺序
+    results.sort_by_key(|r| {
+        priority
+            .iter()
+            .position(|p| *p == r.source || *p == r.label)
+            .unwrap_or(priority.len())
+    });
+    let first = results.first()?;
+
+    let mut merged = DictResult::new(first.result.text.trim(), "");
+    let mut sources: Vec<String> = Vec::new();
+    let mut pronunciations = Vec::new();
+    let mut groups: Vec<Group> = Vec::new();
+    let mut sentence = Vec::new();
+    let mut wfs = Vec::new();
+    let mut web = Vec::new();
+    let mut tags = Vec::new();
+
+    for item in &results {
+        let source = if item.label.is_empty() {
+            item.source.as_str()
+        } else {
+            item.label.as_str()
+        };
+        if !sources.iter().any(|s| s == source) {
+            sources.push(source.to_string());
+        }
+        let result = &item.result;
+        if merged.phonetic.is_none() {
+            merged.phonetic = result.phonetic.clone().filter(|p| !p.trim().is_empty());
+        }
+        for p in &result.pronunciations {
+            add_pronunciation(&mut pronunciations, p, source);
+        }
+        for explanation in &result.explanations {
+            add_explanation(&mut groups, explanation, source);
+        }
+        for s in &result.sentence {
+            add_sentence(&mut sentence, s, source);
+        }
+        for wf in &result.wfs {
+            add_word_form(&mut wfs, wf, source);
+        }
+        for phrase in &result.web {
+            add_web(&mut web, phrase, source);
+        }
+        for tag in &result.tags {
+            add_tag(&mut tags, tag, source);
+        }
+    }
+
+    let mut provenance = Provenance::default();
+    (merged.pronunciations, provenance.pronunciations) = unzip(pronunciations);
+    (merged.sentence, provenance.sentence) = unzip(sentence);
+    (merged.wfs, provenance.wfs) = unzip(wfs);
+    (merged.web, provenance.web) = unzip(web);
+    (merged.tags, provenance.tags) = unzip(tags);
+    for group in groups {
+        let (explains, sources) = unzip(group.explains);
+        merged.explanations.push(Explanation {
+            trait_: group.trait_,
+            explains,
+        });
+        provenance.explanations.push(sources);
+    }
+    merged.dict = sources.join("、");
+    Some(MergedDictResult {
+        result: merged,
+        sources,
+        provenance,
+    })
+}
+
+/// 合并多个词典服务的结果，未指定优先级时使用配置中的 dict_merge_priority
+#[tauri::command(async)]
+pub fn dict_merge(
+    results: Vec<SourceResult>,
+    priority: Option<Vec<String>>,
+) -> Result<MergedDictResult, String> {
+    let priority = priority.unwrap_or_else(|| get_or_array("dict_merge_priority"));
+    merge(results, &priority).ok_or_else(|| "没有可合并的词典结果".to_string())
+}
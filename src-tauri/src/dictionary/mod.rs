@@ -0,0 +1,535 @@
+pub mod ecdict;
+mod mdict;
+pub mod merge;
+mod parse;
+mod stardict;
+
+use crate::{global::BASE_PATH, lemmatize::WordForm};
+use base64::{engine::general_purpose, Engine as _};
+use log::{info, warn};
+use mdict::{MDict, LINK_PREFIX};
+use once_cell::sync::{Lazy, OnceCell};
+use serde::{Deserialize, Serialize};
+use stardict::StarDict;
+use std::{
+    collections::HashSet,
+    fs,
+    path::{Path, PathBuf},
+    sync::{Arc, RwLock},
+};
+
+pub static DICTIONARIES_DIR_NAME: &str = "dictionaries";
+
+// 查找词典文件的最大目录深度，词典一般放在单独的子目录中
+const MAX_SCAN_DEPTH: usize = 3;
+// 跟随 @@@LINK= 跳转的最大次数
+const MAX_LINK_DEPTH: usize = 3;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Pronunciation {
+    #[serde(default)]
+    pub region: String,
+    #[serde(default)]
+    pub symbol: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub voice: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Explanation {
+    #[serde(rename = "trait", default)]
+    pub trait_: String,
+    #[serde(default)]
+    pub explains: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebPhrase {
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub list: Vec<String>,
+}
+
+/// 与前端 IDictResult 结构一致的查词结果
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DictResult {
+    pub text: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub phonetic: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub pronunciations: Vec<Pronunciation>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub explanations: Vec<Explanation>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub sentence: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub wfs: Vec<WordForm>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub web: Vec<WebPhrase>,
+    /// 考试、词表标签，如 四级、雅思
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// 来源词典名称
+    pub dict: String,
+    /// MDict、StarDict HTML词条的原始内容
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub html: Option<String>,
+}
+
+impl DictResult {
+    pub fn new(text: &str, dict: &str) -> Self {
+        DictResult {
+            text: text.to_string(),
+            dict: dict.to_string(),
+            ..Default::default()
+        }
+    }
+}
+
+/// 索引中的一个词条，offset、size为释义数据的位置
+#[derive(Debug, Clone)]
+pub struct IndexEntry {
+    pub word: String,
+    pub lower: String,
+    pub offset: u64,
+    pub size: u64,
+}
+
+impl IndexEntry {
+    pub fn new(word: String, offset: u64, size: u64) -> Self {
+        IndexEntry {
+            lower: word.to_lowercase(),
+            word,
+            offset,
+            size,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DictKind {
+    StarDict,
+    MDict,
+}
+
+enum Source {
+    StarDict(StarDict),
+    MDict(MDict),
+}
+
+impl Source {
+    fn title(&self) -> &str {
+        match self {
+            Source::StarDict(d) => &d.name,
+            Source::MDict(d) => &d.name,
+        }
+    }
+
+    fn read(&self, entry: &IndexEntry) -> Result<Vec<u8>, String> {
+        match self {
+            Source::StarDict(d) => d.read(entry.offset, entry.size),
+            Source::MDict(d) => d.read(entry.offset, entry.size),
+        }
+    }
+}
+
+/// 已加载的词典，索引按小写词语排序，用于二分查找和前缀搜索
+struct Loaded {
+    source: Source,
+    entries: Vec<IndexEntry>,
+}
+
+impl Loaded {
+    fn range(&self, lower: &str) -> &[IndexEntry] {
+        let start = self.entries.partition_point(|e| e.lower.as_str() < lower);
+        let end = start + self.entries[start..].partition_point(|e| e.lower == lower);
+        &self.entries[start..end]
+    }
+
+    fn with_prefix(&self, prefix: &str) -> impl Iterator<Item = &IndexEntry> {
+        let start = self.entries.partition_point(|e| e.lower.as_str() < prefix);
+        let prefix = prefix.to_string();
+        self.entries[start..]
+            .iter()
+            .take_while(move |e| e.lower.starts_with(&prefix))
+    }
+}
+
+pub struct Dictionary {
+    /// 相对词典目录的路径（不含扩展名），作为词典的标识
+    pub name: String,
+    pub kind: DictKind,
+    pub path: PathBuf,
+    /// MDict的资源包（.mdd、.1.mdd ...）
+    resources: Vec<Arc<Dictionary>>,
+    /// 首次查询时才读取索引
+    loaded: OnceCell<Result<Loaded, String>>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DictInfo {
+    pub name: String,
+    /// 词典内记录的标题，加载前StarDict可读取.ifo，MDict为None
+    pub title: Option<String>,
+    pub kind: DictKind,
+    pub path: PathBuf,
+    pub loaded: bool,
+    pub word_count: Option<usize>,
+    pub error: Option<String>,
+}
+
+impl Dictionary {
+    fn new(name: String, kind: DictKind, path: PathBuf) -> Self {
+        Dictionary {
+            name,
+            kind,
+            path,
+            resources: Vec::new(),
+            loaded: OnceCell::new(),
+        }
+    }
+
+    fn load(&self) -> Result<&Loaded, String> {
+        self.loaded
+            .get_or_init(|| {
+                let start = std::time::Instant::now();
+                let (source, mut entries) = match self.kind {
+                    DictKind::StarDict => {
+                        StarDict::open(&self.path).map(|(d, e)| (Source::StarDict(d), e))?
+                    }
+                    DictKind::MDict => {
+                        MDict::open(&self.path).map(|(d, e)| (Source::MDict(d), e))?
+                    }
+                };
+                entries.sort_by(|a, b| a.lower.cmp(&b.lower));
+                info!(
+                    "dictionary {:?} loaded {} entries: {:?}",
+                    self.path,
+                    entries.len(),
+                    start.elapsed()
+                );
+                Ok(Loaded { source, entries })
+            })
+            .as_ref()
+            .map_err(|e| e.clone())
+    }
+
+    fn info(&self) -> DictInfo {
+        let loaded = self.loaded.get();
+        let title = match loaded {
+            Some(Ok(l)) => Some(l.source.title().to_string()),
+            _ if self.kind == DictKind::StarDict => stardict::read_ifo(&self.path)
+                .ok()
+                .and_then(|ifo| ifo.get("bookname").cloned()),
+            _ => None,
+        };
+        DictInfo {
+            name: self.name.clone(),
+            title,
+            kind: self.kind,
+            path: self.path.clone(),
+            loaded: matches!(loaded, Some(Ok(_))),
+            word_count: match loaded {
+                Some(Ok(l)) => Some(l.entries.len()),
+                _ => None,
+            },
+            error: match loaded {
+                Some(Err(e)) => Some(e.clone()),
+                _ => None,
+            },
+        }
+    }
+
+    /// 查询词语，先匹配大小写完全一致的词条
+    pub fn lookup(&self, word: &str) -> Result<Vec<DictResult>, String> {
+        let loaded = self.load()?;
+        let mut entries: Vec<&IndexEntry> = loaded.range(&word.to_lowercase()).iter().collect();
+        entries.sort_by_key(|e| e.word != word);
+        let mut results = Vec::new();
+        let mut seen = HashSet::new();
+        for entry in entries {
+            // StarDict的同义词与原词条指向同一释义
+            if !seen.insert(entry.offset) {
+                continue;
+            }
+            if let Some(result) = self.read_entry(loaded, entry, 0)? {
+                results.push(result);
+            }
+        }
+        Ok(results)
+    }
+
+    fn read_entry(
+        &self,
+        loaded: &Loaded,
+        entry: &IndexEntry,
+        depth: usize,
+    ) -> Result<Option<DictResult>, String> {
+        let data = loaded.source.read(entry)?;
+        match &loaded.source {
+            Source::StarDict(d) => Ok(Some(d.to_result(&entry.word, &data))),
+            Source::MDict(d) => {
+                let html = d.decode(&data);
+                if let Some(target) = html.trim().strip_prefix(LINK_PREFIX) {
+                    if depth >= MAX_LINK_DEPTH {
+                        return Ok(None);
+                    }
+                    let target = target.trim().to_lowercase();
+                    return match loaded.range(&target).first() {
+                        Some(t) => self.read_entry(loaded, t, depth + 1),
+                        None => Ok(None),
+                    };
+                }
+                Ok(Some(d.to_result(&entry.word, &data)))
+            }
+        }
+    }
+
+    /// 读取MDict资源包中的文件，路径不区分大小写和斜杠方向
+    pub fn resource(&self, path: &str) -> Result<Option<Vec<u8>>, String> {
+        let key = format!(
+            "\\{}",
+            path.replace('/', "\\")
+                .trim_start_matches('\\')
+                .to_lowercase()
+        );
+        for res in &self.resources {
+            let loaded = match res.load() {
+                Ok(l) => l,
+                Err(e) => {
+                    warn!("load resource {:?} error: {}", res.path, e);
+                    continue;
+                }
+            };
+            if let Some(entry) = loaded.range(&key).first() {
+                return loaded.source.read(entry).map(Some);
+            }
+        }
+        Ok(None)
+    }
+}
+
+fn dictionaries_dir() -> PathBuf {
+    BASE_PATH.get().unwrap().join(DICTIONARIES_DIR_NAME)
+}
+
+fn file_stem(path: &Path) -> String {
+    path.file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+/// 词典标识：相对词典目录的路径，不同子目录中的同名词典不会冲突
+fn dict_name(path: &Path) -> String {
+    let relative = path
+        .strip_prefix(dictionaries_dir())
+        .unwrap_or(path)
+        .with_extension("");
+    relative.to_string_lossy().replace('\\', "/")
+}
+
+fn scan_dir(dir: &Path, depth: usize, out: &mut Vec<Dictionary>) {
+    let mut paths: Vec<PathBuf> = match fs::read_dir(dir) {
+        Ok(dir) => dir.filter_map(|e| e.ok().map(|e| e.path())).collect(),
+        Err(_) => return,
+    };
+    paths.sort();
+    for path in &paths {
+        if path.is_dir() {
+            if depth < MAX_SCAN_DEPTH {
+                scan_dir(path, depth + 1, out);
+            }
+            continue;
+        }
+        let ext = match path.extension() {
+            Some(e) => e.to_string_lossy().to_lowercase(),
+            None => continue,
+        };
+        match ext.as_str() {
+            "ifo" => out.push(Dictionary::new(
+                dict_name(path),
+                DictKind::StarDict,
+                path.clone(),
+            )),
+            "mdx" => {
+                let name = file_stem(path);
+                let mut dict = Dictionary::new(dict_name(path), DictKind::MDict, path.clone());
+                // 资源包为同名的 .mdd 以及 .1.mdd、.2.mdd ...
+                dict.resources = paths
+                    .iter()
+                    .filter(|p| {
+                        p.extension()
+                            .map_or(false, |e| e.eq_ignore_ascii_case("mdd"))
+                            && (file_stem(p) == name
+                                || file_stem(p)
+                                    .strip_prefix(&format!("{}.", name))
+                                    .map_or(false, |n| n.parse::<u32>().is_ok()))
+                    })
+                    .map(|p| Arc::new(Dictionary::new(dict_name(p), DictKind::MDict, p.clone())))
+                    .collect();
+                out.push(dict);
+            }
+            _ => {}
+        }
+    }
+}
+
+fn scan() -> Vec<Arc<Dictionary>> {
+    let mut dicts = Vec::new();
+    scan_dir(&dictionaries_dir(), 0, &mut dicts);
+    dicts.into_iter().map(Arc::new).collect()
+}
+
+static DICTIONARIES: Lazy<RwLock<Vec<Arc<Dictionary>>>> = Lazy::new(|| RwLock::new(scan()));
+
+/// 获取词典列表，names为空时返回全部词典
+fn dictionaries(names: Option<&[String]>) -> Vec<Arc<Dictionary>> {
+    DICTIONARIES
+        .read()
+        .unwrap()
+        .iter()
+        .filter(|d| names.map_or(true, |n| n.is_empty() || n.contains(&d.name)))
+        .cloned()
+        .collect()
+}
+
+/// 编辑距离，超过max时提前结束
+fn edit_distance(a: &[char], b: &[char], max: usize) -> Option<usize> {
+    if a.len().abs_diff(b.len()) > max {
+        return None;
+    }
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.iter().enumerate() {
+        let mut cur = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let cost = if ca == cb { 0 } else { 1 };
+            cur[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(cur[j] + 1);
+        }
+        if cur.iter().min().map_or(true, |m| *m > max) {
+            return None;
+        }
+        prev = cur;
+    }
+    Some(prev[b.len()]).filter(|d| *d <= max)
+}
+
+/// 前缀搜索，按长度、字母顺序排序
+pub fn search_prefix(text: &str, limit: usize, names: Option<&[String]>) -> Vec<String> {
+    let prefix = text.trim().to_lowercase();
+    if prefix.is_empty() {
+        return Vec::new();
+    }
+    let mut words: Vec<String> = Vec::new();
+    let mut seen = HashSet::new();
+    for dict in dictionaries(names) {
+        let loaded = match dict.load() {
+            Ok(l) => l,
+            Err(_) => continue,
+        };
+        for entry in loaded.with_prefix(&prefix).take(limit) {
+            if seen.insert(entry.lower.clone()) {
+                words.push(entry.word.clone());
+            }
+        }
+    }
+    words.sort_by(|a, b| a.len().cmp(&b.len()).then_with(|| a.cmp(b)));
+    words.truncate(limit);
+    words
+}
+
+/// 模糊搜索，允许的编辑距离随词语长度增加
+pub fn search_fuzzy(text: &str, limit: usize, names: Option<&[String]>) -> Vec<String> {
+    let target: Vec<char> = text.trim().to_lowercase().chars().collect();
+    if target.is_empty() {
+        return Vec::new();
+    }
+    let max = match target.len() {
+        0..=4 => 1,
+        5..=8 => 2,
+        _ => 3,
+    };
+    let mut found: Vec<(usize, String)> = Vec::new();
+    let mut seen = HashSet::new();
+    for dict in dictionaries(names) {
+        let loaded = match dict.load() {
+            Ok(l) => l,
+            Err(_) => continue,
+        };
+        for entry in &loaded.entries {
+            if seen.contains(&entry.lower) {
+                continue;
+            }
+            let chars: Vec<char> = entry.lower.chars().collect();
+            if let Some(d) = edit_distance(&target, &chars, max) {
+                seen.insert(entry.lower.clone());
+                found.push((d, entry.word.clone()));
+            }
+        }
+    }
+    found.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.len().cmp(&b.1.len())));
+    found.into_iter().take(limit).map(|(_, w)| w).collect()
+}
+
+/// 在所有（或指定的）词典中查词，每个词典可能返回多个词条
+pub fn lookup_word(text: &str, names: Option<&[String]>) -> Vec<DictResult> {
+    let word = text.trim();
+    if word.is_empty() {
+        return Vec::new();
+    }
+    let mut results = Vec::new();
+    for dict in dictionaries(names) {
+        match dict.lookup(word) {
+            Ok(r) => results.extend(r),
+            Err(e) => warn!("lookup {} in {:?} error: {}", word, dict.path, e),
+        }
+    }
+    results
+}
+
+#[tauri::command]
+pub fn dict_list() -> Result<Vec<DictInfo>, ()> {
+    Ok(dictionaries(None).iter().map(|d| d.info()).collect())
+}
+
+#[tauri::command(async)]
+pub fn dict_lookup(text: &str, dicts: Option<Vec<String>>) -> Result<Vec<DictResult>, ()> {
+    Ok(lookup_word(text, dicts.as_deref()))
+}
+
+/// mode: prefix（默认）、fuzzy
+#[tauri::command(async)]
+pub fn dict_search(
+    text: &str,
+    mode: Option<&str>,
+    limit: Option<usize>,
+    dicts: Option<Vec<String>>,
+) -> Result<Vec<String>, ()> {
+    let limit = limit.unwrap_or(20);
+    Ok(match mode {
+        Some("fuzzy") => search_fuzzy(text, limit, dicts.as_deref()),
+        _ => search_prefix(text, limit, dicts.as_deref()),
+    })
+}
+
+/// 读取MDict词条中引用的图片、样式等资源，返回base64
+#[tauri::command(async)]
+pub fn dict_resource(dict: &str, path: &str) -> Result<String, String> {
+    let dict = dictionaries(None)
+        .into_iter()
+        .find(|d| d.name == dict)
+        .ok_or_else(|| format!("dictionary {} not found", dict))?;
+    match dict.resource(path)? {
+        Some(bytes) => Ok(general_purpose::STANDARD.encode(bytes)),
+        None => Err(format!("resource {} not found", path)),
+    }
+}
+
+/// 重新扫描词典目录，已加载的索引会被丢弃
+#[tauri::command(async)]
+pub fn dict_reload() -> Result<(), ()> {
+    *DICTIONARIES.write().unwrap() = scan();
+    Ok(())
+}
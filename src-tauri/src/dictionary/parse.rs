@@ -0,0 +1,110 @@
+use super::{DictResult, Explanation};
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+// 常见词性缩写，释义行以词性开头时作为 trait
+static POS_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"^(n|v|vt|vi|adj|a|adv|ad|prep|conj|pron|int|interj|num|art|aux|abbr|pl|phr|det|modal v)\.\s*(.*)$",
+    )
+    .unwrap()
+});
+// 音标：/.../ 或 [...]，不能太长，避免把普通的方括号内容当作音标
+static PHONETIC_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^(?:[A-Za-z]{2}\s*)?(/[^/\n]{1,40}/|\[[^\]\n]{1,40}\])$").unwrap());
+static TAG_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?s)<[^>]*>").unwrap());
+static BLOCK_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)<\s*(br|/p|/div|/li|/h\d|/tr|hr)\b[^>]*>").unwrap());
+static HIDDEN_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?is)<(script|style)\b.*?</(script|style)\s*>").unwrap());
+static ENTITY_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"&(#x?[0-9a-fA-F]+|\w+);").unwrap());
+
+pub fn decode_entities(text: &str) -> String {
+    ENTITY_RE
+        .replace_all(text, |c: &regex::Captures| {
+            let name = &c[1];
+            let ch = match name {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some(' '),
+                _ if name.starts_with("#x") || name.starts_with("#X") => {
+                    u32::from_str_radix(&name[2..], 16)
+                        .ok()
+                        .and_then(char::from_u32)
+                }
+                _ if name.starts_with('#') => name[1..].parse().ok().and_then(char::from_u32),
+                _ => None,
+            };
+            ch.map_or_else(|| c[0].to_string(), |c| c.to_string())
+        })
+        .to_string()
+}
+
+/// HTML转为纯文本，块级元素换行
+pub fn html_to_text(html: &str) -> String {
+    let text = HIDDEN_RE.replace_all(html, "");
+    let text = BLOCK_RE.replace_all(&text, "\n");
+    let text = TAG_RE.replace_all(&text, "");
+    decode_entities(&text)
+}
+
+/// 判断是否是例句：英文单词较多且以句末标点结尾，或以“例”开头
+fn is_example(line: &str) -> bool {
+    if line.starts_with('例') || line.starts_with("e.g.") {
+        return true;
+    }
+    let words = line
+        .split_whitespace()
+        .filter(|w| w.chars().any(|c| c.is_ascii_alphabetic()))
+        .count();
+    words >= 4
+        && line
+            .trim_end()
+            .chars()
+            .last()
+            .map_or(false, |c| ".!?。！？".contains(c))
+        && !POS_RE.is_match(line)
+}
+
+/// 从纯文本释义中提取音标、按词性分组的释义和例句，无法识别的行作为无词性的释义
+pub fn fill_from_text(result: &mut DictResult, text: &str) {
+    let mut explanations: Vec<Explanation> = Vec::new();
+    for line in text.lines().map(|l| l.trim()).filter(|l| !l.is_empty()) {
+        if line.eq_ignore_ascii_case(&result.text) {
+            continue;
+        }
+        if result.phonetic.is_none() && PHONETIC_RE.is_match(line) {
+            result.phonetic = Some(line.to_string());
+            continue;
+        }
+        if is_example(line) {
+            result.sentence.push(
+                line.trim_start_matches('例')
+                    .trim_start_matches(['：', ':'])
+                    .trim()
+                    .to_string(),
+            );
+            continue;
+        }
+        let (trait_, content) = match POS_RE.captures(line) {
+            Some(c) => (format!("{}.", &c[1]), c[2].to_string()),
+            None => (String::new(), line.to_string()),
+        };
+        let explains: Vec<String> = content
+            .split(['；', ';'])
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+            .collect();
+        if explains.is_empty() {
+            continue;
+        }
+        match explanations.iter_mut().find(|e| e.trait_ == trait_) {
+            Some(e) => e.explains.extend(explains),
+            None => explanations.push(Explanation { trait_, explains }),
+        }
+    }
+    result.explanations = explanations;
+}
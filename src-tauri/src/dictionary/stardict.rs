@@ -0,0 +1,354 @@
+use super::{parse, DictResult, IndexEntry};
+use flate2::{read::GzDecoder, Decompress, FlushDecompress};
+use std::{
+    collections::HashMap,
+    fs::{self, File},
+    io::{Read, Seek, SeekFrom},
+    path::{Path, PathBuf},
+};
+
+/// StarDict词典：.ifo为词典信息，.idx(.gz)为索引，.dict(.dz)为释义，可选的.syn为同义词索引
+pub struct StarDict {
+    pub name: String,
+    /// 每条释义的数据类型，未设置时每段数据前带有类型字符
+    same_type_sequence: Option<String>,
+    data: DictData,
+}
+
+enum DictData {
+    Plain(PathBuf),
+    DictZip(DictZip),
+    /// 没有分块信息的gzip无法随机读取，只能整个解压到内存
+    Memory(Vec<u8>),
+}
+
+/// dictzip格式：gzip扩展字段RA中记录了每个分块压缩后的大小，分块可单独解压，支持随机读取
+struct DictZip {
+    path: PathBuf,
+    chunk_len: usize,
+    /// 每个分块在文件中的开始位置和压缩后的大小
+    chunks: Vec<(u64, usize)>,
+}
+
+/// 读取.ifo中的 key=value
+pub fn read_ifo(path: &Path) -> Result<HashMap<String, String>, String> {
+    let content = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let mut lines = content.lines();
+    if !lines.next().map_or(false, |l| {
+        l.trim_start_matches('\u{feff}')
+            .starts_with("StarDict's dict ifo file")
+    }) {
+        return Err(format!("{:?} 不是StarDict词典信息文件", path));
+    }
+    Ok(lines
+        .filter_map(|l| l.split_once('='))
+        .map(|(k, v)| (k.trim().to_string(), v.trim().to_string()))
+        .collect())
+}
+
+/// 在.ifo同目录下查找同名的其它文件，优先使用未压缩的文件
+fn sibling(ifo: &Path, extensions: &[&str]) -> Option<PathBuf> {
+    let stem = ifo.file_stem()?.to_string_lossy().to_string();
+    let dir = ifo.parent()?;
+    extensions
+        .iter()
+        .map(|ext| dir.join(format!("{}.{}", stem, ext)))
+        .find(|p| p.is_file())
+}
+
+fn read_maybe_gz(path: &Path) -> Result<Vec<u8>, String> {
+    let bytes = fs::read(path).map_err(|e| e.to_string())?;
+    if path.extension().map_or(false, |e| e == "gz" || e == "dz") {
+        let mut out = Vec::new();
+        GzDecoder::new(bytes.as_slice())
+            .read_to_end(&mut out)
+            .map_err(|e| e.to_string())?;
+        return Ok(out);
+    }
+    Ok(bytes)
+}
+
+fn be_u32(bytes: &[u8]) -> u32 {
+    u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+}
+
+fn le_u16(bytes: &[u8]) -> usize {
+    u16::from_le_bytes([bytes[0], bytes[1]]) as usize
+}
+
+impl StarDict {
+    /// 打开词典并读取全部索引，返回的索引未排序
+    pub fn open(ifo_path: &Path) -> Result<(StarDict, Vec<IndexEntry>), String> {
+        let ifo = read_ifo(ifo_path)?;
+        let name = ifo.get("bookname").cloned().unwrap_or_else(|| {
+            ifo_path
+                .file_stem()
+                .map(|s| s.to_string_lossy().to_string())
+                .unwrap_or_default()
+        });
+        let offset_bits_64 = ifo.get("idxoffsetbits").map_or(false, |b| b == "64");
+        let idx_path = sibling(ifo_path, &["idx", "idx.gz"]).ok_or("缺少.idx文件")?;
+        let dict_path = sibling(ifo_path, &["dict", "dict.dz"]).ok_or("缺少.dict文件")?;
+
+        let idx = read_maybe_gz(&idx_path)?;
+        let offset_len = if offset_bits_64 { 8 } else { 4 };
+        let mut entries = Vec::new();
+        let mut i = 0;
+        while i < idx.len() {
+            let end = match idx[i..].iter().position(|b| *b == 0) {
+                Some(p) => i + p,
+                None => break,
+            };
+            if end + 1 + offset_len + 4 > idx.len() {
+                break;
+            }
+            let word = String::from_utf8_lossy(&idx[i..end]).to_string();
+            let mut p = end + 1;
+            let offset = if offset_bits_64 {
+                u64::from_be_bytes(idx[p..p + 8].try_into().unwrap())
+            } else {
+                be_u32(&idx[p..p + 4]) as u64
+            };
+            p += offset_len;
+            let size = be_u32(&idx[p..p + 4]) as u64;
+            entries.push(IndexEntry::new(word, offset, size));
+            i = p + 4;
+        }
+
+        // 同义词：词语\0 + 对应.idx中的序号
+        if let Some(syn_path) = sibling(ifo_path, &["syn", "syn.gz"]) {
+            let syn = read_maybe_gz(&syn_path)?;
+            let count = entries.len();
+            let mut i = 0;
+            while i < syn.len() {
+                let end = match syn[i..].iter().position(|b| *b == 0) {
+                    Some(p) => i + p,
+                    None => break,
+                };
+                if end + 5 > syn.len() {
+                    break;
+                }
+                let index = be_u32(&syn[end + 1..end + 5]) as usize;
+                if index < count {
+                    let word = String::from_utf8_lossy(&syn[i..end]).to_string();
+                    let (offset, size) = (entries[index].offset, entries[index].size);
+                    entries.push(IndexEntry::new(word, offset, size));
+                }
+                i = end + 5;
+            }
+        }
+
+        let data = if dict_path.extension().map_or(false, |e| e == "dz") {
+            match DictZip::open(&dict_path)? {
+                Some(dz) => DictData::DictZip(dz),
+                None => DictData::Memory(read_maybe_gz(&dict_path)?),
+            }
+        } else {
+            DictData::Plain(dict_path)
+        };
+        let dict = StarDict {
+            name,
+            same_type_sequence: ifo
+                .get("sametypesequence")
+                .filter(|s| !s.is_empty())
+                .cloned(),
+            data,
+        };
+        Ok((dict, entries))
+    }
+
+    pub fn read(&self, offset: u64, size: u64) -> Result<Vec<u8>, String> {
+        match &self.data {
+            DictData::Plain(path) => {
+                let mut file = File::open(path).map_err(|e| e.to_string())?;
+                file.seek(SeekFrom::Start(offset))
+                    .map_err(|e| e.to_string())?;
+                let mut buf = vec![0; size as usize];
+                file.read_exact(&mut buf).map_err(|e| e.to_string())?;
+                Ok(buf)
+            }
+            DictData::DictZip(dz) => dz.read(offset, size as usize),
+            DictData::Memory(data) => data
+                .get(offset as usize..(offset + size) as usize)
+                .map(|d| d.to_vec())
+                .ok_or_else(|| "释义位置超出词典范围".to_string()),
+        }
+    }
+
+    /// 按数据类型拆分释义：小写类型以\0结尾，大写类型前有4字节长度，最后一段不带结尾或长度
+    fn split_fields<'a>(&self, data: &'a [u8]) -> Vec<(char, &'a [u8])> {
+        let mut fields = Vec::new();
+        let mut i = 0;
+        let types: Vec<char> = match &self.same_type_sequence {
+            Some(seq) => seq.chars().collect(),
+            None => Vec::new(),
+        };
+        let mut n = 0;
+        while i < data.len() {
+            let (t, is_last) = if types.is_empty() {
+                let t = data[i] as char;
+                i += 1;
+                (t, false)
+            } else {
+                match types.get(n) {
+                    Some(t) => (*t, n == types.len() - 1),
+                    None => break,
+                }
+            };
+            n += 1;
+            if is_last {
+                fields.push((t, &data[i..]));
+                break;
+            }
+            if t.is_ascii_lowercase() {
+                let end = data[i..]
+                    .iter()
+                    .position(|b| *b == 0)
+                    .map_or(data.len(), |p| i + p);
+                fields.push((t, &data[i..end]));
+                i = end + 1;
+            } else {
+                if i + 4 > data.len() {
+                    break;
+                }
+                let len = be_u32(&data[i..i + 4]) as usize;
+                let end = (i + 4 + len).min(data.len());
+                fields.push((t, &data[i + 4..end]));
+                i = end;
+            }
+        }
+        fields
+    }
+
+    pub fn to_result(&self, word: &str, data: &[u8]) -> DictResult {
+        let mut result = DictResult::new(word, &self.name);
+        let mut text = String::new();
+        let mut html = String::new();
+        for (t, field) in self.split_fields(data) {
+            let content = String::from_utf8_lossy(field);
+            match t {
+                't' => result.phonetic = Some(content.trim().to_string()),
+                'm' | 'l' | 'y' => {
+                    text.push_str(&content);
+                    text.push('\n');
+                }
+                'h' | 'g' | 'x' | 'k' | 'w' => {
+                    html.push_str(&content);
+                    text.push_str(&parse::html_to_text(&content));
+                    text.push('\n');
+                }
+                // 图片、音频等资源不处理
+                _ => {}
+            }
+        }
+        parse::fill_from_text(&mut result, &text);
+        if !html.is_empty() {
+            result.html = Some(html);
+        }
+        result
+    }
+}
+
+/// 跳过以\0结尾的字段，返回跳过的字节数
+fn skip_zero_terminated(file: &mut File) -> Result<u64, String> {
+    let mut b = [0u8; 1];
+    let mut len = 0;
+    loop {
+        file.read_exact(&mut b).map_err(|e| e.to_string())?;
+        len += 1;
+        if b[0] == 0 {
+            return Ok(len);
+        }
+    }
+}
+
+impl DictZip {
+    fn open(path: &Path) -> Result<Option<DictZip>, String> {
+        let mut file = File::open(path).map_err(|e| e.to_string())?;
+        let mut head = [0u8; 12];
+        file.read_exact(&mut head).map_err(|e| e.to_string())?;
+        if head[0] != 0x1f || head[1] != 0x8b || head[3] & 0x04 == 0 {
+            return Ok(None);
+        }
+        let flags = head[3];
+        let xlen = le_u16(&head[10..12]);
+        let mut extra = vec![0; xlen];
+        file.read_exact(&mut extra).map_err(|e| e.to_string())?;
+        let mut pos = 12 + xlen as u64;
+
+        // 查找RA子字段
+        let mut ra = None;
+        let mut i = 0;
+        while i + 4 <= extra.len() {
+            let len = le_u16(&extra[i + 2..i + 4]);
+            if &extra[i..i + 2] == b"RA" && i + 4 + len <= extra.len() {
+                ra = Some(&extra[i + 4..i + 4 + len]);
+                break;
+            }
+            i += 4 + len;
+        }
+        let ra = match ra {
+            Some(ra) if ra.len() >= 6 => ra,
+            _ => return Ok(None),
+        };
+        let chunk_len = le_u16(&ra[2..4]);
+        let count = le_u16(&ra[4..6]);
+
+        // 跳过文件名、注释和头部校验
+        if flags & 0x08 != 0 {
+            pos += skip_zero_terminated(&mut file)?;
+        }
+        if flags & 0x10 != 0 {
+            pos += skip_zero_terminated(&mut file)?;
+        }
+        if flags & 0x02 != 0 {
+            pos += 2;
+        }
+
+        let mut chunks = Vec::with_capacity(count);
+        let mut offset = pos;
+        for n in 0..count {
+            let start = 6 + n * 2;
+            if start + 2 > ra.len() {
+                break;
+            }
+            let size = le_u16(&ra[start..start + 2]);
+            chunks.push((offset, size));
+            offset += size as u64;
+        }
+        Ok(Some(DictZip {
+            path: path.to_path_buf(),
+            chunk_len,
+            chunks,
+        }))
+    }
+
+    fn read(&self, offset: u64, size: usize) -> Result<Vec<u8>, String> {
+        if self.chunk_len == 0 {
+            return Err("dictzip分块大小为0".to_string());
+        }
+        let first = offset as usize / self.chunk_len;
+        let last = (offset as usize + size).saturating_sub(1) / self.chunk_len;
+        let mut file = File::open(&self.path).map_err(|e| e.to_string())?;
+        let mut out = Vec::with_capacity((last - first + 1) * self.chunk_len);
+        for n in first..=last {
+            let (start, len) = *self.chunks.get(n).ok_or("释义位置超出词典范围")?;
+            file.seek(SeekFrom::Start(start))
+                .map_err(|e| e.to_string())?;
+            let mut compressed = vec![0; len];
+            file.read_exact(&mut compressed)
+                .map_err(|e| e.to_string())?;
+            // 每个分块以完全刷新结束，可作为独立的raw deflate数据解压
+            let mut chunk = Vec::with_capacity(self.chunk_len);
+            Decompress::new(false)
+                .decompress_vec(&compressed, &mut chunk, FlushDecompress::Sync)
+                .map_err(|e| e.to_string())?;
+            out.extend_from_slice(&chunk);
+        }
+        let begin = offset as usize - first * self.chunk_len;
+        if begin + size > out.len() {
+            return Err("释义位置超出词典范围".to_string());
+        }
+        Ok(out[begin..begin + size].to_vec())
+    }
+}
@@ -0,0 +1,224 @@
+use log::{debug, info, warn};
+use once_cell::sync::Lazy;
+use std::{
+    io::{BufRead, BufReader, Read},
+    process::{Child, Command, Stdio},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+/// 意外退出后最多连续重启的次数，稳定运行超过 STABLE_SECS 秒后重新计数
+const MAX_RESTARTS: u32 = 3;
+const STABLE_SECS: u64 = 60;
+
+/// 本地翻译引擎的启动命令，如 libretranslate --host 127.0.0.1 --port 5000
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EngineSpec {
+    pub command: String,
+    pub args: Vec<String>,
+}
+
+impl EngineSpec {
+    pub fn new(command: &str, args: &str) -> Self {
+        EngineSpec {
+            command: command.trim().to_string(),
+            args: split_args(args),
+        }
+    }
+}
+
+struct Engine {
+    spec: EngineSpec,
+    child: Child,
+    generation: u64,
+    started: Instant,
+    restarts: u32,
+}
+
+static ENGINE: Lazy<Mutex<Option<Engine>>> = Lazy::new(|| Mutex::new(None));
+// 每次启动、停止引擎时递增，用于让旧的守护线程退出
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// 按空白拆分参数，双引号内的空白不拆分
+fn split_args(args: &str) -> Vec<String> {
+    let mut list = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+    let mut has_arg = false;
+    for c in args.chars() {
+        match c {
+            '"' => {
+                quoted = !quoted;
+                has_arg = true;
+            }
+            c if c.is_whitespace() && !quoted => {
+                if has_arg {
+                    list.push(std::mem::take(&mut current));
+                    has_arg = false;
+                }
+            }
+            c => {
+                current.push(c);
+                has_arg = true;
+            }
+        }
+    }
+    if has_arg {
+        list.push(current);
+    }
+    list
+}
+
+fn log_output(reader: impl Read) {
+    for line in BufReader::new(reader).lines().map_while(Result::ok) {
+        debug!("engine: {}", line);
+    }
+}
+
+fn spawn(spec: &EngineSpec, restarts: u32) -> Result<Engine, String> {
+    let mut command = Command::new(&spec.command);
+    command
+        .args(&spec.args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        // CREATE_NO_WINDOW，不显示控制台窗口
+        command.creation_flags(0x08000000);
+    }
+    let mut child = command
+        .spawn()
+        .map_err(|e| format!("启动本地翻译引擎失败：{}", e))?;
+    let generation = GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+    info!(
+        "engine started: {} {:?}, pid: {}",
+        spec.command,
+        spec.args,
+        child.id()
+    );
+    if let Some(stdout) = child.stdout.take() {
+        thread::spawn(move || log_output(stdout));
+    }
+    if let Some(stderr) = child.stderr.take() {
+        thread::spawn(move || log_output(stderr));
+    }
+    thread::spawn(move || supervise(generation));
+    Ok(Engine {
+        spec: spec.clone(),
+        child,
+        generation,
+        started: Instant::now(),
+        restarts,
+    })
+}
+
+/// 守护线程，引擎意外退出时按 1、2、4 秒的间隔重启
+fn supervise(generation: u64) {
+    let engine = loop {
+        thread::sleep(Duration::from_millis(500));
+        let mut guard = ENGINE.lock().unwrap();
+        let exited = match guard.as_mut() {
+            Some(engine) if engine.generation == generation => match engine.child.try_wait() {
+                Ok(None) => false,
+                Ok(Some(status)) => {
+                    warn!("engine exited: {}", status);
+                    true
+                }
+                Err(e) => {
+                    warn!("engine state error: {:?}", e);
+                    true
+                }
+            },
+            // 已停止或被新的进程替换
+            _ => return,
+        };
+        if exited {
+            break guard.take().unwrap();
+        }
+    };
+    let restarts = if engine.started.elapsed().as_secs() > STABLE_SECS {
+        0
+    } else {
+        engine.restarts
+    };
+    if restarts >= MAX_RESTARTS {
+        warn!("engine restarted {} times, give up", restarts);
+        return;
+    }
+    thread::sleep(Duration::from_secs(1 << restarts));
+    let mut guard = ENGINE.lock().unwrap();
+    // 等待期间手动启动或停止了引擎
+    if guard.is_some() || GENERATION.load(Ordering::SeqCst) != generation {
+        return;
+    }
+    debug!("restart engine, count: {}", restarts + 1);
+    match spawn(&engine.spec, restarts + 1) {
+        Ok(engine) => *guard = Some(engine),
+        Err(e) => warn!("{}", e),
+    }
+}
+
+/// 启动引擎，已使用相同命令运行时返回 false，命令不同时重启
+pub fn start_engine_worker(spec: &EngineSpec) -> Result<bool, String> {
+    if spec.command.is_empty() {
+        return Err("未设置本地翻译引擎的启动命令".to_string());
+    }
+    let mut guard = ENGINE.lock().unwrap();
+    if let Some(engine) = guard.as_ref() {
+        if engine.spec == *spec {
+            return Ok(false);
+        }
+    }
+    if let Some(engine) = guard.take() {
+        kill(engine);
+    }
+    *guard = Some(spawn(spec, 0)?);
+    Ok(true)
+}
+
+fn kill(mut engine: Engine) {
+    debug!("kill engine, pid: {}", engine.child.id());
+    if let Err(e) = engine.child.kill() {
+        debug!("kill engine error: {:?}", e);
+    }
+    let _ = engine.child.wait();
+}
+
+pub fn stop_engine_worker() {
+    let mut guard = ENGINE.lock().unwrap();
+    GENERATION.fetch_add(1, Ordering::SeqCst);
+    if let Some(engine) = guard.take() {
+        kill(engine);
+    }
+}
+
+/// 当前引擎进程的序号，重启后变化
+pub fn generation() -> u64 {
+    GENERATION.load(Ordering::SeqCst)
+}
+
+pub fn is_engine_running() -> bool {
+    ENGINE.lock().unwrap().is_some()
+}
+
+#[tauri::command]
+pub fn start_engine(command: String, args: String) -> Result<bool, String> {
+    start_engine_worker(&EngineSpec::new(&command, &args))
+}
+
+#[tauri::command]
+pub fn stop_engine() -> Result<(), ()> {
+    stop_engine_worker();
+    Ok(())
+}
+
+#[tauri::command]
+pub fn engine_state() -> Result<bool, ()> {
+    Ok(is_engine_running())
+}
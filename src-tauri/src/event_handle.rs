@@ -1,5 +1,12 @@
 use crate::{
-    config::get_or_bool, get_selected_text::get_selected_text, global::*, hotkey, window::*,
+    config::get_or_bool,
+    engine::stop_engine_worker,
+    get_selected_text::get_selected_text,
+    global::*,
+    hotkey,
+    prompt::{active_app, invoke_preset, PROMPT_PREFIX},
+    trans_cache, translator, tray, vocabulary,
+    window::*,
 };
 use base64::{engine::general_purpose, Engine as _};
 use log::debug;
@@ -23,9 +30,26 @@ fn handlle_hotkey_name_event(name: &str) {
                 if selected_text.len() < 3 {
                     return;
                 }
+                vocabulary::remember_source(&selected_text, active_app());
                 emit_to(TRANSLATOR_LABEL, "translator://text", selected_text);
             });
         }
+        NAMING => {
+            std::thread::spawn(move || {
+                let selected_text = get_selected_text(true).unwrap_or_default();
+                if selected_text.trim().is_empty() {
+                    return;
+                }
+                emit_to(TRANSLATOR_LABEL, "translator://naming", selected_text);
+            });
+        }
+        name if name.starts_with(PROMPT_PREFIX) => {
+            let id = name[PROMPT_PREFIX.len()..].to_string();
+            std::thread::spawn(move || {
+                let selected_text = get_selected_text(true).unwrap_or_default();
+                invoke_preset(&id, selected_text);
+            });
+        }
         _ => {}
     }
 }
@@ -101,9 +125,12 @@ pub fn tray_event_handler(app: &AppHandle, event: SystemTrayEvent) {
                 show_setting_window();
             }
             "relaunch" => {
+                // 先结束离线翻译引擎，避免旧进程占用端口导致新实例的引擎无法启动
+                stop_engine_worker();
                 app.restart();
             }
             "quit" => {
+                stop_engine_worker();
                 std::process::exit(0);
             }
             name => {
@@ -140,8 +167,28 @@ pub fn handle_ahk_event(line: String) {
 
 pub fn handle_config_change(key: String, value: serde_json::Value) {
     match key.as_str() {
-        SHOW_TRANSLATOR | SCREENSHOT_TRANSLATE | SELECTION_TRANSLATE | SCREENSHOT_RECOGNIZER => {
+        SHOW_TRANSLATOR
+        | SCREENSHOT_TRANSLATE
+        | SELECTION_TRANSLATE
+        | SCREENSHOT_RECOGNIZER
+        | NAMING => {
+            hotkey::init_hotkey();
+        }
+        "trans_services" => {
+            // 服务参数变化后旧的翻译缓存不再可用
+            std::thread::spawn(trans_cache::invalidate);
+            // 按新的配置启动或重启本地引擎，没有启用的服务使用引擎时停止
+            std::thread::spawn(|| {
+                if !translator::libre::start_engines() {
+                    stop_engine_worker();
+                }
+            });
+        }
+        "prompt_presets" => {
             hotkey::init_hotkey();
+            if let Some(app) = APP.get() {
+                tray::generate_tray(app.clone());
+            }
         }
         _ => {}
     }
@@ -0,0 +1,628 @@
+use crate::{
+    global::{BASE_PATH, IDENTIFIER},
+    glossary::escape_xml,
+    history,
+    vocabulary::{self, definition, phonetic},
+};
+use rusqlite::{params, Connection};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use sha1::Sha1;
+use sha2::{Digest, Sha256};
+use std::{
+    collections::HashSet,
+    fs,
+    io::Write,
+    path::Path,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// 可导出的列，CSV、TSV 未指定列时导出全部
+pub const COLUMNS: [&str; 10] = [
+    "time", "text", "result", "phonetic", "example", "context", "app", "service", "from", "to",
+];
+/// Anki 笔记类型及牌组的 id，固定后重复导入时会更新已有的笔记
+const ANKI_MODEL_ID: i64 = 1712745600000;
+const ANKI_DECK_ID: i64 = 1712745600001;
+const ANKI_FIELDS: [&str; 6] = [
+    "Word",
+    "Phonetic",
+    "Definitions",
+    "Example",
+    "Context",
+    "Source",
+];
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ExportOptions {
+    /// vocabulary 或 history
+    pub source: String,
+    /// apkg、csv、tsv 或 md，为空时按文件扩展名
+    pub format: String,
+    /// 毫秒时间戳，包含
+    pub since: Option<i64>,
+    /// 毫秒时间戳，不包含
+    pub until: Option<i64>,
+    /// 服务名称或显示名称，只用于历史记录
+    pub service: String,
+    pub from: String,
+    pub to: String,
+    pub columns: Vec<String>,
+    /// 只导出生词本中的单词
+    pub starred: bool,
+}
+
+/// 导出的一行，生词的 result 为释义
+#[derive(Debug, Clone, Default)]
+struct Entry {
+    /// 历史记录为同一次翻译的 group_id，相同的分组在 Markdown 中合并显示
+    group: String,
+    time: i64,
+    text: String,
+    result: String,
+    phonetic: String,
+    example: String,
+    context: String,
+    app: String,
+    service: String,
+    from: String,
+    to: String,
+}
+
+impl Entry {
+    fn column(&self, name: &str, local: &(String, String)) -> String {
+        match name {
+            "time" => format!("{} {}", local.0, local.1),
+            "text" => self.text.clone(),
+            "result" => self.result.clone(),
+            "phonetic" => self.phonetic.clone(),
+            "example" => self.example.clone(),
+            "context" => self.context.clone(),
+            "app" => self.app.clone(),
+            "service" => self.service.clone(),
+            "from" => self.from.clone(),
+            "to" => self.to.clone(),
+            _ => String::new(),
+        }
+    }
+}
+
+fn now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or_default()
+}
+
+/// 词典结果中的第一个例句
+fn example(result: &Value) -> String {
+    result
+        .get("sentence")
+        .and_then(Value::as_array)
+        .and_then(|list| {
+            list.iter()
+                .filter_map(Value::as_str)
+                .find(|s| !s.is_empty())
+        })
+        .unwrap_or_default()
+        .to_string()
+}
+
+fn entries(options: &ExportOptions) -> Result<Vec<Entry>, String> {
+    let since = options.since.unwrap_or(0);
+    let until = options.until.unwrap_or(i64::MAX);
+    if options.source == "history" {
+        let records = history::query(
+            "translate",
+            since,
+            until,
+            &options.service,
+            &options.from,
+            &options.to,
+        )?;
+        return Ok(records
+            .into_iter()
+            .map(|r| Entry {
+                group: r.group_id,
+                time: r.timestamp,
+                result: match &r.result {
+                    Value::String(s) => s.clone(),
+                    other => definition(other),
+                },
+                phonetic: phonetic(&r.result),
+                example: example(&r.result),
+                service: if r.label.is_empty() {
+                    r.service
+                } else {
+                    r.label
+                },
+                text: r.text,
+                from: r.from,
+                to: r.to,
+                ..Default::default()
+            })
+            .collect());
+    }
+    let words = vocabulary::query(since, until, &options.from, &options.to, options.starred)?;
+    Ok(words
+        .into_iter()
+        .map(|w| Entry {
+            group: w.id.to_string(),
+            time: w.created,
+            example: example(&w.result),
+            text: w.word,
+            result: w.definition,
+            phonetic: w.phonetic,
+            context: w.context,
+            app: w.source_app,
+            from: w.from,
+            to: w.to,
+            ..Default::default()
+        })
+        .collect())
+}
+
+/// 本地时间的日期及时分，使用 SQLite 的时间函数转换
+fn local_times(entries: &[Entry]) -> Result<Vec<(String, String)>, String> {
+    let convert = || -> rusqlite::Result<Vec<(String, String)>> {
+        let conn = Connection::open_in_memory()?;
+        let mut stmt = conn.prepare(
+            "SELECT date(?1 / 1000, 'unixepoch', 'localtime'), strftime('%H:%M', ?1 / 1000, 'unixepoch', 'localtime')",
+        )?;
+        entries
+            .iter()
+            .map(|e| stmt.query_row([e.time], |r| Ok((r.get(0)?, r.get(1)?))))
+            .collect()
+    };
+    convert().map_err(|e| format!("转换时间失败：{}", e))
+}
+
+/// 本地日期 YYYY-MM-DD 的零点，返回毫秒时间戳
+pub fn parse_date(date: &str) -> Result<i64, String> {
+    let seconds: Option<i64> = Connection::open_in_memory()
+        .and_then(|conn| {
+            conn.query_row(
+                "SELECT CAST(strftime('%s', date(?1), 'utc') AS INTEGER)",
+                [date.trim()],
+                |r| r.get(0),
+            )
+        })
+        .map_err(|e| e.to_string())?;
+    seconds
+        .map(|s| s * 1000)
+        .ok_or_else(|| format!("日期格式错误：{}，应为 YYYY-MM-DD", date))
+}
+
+fn export_delimited(
+    entries: &[Entry],
+    columns: &[String],
+    delimiter: u8,
+) -> Result<String, String> {
+    let columns: Vec<&str> = if columns.is_empty() {
+        COLUMNS.to_vec()
+    } else {
+        columns.iter().map(|c| c.as_str()).collect()
+    };
+    if let Some(c) = columns.iter().find(|c| !COLUMNS.contains(c)) {
+        return Err(format!("不支持的列：{}，可选：{}", c, COLUMNS.join(",")));
+    }
+    let times = local_times(entries)?;
+    let mut writer = csv::WriterBuilder::new()
+        .delimiter(delimiter)
+        .from_writer(Vec::new());
+    let mut write = |row: &[String]| writer.write_record(row).map_err(|e| e.to_string());
+    write(&columns.iter().map(|c| c.to_string()).collect::<Vec<_>>())?;
+    for (entry, local) in entries.iter().zip(&times) {
+        write(
+            &columns
+                .iter()
+                .map(|c| entry.column(c, local))
+                .collect::<Vec<_>>(),
+        )?;
+    }
+    let data = writer.into_inner().map_err(|e| e.to_string())?;
+    String::from_utf8(data).map_err(|e| e.to_string())
+}
+
+fn quote(text: &str) -> String {
+    text.trim()
+        .lines()
+        .map(|l| format!("> {}", l).trim_end().to_string())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// 按天分组的双语记录，历史记录中同一次翻译的各个服务结果合并显示
+fn export_markdown(entries: &[Entry], source: &str) -> Result<String, String> {
+    let times = local_times(entries)?;
+    let title = if source == "history" {
+        "Tosa 翻译记录"
+    } else {
+        "Tosa 生词本"
+    };
+    let mut md = format!("# {}\n", title);
+    let mut day = "";
+    let mut group = "";
+    for (entry, (date, time)) in entries.iter().zip(&times) {
+        if day != date {
+            day = date;
+            group = "";
+            md.push_str(&format!("\n## {}\n", date));
+        }
+        if source == "history" {
+            if group != entry.group {
+                group = &entry.group;
+                md.push_str(&format!(
+                    "\n### {} · {} → {}\n\n{}\n",
+                    time,
+                    entry.from,
+                    entry.to,
+                    quote(&entry.text)
+                ));
+            }
+            md.push_str(&format!(
+                "\n**{}**\n\n{}\n",
+                entry.service,
+                entry.result.trim()
+            ));
+            continue;
+        }
+        md.push_str(&format!("\n### {}", entry.text));
+        if !entry.phonetic.is_empty() {
+            md.push_str(&format!(" /{}/", entry.phonetic));
+        }
+        md.push_str(&format!(
+            "\n\n{}\n",
+            entry.result.trim().replace('\n', "  \n")
+        ));
+        if !entry.context.is_empty() {
+            md.push_str(&format!("\n{}\n", quote(&entry.context)));
+        }
+    }
+    Ok(md)
+}
+
+fn html(text: &str) -> String {
+    escape_xml(text.trim()).replace('\n', "<br>")
+}
+
+/// 与 Anki 相同的校验和：首字段 sha1 的前8位十六进制
+fn anki_checksum(field: &str) -> i64 {
+    let hash = Sha1::digest(field.as_bytes());
+    i64::from(u32::from_be_bytes([hash[0], hash[1], hash[2], hash[3]]))
+}
+
+fn anki_guid(entry: &Entry) -> String {
+    let mut hasher = Sha256::new();
+    for field in [&entry.text, &entry.from, &entry.to, &entry.service] {
+        hasher.update((field.len() as u64).to_le_bytes());
+        hasher.update(field.as_bytes());
+    }
+    format!("{:x}", hasher.finalize())[..20].to_string()
+}
+
+fn anki_models(deck_name: &str, time: i64) -> (Value, Value) {
+    let fields: Vec<Value> = ANKI_FIELDS
+        .iter()
+        .enumerate()
+        .map(|(i, name)| {
+            json!({"name": name, "ord": i, "sticky": false, "rtl": false, "font": "Arial", "size": 20, "media": []})
+        })
+        .collect();
+    let models = json!({
+        ANKI_MODEL_ID.to_string(): {
+            "id": ANKI_MODEL_ID,
+            "name": "Tosa 单词",
+            "type": 0,
+            "mod": time / 1000,
+            "usn": -1,
+            "sortf": 0,
+            "did": ANKI_DECK_ID,
+            "tmpls": [{
+                "name": "Card 1",
+                "ord": 0,
+                "qfmt": "<div class=\"word\">{{Word}}</div>{{#Phonetic}}<div class=\"phonetic\">/{{Phonetic}}/</div>{{/Phonetic}}",
+                "afmt": "{{FrontSide}}<hr id=\"answer\"><div class=\"definitions\">{{Definitions}}</div>\
+                         {{#Example}}<div class=\"example\">{{Example}}</div>{{/Example}}\
+                         {{#Context}}<div class=\"context\">{{Context}}</div>{{/Context}}\
+                         {{#Source}}<div class=\"source\">{{Source}}</div>{{/Source}}",
+                "did": null,
+                "bqfmt": "",
+                "bafmt": ""
+            }],
+            "flds": fields,
+            "css": ".card { font-family: arial; font-size: 20px; text-align: center; color: black; background-color: white; }\n\
+                    .word { font-size: 32px; font-weight: bold; }\n\
+                    .phonetic, .source { color: #888; }\n\
+                    .definitions { text-align: left; }\n\
+                    .example, .context { margin-top: 12px; font-style: italic; text-align: left; }",
+            "latexPre": "\\documentclass[12pt]{article}\n\\special{papersize=3in,5in}\n\\usepackage{amssymb,amsmath}\n\\pagestyle{empty}\n\\setlength{\\parindent}{0in}\n\\begin{document}\n",
+            "latexPost": "\\end{document}",
+            "latexsvg": false,
+            "req": [[0, "any", [0]]],
+            "tags": [],
+            "vers": []
+        }
+    });
+    let deck = |id: i64, name: &str| {
+        json!({
+            "id": id, "name": name, "mod": time / 1000, "usn": -1, "desc": "", "dyn": 0, "conf": 1,
+            "collapsed": false, "extendNew": 10, "extendRev": 50,
+            "lrnToday": [0, 0], "revToday": [0, 0], "newToday": [0, 0], "timeToday": [0, 0]
+        })
+    };
+    let decks = json!({
+        "1": deck(1, "Default"),
+        ANKI_DECK_ID.to_string(): deck(ANKI_DECK_ID, deck_name),
+    });
+    (models, decks)
+}
+
+/// 生成 Anki 2.1 的 collection.anki2 数据库，每条记录一个笔记和一张新卡片
+fn build_collection(path: &Path, entries: &[Entry], deck_name: &str) -> rusqlite::Result<usize> {
+    let time = now();
+    let (models, decks) = anki_models(deck_name, time);
+    let conf = json!({
+        "activeDecks": [1], "curDeck": 1, "newSpread": 0, "collapseTime": 1200, "timeLim": 0,
+        "estTimes": true, "dueCounts": true, "curModel": ANKI_MODEL_ID.to_string(), "nextPos": entries.len() + 1,
+        "sortType": "noteFld", "sortBackwards": false, "addToCur": true
+    });
+    let dconf = json!({
+        "1": {
+            "id": 1, "name": "Default", "mod": 0, "usn": 0, "maxTaken": 60, "autoplay": true, "timer": 0,
+            "replayq": true, "dyn": false,
+            "new": {"delays": [1, 10], "ints": [1, 4, 7], "initialFactor": 2500, "order": 1, "perDay": 20, "bury": true, "separate": true},
+            "lapse": {"delays": [10], "mult": 0, "minInt": 1, "leechFails": 8, "leechAction": 0},
+            "rev": {"perDay": 100, "ease4": 1.3, "fuzz": 0.05, "ivlFct": 1, "maxIvl": 36500, "bury": true, "minSpace": 1}
+        }
+    });
+    let mut conn = Connection::open(path)?;
+    conn.execute_batch(
+        "CREATE TABLE col (
+             id INTEGER PRIMARY KEY, crt INTEGER NOT NULL, mod INTEGER NOT NULL, scm INTEGER NOT NULL,
+             ver INTEGER NOT NULL, dty INTEGER NOT NULL, usn INTEGER NOT NULL, ls INTEGER NOT NULL,
+             conf TEXT NOT NULL, models TEXT NOT NULL, decks TEXT NOT NULL, dconf TEXT NOT NULL, tags TEXT NOT NULL
+         );
+         CREATE TABLE notes (
+             id INTEGER PRIMARY KEY, guid TEXT NOT NULL, mid INTEGER NOT NULL, mod INTEGER NOT NULL,
+             usn INTEGER NOT NULL, tags TEXT NOT NULL, flds TEXT NOT NULL, sfld INTEGER NOT NULL,
+             csum INTEGER NOT NULL, flags INTEGER NOT NULL, data TEXT NOT NULL
+         );
+         CREATE TABLE cards (
+             id INTEGER PRIMARY KEY, nid INTEGER NOT NULL, did INTEGER NOT NULL, ord INTEGER NOT NULL,
+             mod INTEGER NOT NULL, usn INTEGER NOT NULL, type INTEGER NOT NULL, queue INTEGER NOT NULL,
+             due INTEGER NOT NULL, ivl INTEGER NOT NULL, factor INTEGER NOT NULL, reps INTEGER NOT NULL,
+             lapses INTEGER NOT NULL, left INTEGER NOT NULL, odue INTEGER NOT NULL, odid INTEGER NOT NULL,
+             flags INTEGER NOT NULL, data TEXT NOT NULL
+         );
+         CREATE TABLE revlog (
+             id INTEGER PRIMARY KEY, cid INTEGER NOT NULL, usn INTEGER NOT NULL, ease INTEGER NOT NULL,
+             ivl INTEGER NOT NULL, lastIvl INTEGER NOT NULL, factor INTEGER NOT NULL, time INTEGER NOT NULL,
+             type INTEGER NOT NULL
+         );
+         CREATE TABLE graves (usn INTEGER NOT NULL, oid INTEGER NOT NULL, type INTEGER NOT NULL);
+         CREATE INDEX ix_notes_usn ON notes (usn);
+         CREATE INDEX ix_cards_usn ON cards (usn);
+         CREATE INDEX ix_revlog_usn ON revlog (usn);
+         CREATE INDEX ix_cards_nid ON cards (nid);
+         CREATE INDEX ix_cards_sched ON cards (did, queue, due);
+         CREATE INDEX ix_revlog_cid ON revlog (cid);
+         CREATE INDEX ix_notes_csum ON notes (csum);",
+    )?;
+    let tx = conn.transaction()?;
+    tx.execute(
+        "INSERT INTO col VALUES (1, ?1, ?2, ?2, 11, 0, 0, 0, ?3, ?4, ?5, ?6, '{}')",
+        params![
+            time / 1000,
+            time,
+            conf.to_string(),
+            models.to_string(),
+            decks.to_string(),
+            dconf.to_string()
+        ],
+    )?;
+    // 相同的单词只保留最后一条
+    let mut seen = HashSet::new();
+    let mut count = 0;
+    for entry in entries.iter().rev() {
+        let guid = anki_guid(entry);
+        if !seen.insert(guid.clone()) {
+            continue;
+        }
+        let source = [entry.app.as_str(), entry.service.as_str()]
+            .into_iter()
+            .filter(|s| !s.is_empty())
+            .collect::<Vec<_>>()
+            .join(" · ");
+        let fields = [
+            html(&entry.text),
+            html(&entry.phonetic),
+            html(&entry.result),
+            html(&entry.example),
+            html(&entry.context),
+            html(&source),
+        ];
+        let id = time + count as i64;
+        tx.execute(
+            "INSERT INTO notes VALUES (?1, ?2, ?3, ?4, -1, '', ?5, ?6, ?7, 0, '')",
+            params![
+                id,
+                guid,
+                ANKI_MODEL_ID,
+                time / 1000,
+                fields.join("\x1f"),
+                entry.text.trim(),
+                anki_checksum(entry.text.trim())
+            ],
+        )?;
+        tx.execute(
+            "INSERT INTO cards VALUES (?1, ?1, ?2, 0, ?3, -1, 0, 0, ?4, 0, 0, 0, 0, 0, 0, 0, 0, '')",
+            params![id, ANKI_DECK_ID, time / 1000, (entries.len() - count) as i64],
+        )?;
+        count += 1;
+    }
+    tx.commit()?;
+    Ok(count)
+}
+
+/// Anki 包为 zip 文件，包含 collection.anki2 及媒体文件列表
+fn export_apkg(path: &Path, entries: &[Entry], source: &str) -> Result<usize, String> {
+    let deck_name = if source == "history" {
+        "Tosa 翻译记录"
+    } else {
+        "Tosa 生词本"
+    };
+    let collection = std::env::temp_dir().join(format!("tosa-{}.anki2", now()));
+    let res = build_collection(&collection, entries, deck_name)
+        .map_err(|e| format!("生成 Anki 数据库失败：{}", e))
+        .and_then(|count| {
+            let data = fs::read(&collection).map_err(|e| format!("读取 Anki 数据库失败：{}", e))?;
+            let file = fs::File::create(path).map_err(|e| format!("写入文件失败：{}", e))?;
+            let mut zip = zip::ZipWriter::new(file);
+            let options = zip::write::FileOptions::default()
+                .compression_method(zip::CompressionMethod::Deflated);
+            let mut write = |name: &str, data: &[u8]| -> zip::result::ZipResult<()> {
+                zip.start_file(name, options)?;
+                zip.write_all(data)?;
+                Ok(())
+            };
+            write("collection.anki2", &data)
+                .and_then(|_| write("media", b"{}"))
+                .and_then(|_| zip.finish().map(|_| ()))
+                .map_err(|e| format!("写入文件失败：{}", e))?;
+            Ok(count)
+        });
+    let _ = fs::remove_file(&collection);
+    res
+}
+
+fn format_of(path: &Path, format: &str) -> String {
+    if !format.is_empty() {
+        return format.to_lowercase();
+    }
+    match path
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or_default()
+        .to_lowercase()
+        .as_str()
+    {
+        "markdown" => "md".to_string(),
+        ext => ext.to_string(),
+    }
+}
+
+/// 按格式导出生词或历史记录，返回导出的数量
+pub fn export_file(path: &Path, options: &ExportOptions) -> Result<usize, String> {
+    let format = format_of(path, &options.format);
+    if !["apkg", "csv", "tsv", "md"].contains(&format.as_str()) {
+        return Err(format!("不支持的导出格式：{}", format));
+    }
+    let entries = entries(options)?;
+    if entries.is_empty() {
+        return Err("没有符合条件的记录".to_string());
+    }
+    let content = match format.as_str() {
+        "apkg" => return export_apkg(path, &entries, &options.source),
+        "tsv" => export_delimited(&entries, &options.columns, b'\t')?,
+        "md" => export_markdown(&entries, &options.source)?,
+        _ => export_delimited(&entries, &options.columns, b',')?,
+    };
+    fs::write(path, content).map_err(|e| format!("写入文件失败：{}", e))?;
+    Ok(entries.len())
+}
+
+const CLI_USAGE: &str = "用法：tosa export --output <文件> [选项]
+  --source <vocabulary|history>   导出生词本或历史记录，默认 vocabulary
+  --format <apkg|csv|tsv|md>      默认按文件扩展名
+  --since <YYYY-MM-DD>            开始日期（包含）
+  --until <YYYY-MM-DD>            结束日期（包含）
+  --service <名称>                只导出指定服务的历史记录
+  --from <语种> --to <语种>        语种对
+  --columns <列,...>              CSV、TSV 的列：time,text,result,phonetic,example,context,app,service,from,to
+  --starred                       只导出生词本中的单词";
+
+fn parse_cli(args: &[String]) -> Result<(String, ExportOptions), String> {
+    let mut output = String::new();
+    let mut options = ExportOptions {
+        source: "vocabulary".to_string(),
+        ..Default::default()
+    };
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        if arg == "--starred" {
+            options.starred = true;
+            continue;
+        }
+        let mut value = || {
+            iter.next()
+                .cloned()
+                .ok_or_else(|| format!("缺少参数 {} 的值", arg))
+        };
+        match arg.as_str() {
+            "--output" | "-o" => output = value()?,
+            "--source" => options.source = value()?,
+            "--format" => options.format = value()?,
+            "--since" => options.since = Some(parse_date(&value()?)?),
+            // 结束日期包含当天
+            "--until" => options.until = Some(parse_date(&value()?)? + 24 * 60 * 60 * 1000),
+            "--service" => options.service = value()?,
+            "--from" => options.from = value()?,
+            "--to" => options.to = value()?,
+            "--columns" => {
+                options.columns = value()?
+                    .split(',')
+                    .map(|c| c.trim().to_string())
+                    .filter(|c| !c.is_empty())
+                    .collect()
+            }
+            _ => return Err(format!("未知参数：{}", arg)),
+        }
+    }
+    if output.is_empty() {
+        return Err("缺少参数 --output".to_string());
+    }
+    if !["vocabulary", "history"].contains(&options.source.as_str()) {
+        return Err(format!("不支持的导出内容：{}", options.source));
+    }
+    Ok((output, options))
+}
+
+/// Windows 发布版本没有控制台窗口，附加到启动程序的命令行窗口后才能输出
+#[cfg(target_os = "windows")]
+pub fn attach_console() {
+    use windows::Win32::System::Console::{AttachConsole, ATTACH_PARENT_PROCESS};
+    unsafe {
+        let _ = AttachConsole(ATTACH_PARENT_PROCESS);
+    }
+}
+
+/// 命令行导出，args 为 export 之后的参数，返回进程的退出码：0 成功，1 导出失败，2 参数错误
+pub fn run_cli(args: &[String]) -> i32 {
+    #[cfg(target_os = "windows")]
+    attach_console();
+    let (output, options) = match parse_cli(args) {
+        Ok(res) => res,
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, CLI_USAGE);
+            return 2;
+        }
+    };
+    BASE_PATH.get_or_init(|| dirs::config_dir().unwrap().join(IDENTIFIER));
+    match export_file(Path::new(&output), &options) {
+        Ok(count) => {
+            println!("已导出 {} 条记录到 {}", count, output);
+            0
+        }
+        Err(e) => {
+            eprintln!("导出失败：{}", e);
+            1
+        }
+    }
+}
+
+#[tauri::command]
+pub fn export_data(path: String, options: ExportOptions) -> Result<usize, String> {
+    export_file(Path::new(&path), &options)
+}
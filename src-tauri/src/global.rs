@@ -32,12 +32,16 @@ pub const SHOW_TRANSLATOR: &str = "show_translator";
 pub const SCREENSHOT_TRANSLATE: &str = "screenshot_translate";
 pub const SELECTION_TRANSLATE: &str = "selection_translate";
 pub const SCREENSHOT_RECOGNIZER: &str = "screenshot_recognizer";
+pub const NAMING: &str = "naming";
 
 pub const TRANSLATOR_LABEL: &str = "translator";
 pub const SETTING_LABEL: &str = "setting";
 pub const SELECTION_TRANSLATOR_LABEL: &str = "selection-translator";
 pub const SCREEN_CAPTURE_LABEL: &str = "screen-capture";
 
+/// 与 tauri.conf.json 中的 identifier 一致，命令行模式下用于定位数据目录
+pub const IDENTIFIER: &str = "com.danger-dream.tosa";
+
 pub static APP: OnceCell<tauri::AppHandle> = OnceCell::new();
 pub static BASE_PATH: OnceCell<PathBuf> = OnceCell::new();
 
@@ -49,5 +53,6 @@ lazy_static::lazy_static! {
     pub static ref AHK_STATE: Arc<AtomicBool> = Arc::new(AtomicBool::new(false));
 
     pub static ref STORE: RwLock<HashMap<String, Value>> = RwLock::new(HashMap::new());
-    pub static ref JIEBA: Mutex<jieba_rs::Jieba> = Mutex::new(jieba_rs::Jieba::new());
+    // 分词器，启动后在后台线程加载词典，通过 segment::with_jieba 使用
+    pub static ref JIEBA: RwLock<Option<jieba_rs::Jieba>> = RwLock::new(None);
 }
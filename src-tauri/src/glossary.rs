@@ -0,0 +1,584 @@
+use crate::{global::BASE_PATH, lang_detect, translator};
+use log::{debug, warn};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::{Arc, RwLock},
+};
+
+const GLOSSARY_FILE_NAME: &str = "glossary.json";
+
+/// 术语，按语种对区分，语种为Tosa的语种代码
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Term {
+    pub source: String,
+    /// 为空时保留原文，如产品名称
+    #[serde(default)]
+    pub target: String,
+    pub from: String,
+    pub to: String,
+    #[serde(default)]
+    pub case_sensitive: bool,
+    #[serde(default)]
+    pub note: String,
+}
+
+impl Term {
+    fn target(&self) -> &str {
+        if self.target.is_empty() {
+            &self.source
+        } else {
+            &self.target
+        }
+    }
+
+    /// 去重的键，不区分大小写的术语忽略大小写
+    fn key(&self) -> (String, String, String) {
+        let source = if self.case_sensitive {
+            self.source.clone()
+        } else {
+            self.source.to_lowercase()
+        };
+        (self.from.clone(), self.to.clone(), source)
+    }
+}
+
+/// 文本中出现的术语，placeholder 为空时未使用占位符保护
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Matched {
+    pub source: String,
+    pub target: String,
+    #[serde(default)]
+    pub placeholder: String,
+}
+
+/// 发送给翻译服务前处理后的文本
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Prepared {
+    /// 术语替换为占位符后的文本
+    pub text: String,
+    /// 注入大模型提示词的术语说明
+    pub prompt: String,
+    pub terms: Vec<Matched>,
+}
+
+impl Prepared {
+    /// 匹配到的术语及译法，作为翻译缓存键的一部分，术语表修改后旧的缓存不再命中
+    pub fn terms_key(&self) -> String {
+        self.terms
+            .iter()
+            .map(|t| format!("{}\t{}\t{}", t.source, t.target, t.placeholder))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// 译文中未使用指定译法的术语
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Violation {
+    pub source: String,
+    pub target: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Checked {
+    pub text: String,
+    pub violations: Vec<Violation>,
+}
+
+static TERMS: Lazy<RwLock<Option<Arc<Vec<Term>>>>> = Lazy::new(|| RwLock::new(None));
+
+// 翻译服务可能在占位符中插入空格，或转换为全角括号
+static PLACEHOLDER_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"[{｛]\s*[{｛]\s*[Tt]\s*(\d+)\s*[}｝]\s*[}｝]").unwrap());
+
+fn glossary_path() -> PathBuf {
+    BASE_PATH.get().unwrap().join(GLOSSARY_FILE_NAME)
+}
+
+fn placeholder(index: usize) -> String {
+    format!("{{{{T{}}}}}", index)
+}
+
+/// 所有术语，首次使用时从文件加载
+pub fn terms() -> Arc<Vec<Term>> {
+    if let Some(list) = TERMS.read().unwrap().as_ref() {
+        return list.clone();
+    }
+    let list: Vec<Term> = match fs::read_to_string(glossary_path()) {
+        Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+            warn!("parse glossary error: {:?}", e);
+            Vec::new()
+        }),
+        Err(_) => Vec::new(),
+    };
+    let list = Arc::new(list);
+    *TERMS.write().unwrap() = Some(list.clone());
+    list
+}
+
+/// 整理后保存，去掉空术语，重复的术语保留后面的
+pub fn save_terms(list: Vec<Term>) -> Result<usize, String> {
+    let mut result: Vec<Term> = Vec::new();
+    let mut index: HashMap<(String, String, String), usize> = HashMap::new();
+    for mut term in list {
+        term.source = term.source.trim().to_string();
+        term.target = term.target.trim().to_string();
+        term.from = term.from.trim().to_string();
+        term.to = term.to.trim().to_string();
+        if term.source.is_empty() || term.from.is_empty() || term.to.is_empty() {
+            continue;
+        }
+        match index.get(&term.key()) {
+            Some(&i) => result[i] = term,
+            None => {
+                index.insert(term.key(), result.len());
+                result.push(term);
+            }
+        }
+    }
+    let content = serde_json::to_string_pretty(&result).map_err(|e| e.to_string())?;
+    fs::write(glossary_path(), content).map_err(|e| format!("保存术语表失败：{}", e))?;
+    let count = result.len();
+    *TERMS.write().unwrap() = Some(Arc::new(result));
+    Ok(count)
+}
+
+/// 首尾为字母数字的术语需要匹配单词边界，避免匹配到单词的一部分
+fn term_pattern(term: &Term) -> String {
+    let boundary = |c: Option<char>| match c {
+        Some(c) if c.is_ascii_alphanumeric() => r"\b",
+        _ => "",
+    };
+    let pattern = format!(
+        "{}{}{}",
+        boundary(term.source.chars().next()),
+        regex::escape(&term.source),
+        boundary(term.source.chars().last())
+    );
+    if term.case_sensitive {
+        format!("({})", pattern)
+    } else {
+        format!("((?i:{}))", pattern)
+    }
+}
+
+/// 查找文本中出现的术语，较长的术语优先，返回术语及所在的位置
+fn find_terms(text: &str, from: &str, to: &str) -> Vec<(Term, std::ops::Range<usize>)> {
+    let lower = text.to_lowercase();
+    let mut candidates: Vec<Term> = terms()
+        .iter()
+        .filter(|t| t.from == from && t.to == to)
+        .filter(|t| lower.contains(&t.source.to_lowercase()))
+        .cloned()
+        .collect();
+    if candidates.is_empty() {
+        return Vec::new();
+    }
+    candidates.sort_by_key(|t| std::cmp::Reverse(t.source.chars().count()));
+    let pattern = candidates
+        .iter()
+        .map(term_pattern)
+        .collect::<Vec<_>>()
+        .join("|");
+    let re = match Regex::new(&pattern) {
+        Ok(re) => re,
+        Err(e) => {
+            warn!("glossary regex error: {:?}", e);
+            return Vec::new();
+        }
+    };
+    re.captures_iter(text)
+        .filter_map(|caps| {
+            let i = (1..caps.len()).find(|&i| caps.get(i).is_some())?;
+            Some((candidates[i - 1].clone(), caps.get(0)?.range()))
+        })
+        .collect()
+}
+
+/// 大模型提示词中的术语说明
+fn instruction(terms: &[Matched]) -> String {
+    if terms.is_empty() {
+        return String::new();
+    }
+    let lines: Vec<String> = terms
+        .iter()
+        .map(|t| format!("- {} => {}", t.source, t.target))
+        .collect();
+    format!(
+        "Use the following glossary, the source terms must be translated as specified:\n{}",
+        lines.join("\n")
+    )
+}
+
+/// 翻译前处理文本，protect 为 true 时将术语替换为占位符，否则只生成提示词中的术语说明
+pub fn prepare(text: &str, from: &str, to: &str, protect: bool) -> Prepared {
+    let found = find_terms(text, from, to);
+    let mut prepared = Prepared {
+        text: text.to_string(),
+        ..Default::default()
+    };
+    if found.is_empty() {
+        return prepared;
+    }
+    let mut result = String::new();
+    let mut last = 0;
+    for (term, range) in found {
+        let index = match prepared.terms.iter().position(|m| m.source == term.source) {
+            Some(i) => i,
+            None => {
+                prepared.terms.push(Matched {
+                    source: term.source.clone(),
+                    target: term.target().to_string(),
+                    placeholder: if protect {
+                        placeholder(prepared.terms.len())
+                    } else {
+                        String::new()
+                    },
+                });
+                prepared.terms.len() - 1
+            }
+        };
+        if protect {
+            result.push_str(&text[last..range.start]);
+            result.push_str(&prepared.terms[index].placeholder);
+            last = range.end;
+        }
+    }
+    if protect {
+        result.push_str(&text[last..]);
+        prepared.text = result;
+    }
+    prepared.prompt = instruction(&prepared.terms);
+    debug!("glossary matched {} terms", prepared.terms.len());
+    prepared
+}
+
+/// 使用大模型的服务在提示词中注入术语，其余服务使用占位符保护
+pub fn prepare_for(service: &str, text: &str, from: &str, to: &str) -> Prepared {
+    let llm = translator::provider(service).is_some_and(|p| p.capabilities().prompt);
+    prepare(text, from, to, !llm)
+}
+
+/// 将占位符还原为目标术语，并检查译文中是否使用了指定的译法
+pub fn restore(prepared: &Prepared, text: &str) -> Checked {
+    let text = if prepared.terms.iter().any(|t| !t.placeholder.is_empty()) {
+        PLACEHOLDER_RE
+            .replace_all(text, |caps: &regex::Captures| {
+                caps[1]
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(|i| prepared.terms.get(i))
+                    .map(|t| t.target.clone())
+                    .unwrap_or_else(|| caps[0].to_string())
+            })
+            .to_string()
+    } else {
+        text.to_string()
+    };
+    let lower = text.to_lowercase();
+    let violations = prepared
+        .terms
+        .iter()
+        .filter(|t| !lower.contains(&t.target.to_lowercase()))
+        .map(|t| Violation {
+            source: t.source.clone(),
+            target: t.target.clone(),
+        })
+        .collect();
+    Checked { text, violations }
+}
+
+/// 文本中出现的术语说明，用于提示词改写
+pub fn prompt_instruction(text: &str, from: &str, to: &str) -> String {
+    prepare(text, from, to, false).prompt
+}
+
+/// 解析CSV/TSV，支持双引号转义
+fn parse_delimited(content: &str, delimiter: u8) -> Result<Vec<Vec<String>>, String> {
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(content.trim_start_matches('\u{feff}').as_bytes());
+    let mut rows = Vec::new();
+    for record in reader.records() {
+        let record = record.map_err(|e| format!("解析文件失败：{}", e))?;
+        rows.push(record.iter().map(|s| s.trim().to_string()).collect());
+    }
+    Ok(rows)
+}
+
+/// 第一行为表头时按列名读取，否则依次为 原文、译文、备注
+fn import_delimited(
+    content: &str,
+    delimiter: u8,
+    from: &str,
+    to: &str,
+) -> Result<Vec<Term>, String> {
+    let mut rows = parse_delimited(content, delimiter)?.into_iter();
+    let first = match rows.next() {
+        Some(row) => row,
+        None => return Ok(Vec::new()),
+    };
+    let header: Vec<String> = first.iter().map(|s| s.to_lowercase()).collect();
+    let has_header = matches!(
+        header.first().map(String::as_str),
+        Some("source" | "term" | "原文" | "术语")
+    );
+    let column = |names: &[&str], default: Option<usize>| {
+        if has_header {
+            header.iter().position(|h| names.contains(&h.as_str()))
+        } else {
+            default
+        }
+    };
+    let source = column(&["source", "term", "原文", "术语"], Some(0));
+    let target = column(&["target", "translation", "译文"], Some(1));
+    let note = column(&["note", "备注"], Some(2));
+    let from_col = column(&["from", "source_lang", "源语种"], None);
+    let to_col = column(&["to", "target_lang", "目标语种"], None);
+    let case_col = column(&["case_sensitive", "区分大小写"], None);
+    let rows: Vec<Vec<String>> = if has_header {
+        rows.collect()
+    } else {
+        std::iter::once(first).chain(rows).collect()
+    };
+    let get = |row: &Vec<String>, i: Option<usize>| -> String {
+        i.and_then(|i| row.get(i)).cloned().unwrap_or_default()
+    };
+    let lang = |value: String, default: &str| {
+        if value.is_empty() {
+            default.to_string()
+        } else {
+            lang_detect::from_bcp47(&value)
+        }
+    };
+    let list = rows
+        .iter()
+        .map(|row| Term {
+            source: get(row, source),
+            target: get(row, target),
+            from: lang(get(row, from_col), from),
+            to: lang(get(row, to_col), to),
+            case_sensitive: matches!(
+                get(row, case_col).to_lowercase().as_str(),
+                "1" | "true" | "yes" | "是"
+            ),
+            note: get(row, note),
+        })
+        .collect();
+    Ok(list)
+}
+
+fn child_text(node: roxmltree::Node, name: &str) -> Option<String> {
+    node.descendants()
+        .find(|n| n.has_tag_name(name))
+        .map(|n| {
+            n.descendants()
+                .filter(|t| t.is_text())
+                .filter_map(|t| t.text())
+                .collect::<String>()
+        })
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+pub fn xml_lang(node: roxmltree::Node) -> Option<String> {
+    node.attribute(("http://www.w3.org/XML/1998/namespace", "lang"))
+        .or_else(|| node.attribute("lang"))
+        .map(lang_detect::from_bcp47)
+}
+
+/// 解析TBX，兼容TBX 2（termEntry/langSet）和TBX 3（conceptEntry/langSec），
+/// 未指定语种对时使用每个条目中的前两个语种
+fn import_tbx(content: &str, from: &str, to: &str) -> Result<Vec<Term>, String> {
+    let doc = roxmltree::Document::parse(content).map_err(|e| format!("解析TBX失败：{}", e))?;
+    let mut list = Vec::new();
+    for entry in doc
+        .descendants()
+        .filter(|n| n.has_tag_name("termEntry") || n.has_tag_name("conceptEntry"))
+    {
+        let langs: Vec<(String, String)> = entry
+            .children()
+            .filter(|n| n.has_tag_name("langSet") || n.has_tag_name("langSec"))
+            .filter_map(|n| Some((xml_lang(n)?, child_text(n, "term")?)))
+            .collect();
+        let find = |lang: &str| langs.iter().find(|(l, _)| l == lang).cloned();
+        let pair = if from.is_empty() || to.is_empty() {
+            langs.first().cloned().zip(langs.get(1).cloned())
+        } else {
+            find(from).zip(find(to))
+        };
+        let ((from, source), (to, target)) = match pair {
+            Some(pair) => pair,
+            None => continue,
+        };
+        let note = entry
+            .descendants()
+            .find(|n| {
+                n.has_tag_name("note")
+                    || (n.has_tag_name("descrip") && n.attribute("type") == Some("definition"))
+            })
+            .and_then(|n| n.text())
+            .unwrap_or_default()
+            .trim()
+            .to_string();
+        list.push(Term {
+            source,
+            target,
+            from,
+            to,
+            case_sensitive: false,
+            note,
+        });
+    }
+    Ok(list)
+}
+
+pub fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn export_tbx(list: &[&Term]) -> String {
+    let source_lang = list
+        .first()
+        .map(|t| lang_detect::bcp47(&t.from))
+        .unwrap_or_else(|| "en".to_string());
+    let mut xml = format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<martif type=\"TBX-Basic\" xml:lang=\"{}\">\n<martifHeader><fileDesc><sourceDesc><p>Tosa</p></sourceDesc></fileDesc></martifHeader>\n<text>\n<body>\n",
+        source_lang
+    );
+    for (i, term) in list.iter().enumerate() {
+        xml.push_str(&format!("<termEntry id=\"t{}\">\n", i + 1));
+        if !term.note.is_empty() {
+            xml.push_str(&format!(
+                "\t<descrip type=\"definition\">{}</descrip>\n",
+                escape_xml(&term.note)
+            ));
+        }
+        for (lang, text) in [
+            (&term.from, term.source.as_str()),
+            (&term.to, term.target()),
+        ] {
+            xml.push_str(&format!(
+                "\t<langSet xml:lang=\"{}\"><tig><term>{}</term></tig></langSet>\n",
+                lang_detect::bcp47(lang),
+                escape_xml(text)
+            ));
+        }
+        xml.push_str("</termEntry>\n");
+    }
+    xml.push_str("</body>\n</text>\n</martif>\n");
+    xml
+}
+
+fn export_delimited(list: &[&Term], delimiter: u8) -> Result<String, String> {
+    let mut writer = csv::WriterBuilder::new()
+        .delimiter(delimiter)
+        .from_writer(Vec::new());
+    let mut write = |row: &[&str]| writer.write_record(row).map_err(|e| e.to_string());
+    write(&["source", "target", "from", "to", "case_sensitive", "note"])?;
+    for term in list {
+        write(&[
+            &term.source,
+            &term.target,
+            &term.from,
+            &term.to,
+            if term.case_sensitive { "true" } else { "false" },
+            &term.note,
+        ])?;
+    }
+    let data = writer.into_inner().map_err(|e| e.to_string())?;
+    String::from_utf8(data).map_err(|e| e.to_string())
+}
+
+fn extension(path: &Path) -> String {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or_default()
+        .to_lowercase()
+}
+
+/// 按扩展名导入 csv、tsv、tbx 文件，合并到现有术语表，返回导入的数量
+pub fn import_file(path: &Path, from: &str, to: &str) -> Result<usize, String> {
+    let content = fs::read_to_string(path).map_err(|e| format!("读取文件失败：{}", e))?;
+    let imported = match extension(path).as_str() {
+        "tbx" | "xml" => import_tbx(&content, from, to)?,
+        "tsv" | "txt" => import_delimited(&content, b'\t', from, to)?,
+        _ => import_delimited(&content, b',', from, to)?,
+    };
+    let imported: Vec<Term> = imported
+        .into_iter()
+        .filter(|t| !t.source.is_empty() && !t.from.is_empty() && !t.to.is_empty())
+        .collect();
+    if imported.is_empty() {
+        return Err("文件中没有可导入的术语，未包含语种时需指定语种对".to_string());
+    }
+    let count = imported.len();
+    let mut list = terms().to_vec();
+    list.extend(imported);
+    save_terms(list)?;
+    Ok(count)
+}
+
+/// 按扩展名导出为 csv、tsv、tbx 文件，from to 为空时导出所有语种对
+pub fn export_file(path: &Path, from: &str, to: &str) -> Result<usize, String> {
+    let all = terms();
+    let list: Vec<&Term> = all
+        .iter()
+        .filter(|t| from.is_empty() || t.from == from)
+        .filter(|t| to.is_empty() || t.to == to)
+        .collect();
+    let content = match extension(path).as_str() {
+        "tbx" | "xml" => export_tbx(&list),
+        "tsv" | "txt" => export_delimited(&list, b'\t')?,
+        _ => export_delimited(&list, b',')?,
+    };
+    fs::write(path, content).map_err(|e| format!("写入文件失败：{}", e))?;
+    Ok(list.len())
+}
+
+#[tauri::command]
+pub fn glossary_terms() -> Result<Vec<Term>, ()> {
+    Ok(terms().to_vec())
+}
+
+#[tauri::command]
+pub fn glossary_save(terms: Vec<Term>) -> Result<usize, String> {
+    save_terms(terms)
+}
+
+#[tauri::command]
+pub fn glossary_import(path: String, from: String, to: String) -> Result<usize, String> {
+    import_file(Path::new(&path), &from, &to)
+}
+
+#[tauri::command]
+pub fn glossary_export(path: String, from: String, to: String) -> Result<usize, String> {
+    export_file(Path::new(&path), &from, &to)
+}
+
+/// 供前端翻译服务使用，service 为服务名称，from to 为Tosa的语种代码
+#[tauri::command]
+pub fn glossary_prepare(
+    service: String,
+    text: String,
+    from: String,
+    to: String,
+) -> Result<Prepared, ()> {
+    Ok(prepare_for(&service, &text, &from, &to))
+}
+
+#[tauri::command]
+pub fn glossary_restore(prepared: Prepared, text: String) -> Result<Checked, ()> {
+    Ok(restore(&prepared, &text))
+}
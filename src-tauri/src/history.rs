@@ -0,0 +1,501 @@
+use crate::{
+    config::{get_config_with_default, get_or_bool},
+    global::BASE_PATH,
+};
+use log::{debug, info, warn};
+use once_cell::sync::Lazy;
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::{
+    fs,
+    path::PathBuf,
+    sync::Mutex,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+const HISTORY_FILE_NAME: &str = "history.db";
+const CONFIG_FILE_NAME: &str = ".config.dat";
+/// trigram 分词的全文检索要求关键字至少3个字符，更短时使用 LIKE
+const FTS_MIN_CHARS: usize = 3;
+
+static CONNECTION: Lazy<Mutex<Option<Connection>>> = Lazy::new(|| Mutex::new(None));
+
+/// 历史记录，字段与前端 ICacheItem 一致，kind 为记录类型，如 translate
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Record {
+    #[serde(default)]
+    pub id: String,
+    #[serde(default)]
+    pub kind: String,
+    #[serde(default)]
+    pub group_id: String,
+    #[serde(default)]
+    pub service: String,
+    #[serde(default)]
+    pub label: String,
+    /// 毫秒时间戳
+    #[serde(default)]
+    pub timestamp: i64,
+    #[serde(default)]
+    pub hit: i64,
+    #[serde(default)]
+    pub text: String,
+    #[serde(default)]
+    pub from: String,
+    #[serde(default)]
+    pub to: String,
+    /// 文本翻译结果为字符串，词典结果为对象
+    #[serde(default)]
+    pub result: Value,
+    #[serde(default)]
+    pub is_word: bool,
+    #[serde(default)]
+    pub timecost: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Page {
+    pub total: i64,
+    pub items: Vec<Record>,
+}
+
+/// 按 group_id 分组的结果，同一次翻译中各个服务的记录为一组
+#[derive(Debug, Clone, Serialize)]
+pub struct GroupPage {
+    pub total: i64,
+    pub groups: Vec<Vec<Record>>,
+}
+
+fn db_path() -> PathBuf {
+    BASE_PATH.get().unwrap().join(HISTORY_FILE_NAME)
+}
+
+fn now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or_default()
+}
+
+fn open() -> rusqlite::Result<Connection> {
+    let conn = Connection::open(db_path())?;
+    conn.execute_batch(
+        "PRAGMA journal_mode = WAL;
+         PRAGMA synchronous = NORMAL;
+         CREATE TABLE IF NOT EXISTS records (
+             seq INTEGER PRIMARY KEY AUTOINCREMENT,
+             id TEXT NOT NULL UNIQUE,
+             kind TEXT NOT NULL,
+             group_id TEXT NOT NULL,
+             service TEXT NOT NULL,
+             label TEXT NOT NULL,
+             timestamp INTEGER NOT NULL,
+             hit INTEGER NOT NULL DEFAULT 0,
+             text TEXT NOT NULL,
+             from_lang TEXT NOT NULL,
+             to_lang TEXT NOT NULL,
+             result TEXT NOT NULL,
+             result_text TEXT NOT NULL,
+             is_word INTEGER NOT NULL,
+             timecost INTEGER NOT NULL
+         );
+         CREATE INDEX IF NOT EXISTS records_lookup ON records(kind, service, from_lang, to_lang, text);
+         CREATE INDEX IF NOT EXISTS records_group ON records(kind, group_id);
+         CREATE INDEX IF NOT EXISTS records_timestamp ON records(kind, timestamp);
+         CREATE VIRTUAL TABLE IF NOT EXISTS records_fts USING fts5(
+             text, result_text, content = 'records', content_rowid = 'seq', tokenize = 'trigram'
+         );
+         CREATE TRIGGER IF NOT EXISTS records_ai AFTER INSERT ON records BEGIN
+             INSERT INTO records_fts(rowid, text, result_text) VALUES (new.seq, new.text, new.result_text);
+         END;
+         CREATE TRIGGER IF NOT EXISTS records_ad AFTER DELETE ON records BEGIN
+             INSERT INTO records_fts(records_fts, rowid, text, result_text)
+             VALUES ('delete', old.seq, old.text, old.result_text);
+         END;",
+    )?;
+    info!("history opened: {:?}", db_path());
+    Ok(conn)
+}
+
+/// 使用数据库连接执行操作，首次使用时打开数据库
+fn with_conn<T>(f: impl FnOnce(&mut Connection) -> rusqlite::Result<T>) -> Result<T, String> {
+    let mut guard = CONNECTION.lock().unwrap();
+    if guard.is_none() {
+        *guard = Some(open().map_err(|e| format!("打开历史记录失败：{}", e))?);
+    }
+    f(guard.as_mut().unwrap()).map_err(|e| format!("历史记录操作失败：{}", e))
+}
+
+/// 结果中用于检索的文本，词典结果取所有非链接的字符串
+fn result_text(result: &Value) -> String {
+    fn collect(value: &Value, out: &mut Vec<String>) {
+        match value {
+            Value::String(s) if !s.is_empty() && !s.starts_with("http") => out.push(s.clone()),
+            Value::Array(list) => list.iter().for_each(|v| collect(v, out)),
+            Value::Object(map) => map.values().for_each(|v| collect(v, out)),
+            _ => {}
+        }
+    }
+    let mut list = Vec::new();
+    collect(result, &mut list);
+    list.join("\n")
+}
+
+const RECORD_COLUMNS: &str =
+    "id, kind, group_id, service, label, timestamp, hit, text, from_lang, to_lang, result, is_word, timecost";
+
+fn record(row: &Row) -> rusqlite::Result<Record> {
+    let result: String = row.get(10)?;
+    Ok(Record {
+        id: row.get(0)?,
+        kind: row.get(1)?,
+        group_id: row.get(2)?,
+        service: row.get(3)?,
+        label: row.get(4)?,
+        timestamp: row.get(5)?,
+        hit: row.get(6)?,
+        text: row.get(7)?,
+        from: row.get(8)?,
+        to: row.get(9)?,
+        result: serde_json::from_str(&result).unwrap_or(Value::String(result)),
+        is_word: row.get(11)?,
+        timecost: row.get(12)?,
+    })
+}
+
+fn insert(conn: &Connection, record: &Record) -> rusqlite::Result<usize> {
+    conn.prepare_cached(
+        "INSERT OR IGNORE INTO records (id, kind, group_id, service, label, timestamp, hit, text,
+             from_lang, to_lang, result, result_text, is_word, timecost)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)",
+    )?
+    .execute(params![
+        record.id,
+        record.kind,
+        record.group_id,
+        record.service,
+        record.label,
+        record.timestamp,
+        record.hit,
+        record.text,
+        record.from,
+        record.to,
+        record.result.to_string(),
+        result_text(&record.result),
+        record.is_word,
+        record.timecost
+    ])
+}
+
+/// 检索条件，keyword 为空时不过滤
+fn keyword_filter(keyword: &str) -> (String, String) {
+    let keyword = keyword.trim();
+    if keyword.is_empty() {
+        ("?2 = ''".to_string(), String::new())
+    } else if keyword.chars().count() >= FTS_MIN_CHARS {
+        (
+            "seq IN (SELECT rowid FROM records_fts WHERE records_fts MATCH ?2)".to_string(),
+            format!("\"{}\"", keyword.replace('"', "\"\"")),
+        )
+    } else {
+        (
+            "(text LIKE ?2 ESCAPE '\\' OR result_text LIKE ?2 ESCAPE '\\')".to_string(),
+            format!("%{}%", keyword.replace('%', "\\%").replace('_', "\\_")),
+        )
+    }
+}
+
+/// 写入记录并按配置清理，返回记录 id
+pub fn add(mut record: Record) -> Result<String, String> {
+    if record.id.is_empty() {
+        return Err("历史记录缺少 id".to_string());
+    }
+    if record.timestamp == 0 {
+        record.timestamp = now();
+    }
+    with_conn(|conn| insert(conn, &record))?;
+    prune(&record.kind)?;
+    Ok(record.id)
+}
+
+/// 查找相同服务、原文、语种的最新记录
+pub fn find(
+    kind: &str,
+    service: &str,
+    text: &str,
+    from: &str,
+    to: &str,
+) -> Result<Option<Record>, String> {
+    with_conn(|conn| {
+        conn.query_row(
+            &format!(
+                "SELECT {} FROM records WHERE kind = ?1 AND service = ?2 AND from_lang = ?3 AND to_lang = ?4 \
+                 AND text = ?5 ORDER BY seq DESC LIMIT 1",
+                RECORD_COLUMNS
+            ),
+            params![kind, service, from, to, text],
+            record,
+        )
+        .optional()
+    })
+}
+
+pub fn hit(id: &str) -> Result<bool, String> {
+    with_conn(|conn| conn.execute("UPDATE records SET hit = hit + 1 WHERE id = ?1", [id]))
+        .map(|n| n > 0)
+}
+
+/// 按时间倒序分页，page 从1开始
+pub fn page(kind: &str, page: i64, size: i64, keyword: &str) -> Result<Page, String> {
+    let (filter, keyword) = keyword_filter(keyword);
+    let offset = (page.max(1) - 1) * size;
+    with_conn(|conn| {
+        let total = conn.query_row(
+            &format!(
+                "SELECT COUNT(*) FROM records WHERE kind = ?1 AND {}",
+                filter
+            ),
+            params![kind, keyword],
+            |r| r.get(0),
+        )?;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM records WHERE kind = ?1 AND {} ORDER BY seq DESC LIMIT ?3 OFFSET ?4",
+            RECORD_COLUMNS, filter
+        ))?;
+        let items = stmt
+            .query_map(params![kind, keyword, size, offset], record)?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(Page { total, items })
+    })
+}
+
+/// 按 group_id 分组分页，组按最近一条记录倒序，组内按写入顺序
+pub fn group_page(kind: &str, page: i64, size: i64, keyword: &str) -> Result<GroupPage, String> {
+    let (filter, keyword) = keyword_filter(keyword);
+    let offset = (page.max(1) - 1) * size;
+    with_conn(|conn| {
+        let total = conn.query_row(
+            &format!(
+                "SELECT COUNT(DISTINCT group_id) FROM records WHERE kind = ?1 AND {}",
+                filter
+            ),
+            params![kind, keyword],
+            |r| r.get(0),
+        )?;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT group_id FROM records WHERE kind = ?1 AND {} GROUP BY group_id ORDER BY MAX(seq) DESC \
+             LIMIT ?3 OFFSET ?4",
+            filter
+        ))?;
+        let ids = stmt
+            .query_map(params![kind, keyword, size, offset], |r| {
+                r.get::<_, String>(0)
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM records WHERE kind = ?1 AND group_id = ?2 ORDER BY seq",
+            RECORD_COLUMNS
+        ))?;
+        let mut groups = Vec::with_capacity(ids.len());
+        for id in ids {
+            groups.push(
+                stmt.query_map(params![kind, id], record)?
+                    .collect::<rusqlite::Result<Vec<_>>>()?,
+            );
+        }
+        Ok(GroupPage { total, groups })
+    })
+}
+
+/// 导出使用的记录，按时间顺序，时间范围为毫秒时间戳 [since, until)，其它条件为空时不限
+pub fn query(
+    kind: &str,
+    since: i64,
+    until: i64,
+    service: &str,
+    from: &str,
+    to: &str,
+) -> Result<Vec<Record>, String> {
+    with_conn(|conn| {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM records WHERE kind = ?1 AND timestamp >= ?2 AND timestamp < ?3
+             AND (?4 = '' OR service = ?4 OR label = ?4) AND (?5 = '' OR from_lang = ?5) AND (?6 = '' OR to_lang = ?6)
+             ORDER BY timestamp, seq",
+            RECORD_COLUMNS
+        ))?;
+        let items = stmt
+            .query_map(params![kind, since, until, service, from, to], record)?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(items)
+    })
+}
+
+pub fn delete(id: &str) -> Result<bool, String> {
+    with_conn(|conn| conn.execute("DELETE FROM records WHERE id = ?1", [id])).map(|n| n > 0)
+}
+
+pub fn delete_group(kind: &str, group_id: &str) -> Result<usize, String> {
+    with_conn(|conn| {
+        conn.execute(
+            "DELETE FROM records WHERE kind = ?1 AND group_id = ?2",
+            params![kind, group_id],
+        )
+    })
+}
+
+pub fn clear(kind: &str) -> Result<usize, String> {
+    with_conn(|conn| conn.execute("DELETE FROM records WHERE kind = ?1", [kind]))
+}
+
+/// 按配置项 cache_day、cache_max_count 清理，超过条数时先清理命中次数少的记录，启用 reserve_word 时保留词典结果
+pub fn prune(kind: &str) -> Result<usize, String> {
+    let days = get_config_with_default("cache_day", 0, |v| v.as_i64());
+    let max_count = get_config_with_default("cache_max_count", 0, |v| v.as_i64());
+    let reserve_word = get_or_bool("reserve_word", false);
+    with_conn(|conn| {
+        let mut count = 0;
+        if days > 0 {
+            count += conn.execute(
+                "DELETE FROM records WHERE kind = ?1 AND timestamp < ?2 AND NOT (?3 AND is_word)",
+                params![kind, now() - days * 24 * 60 * 60 * 1000, reserve_word],
+            )?;
+        }
+        if max_count > 0 {
+            count += conn.execute(
+                "DELETE FROM records WHERE kind = ?1 AND NOT (?2 AND is_word) AND seq NOT IN (
+                     SELECT seq FROM records WHERE kind = ?1 AND NOT (?2 AND is_word)
+                     ORDER BY hit DESC, seq DESC LIMIT ?3
+                 )",
+                params![kind, reserve_word, max_count],
+            )?;
+        }
+        Ok(count)
+    })
+}
+
+/// 导入旧版本保存在 .<kind>.dat 中的历史记录，导入后文件重命名为 .<kind>.dat.imported
+fn import_legacy() -> Result<usize, String> {
+    let base = BASE_PATH.get().unwrap();
+    let entries = match fs::read_dir(base) {
+        Ok(entries) => entries,
+        Err(_) => return Ok(0),
+    };
+    let mut total = 0;
+    for path in entries.filter_map(|e| e.ok()).map(|e| e.path()) {
+        let name = path
+            .file_name()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let kind = match name.strip_prefix('.').and_then(|s| s.strip_suffix(".dat")) {
+            Some(kind) if name != CONFIG_FILE_NAME && !kind.is_empty() => kind.to_string(),
+            _ => continue,
+        };
+        let content = match fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(_) => continue,
+        };
+        let list: Vec<Record> = match serde_json::from_str(&content) {
+            Ok(list) => list,
+            Err(e) => {
+                warn!("skip legacy history {:?}: {:?}", path, e);
+                continue;
+            }
+        };
+        // 旧版本的记录按写入顺序保存，按原顺序导入以保持 seq 的先后
+        let count = with_conn(|conn| {
+            let tx = conn.transaction()?;
+            let mut count = 0;
+            for mut record in list {
+                if record.id.is_empty() {
+                    continue;
+                }
+                record.kind = kind.clone();
+                record.is_word = record.is_word || !record.result.is_string();
+                count += insert(&tx, &record)?;
+            }
+            tx.commit()?;
+            Ok(count)
+        })?;
+        let _ = fs::rename(&path, path.with_file_name(format!("{}.imported", name)));
+        info!("imported {} legacy history records from {:?}", count, path);
+        total += count;
+    }
+    Ok(total)
+}
+
+/// 启动时导入旧版本的历史记录并按配置清理
+pub fn init() {
+    if let Err(e) = import_legacy() {
+        warn!("import legacy history error: {}", e);
+    }
+    let kinds = with_conn(|conn| {
+        let mut stmt = conn.prepare("SELECT DISTINCT kind FROM records")?;
+        let rows = stmt.query_map([], |r| r.get::<_, String>(0))?;
+        rows.collect::<rusqlite::Result<Vec<_>>>()
+    })
+    .unwrap_or_default();
+    for kind in kinds {
+        match prune(&kind) {
+            Ok(n) if n > 0 => debug!("pruned {} history records of {}", n, kind),
+            Err(e) => warn!("prune history error: {}", e),
+            _ => {}
+        }
+    }
+}
+
+#[tauri::command]
+pub fn history_add(record: Record) -> Result<String, String> {
+    add(record)
+}
+
+#[tauri::command]
+pub fn history_find(
+    kind: String,
+    service: String,
+    text: String,
+    from: String,
+    to: String,
+) -> Result<Option<Record>, String> {
+    find(&kind, &service, &text, &from, &to)
+}
+
+#[tauri::command]
+pub fn history_hit(id: String) -> Result<bool, String> {
+    hit(&id)
+}
+
+#[tauri::command]
+pub fn history_page(kind: String, page: i64, size: i64, keyword: String) -> Result<Page, String> {
+    self::page(&kind, page, size, &keyword)
+}
+
+#[tauri::command]
+pub fn history_groups(
+    kind: String,
+    page: i64,
+    size: i64,
+    keyword: String,
+) -> Result<GroupPage, String> {
+    group_page(&kind, page, size, &keyword)
+}
+
+#[tauri::command]
+pub fn history_delete(id: String) -> Result<bool, String> {
+    delete(&id)
+}
+
+#[tauri::command]
+pub fn history_delete_group(kind: String, group_id: String) -> Result<usize, String> {
+    delete_group(&kind, &group_id)
+}
+
+#[tauri::command]
+pub fn history_clear(kind: String) -> Result<usize, String> {
+    clear(&kind)
+}
+
+#[tauri::command]
+pub fn history_prune(kind: String) -> Result<usize, String> {
+    prune(&kind)
+}
@@ -1,4 +1,9 @@
-use crate::{config::get_or_string, event_handle, global::*};
+use crate::{
+    config::get_or_string,
+    event_handle,
+    global::*,
+    prompt::{presets, PROMPT_PREFIX},
+};
 use log::debug;
 use tauri::GlobalShortcutManager;
 
@@ -14,6 +19,7 @@ pub fn init_hotkey() {
         SCREENSHOT_TRANSLATE,
         SELECTION_TRANSLATE,
         SCREENSHOT_RECOGNIZER,
+        NAMING,
     ]
     .iter()
     {
@@ -29,4 +35,18 @@ pub fn init_hotkey() {
         let register_ok = res.is_ok();
         debug!("register global hotkey {} {}", key, register_ok);
     }
+
+    // 提示词改写预设的快捷键
+    for preset in presets() {
+        let key = preset.hotkey.trim().to_string();
+        if key.is_empty() {
+            continue;
+        }
+        let name = format!("{}{}", PROMPT_PREFIX, preset.id);
+        let key_clone = key.clone();
+        let res = shortcut_manager.register(key.as_str(), move || {
+            event_handle::handle_hotkey(name.clone(), key_clone.clone());
+        });
+        debug!("register prompt hotkey {} {}", key, res.is_ok());
+    }
 }
@@ -0,0 +1,262 @@
+use crate::{global::BASE_PATH, lang_detect, segment::with_jieba};
+use jieba_rs::{KeywordExtract, TextRank, TFIDF};
+use regex::Regex;
+use serde::Serialize;
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    path::PathBuf,
+};
+use unicode_segmentation::UnicodeSegmentation;
+
+pub static STOPWORDS_DIR_NAME: &str = "stopwords";
+
+// 内置英文停用词，RAKE以停用词和标点作为短语分隔
+const EN_STOPWORDS: &str =
+    "a about above after again against all am an and any are as at be because \
+been before being below between both but by can could did do does doing down during each few for \
+from further had has have having he her here hers herself him himself his how i if in into is it \
+its itself just me more most my myself no nor not now of off on once only or other our ours \
+ourselves out over own same she should so some such than that the their theirs them themselves \
+then there these they this those through to too under until up very was we were what when where \
+which while who whom why will with would you your yours yourself yourselves also may might must \
+shall us via etc";
+
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct Position {
+    /// 开始字节位置
+    pub start: usize,
+    /// 结束字节位置（不包含）
+    pub end: usize,
+    /// 开始字符位置
+    pub char_start: usize,
+    /// 结束字符位置（不包含）
+    pub char_end: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Keyword {
+    pub word: String,
+    /// 权重，已归一化到 0~1，第一个关键词为1
+    pub weight: f64,
+    /// 关键词在原文中出现的所有位置
+    pub positions: Vec<Position>,
+}
+
+fn stopwords_dir() -> PathBuf {
+    BASE_PATH.get().unwrap().join(STOPWORDS_DIR_NAME)
+}
+
+/// 读取配置目录下stopwords中的所有txt文件，每行一个停用词，#开头为注释；
+/// 每次提取时读取，修改文件后无需重启即可生效
+fn user_stopwords() -> HashSet<String> {
+    let mut words = HashSet::new();
+    let dir = match fs::read_dir(stopwords_dir()) {
+        Ok(dir) => dir,
+        Err(_) => return words,
+    };
+    for path in dir.filter_map(|e| e.ok().map(|e| e.path())) {
+        if !path.is_file() || path.extension().map_or(true, |e| e != "txt") {
+            continue;
+        }
+        if let Ok(content) = fs::read_to_string(&path) {
+            words.extend(
+                content
+                    .lines()
+                    .map(|l| l.trim())
+                    .filter(|l| !l.is_empty() && !l.starts_with('#'))
+                    .map(|l| l.to_lowercase()),
+            );
+        }
+    }
+    words
+}
+
+/// 查找关键词在原文中的所有位置；空格分词的语言按整词匹配，短语中的空白可匹配任意空白，如换行
+fn find_positions(text: &str, word: &str, spaced: bool) -> Vec<Position> {
+    let parts: Vec<String> = word.split_whitespace().map(regex::escape).collect();
+    if parts.is_empty() {
+        return Vec::new();
+    }
+    let pattern = if spaced {
+        format!(r"(?i)\b{}\b", parts.join(r"\s+"))
+    } else {
+        parts.join(r"\s+")
+    };
+    let re = match Regex::new(&pattern) {
+        Ok(re) => re,
+        Err(_) => return Vec::new(),
+    };
+    let mut positions = Vec::new();
+    let mut char_pos = 0;
+    let mut last = 0;
+    for m in re.find_iter(text) {
+        char_pos += text[last..m.start()].chars().count();
+        let char_len = m.as_str().chars().count();
+        positions.push(Position {
+            start: m.start(),
+            end: m.end(),
+            char_start: char_pos,
+            char_end: char_pos + char_len,
+        });
+        char_pos += char_len;
+        last = m.end();
+    }
+    positions
+}
+
+fn normalize(mut list: Vec<(String, f64)>, top_k: usize) -> Vec<(String, f64)> {
+    list.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+    list.truncate(top_k);
+    let max = list.first().map(|(_, w)| *w).unwrap_or(0.0);
+    if max > 0.0 {
+        for (_, w) in list.iter_mut() {
+            *w /= max;
+        }
+    }
+    list
+}
+
+/// 中文使用jieba的TF-IDF或TextRank提取
+fn extract_chinese(
+    text: &str,
+    top_k: usize,
+    method: &str,
+    stopwords: &HashSet<String>,
+) -> Vec<(String, f64)> {
+    with_jieba(|jieba| {
+        let keywords = if method == "textrank" {
+            let mut extractor = TextRank::new_with_jieba(jieba);
+            for w in stopwords {
+                extractor.add_stop_word(w.clone());
+            }
+            // TextRank默认只保留名词、动词
+            let pos = ["ns", "n", "vn", "v"]
+                .iter()
+                .map(|s| s.to_string())
+                .collect();
+            extractor.extract_tags(text, top_k, pos)
+        } else {
+            let mut extractor = TFIDF::new_with_jieba(jieba);
+            for w in stopwords {
+                extractor.add_stop_word(w.clone());
+            }
+            extractor.extract_tags(text, top_k, vec![])
+        };
+        keywords
+            .into_iter()
+            .map(|k| (k.keyword, k.weight))
+            .collect()
+    })
+}
+
+/// 以空格分词的语言使用RAKE提取：以停用词和标点切分候选短语，
+/// 词的得分为 共现度/词频，短语得分为其中各词得分之和
+fn extract_rake(text: &str, top_k: usize, stopwords: &HashSet<String>) -> Vec<(String, f64)> {
+    let mut phrases: Vec<Vec<String>> = Vec::new();
+    let mut current: Vec<String> = Vec::new();
+    for token in text.split_word_bounds() {
+        let lower = token.to_lowercase();
+        let is_word = lower.chars().any(char::is_alphabetic);
+        if is_word && !stopwords.contains(&lower) && lower.chars().count() > 1 {
+            current.push(lower);
+        } else if is_word || !token.chars().all(char::is_whitespace) {
+            // 停用词、数字、标点都作为短语边界，空白不打断短语
+            if !current.is_empty() {
+                phrases.push(std::mem::take(&mut current));
+            }
+        }
+    }
+    if !current.is_empty() {
+        phrases.push(current);
+    }
+
+    let mut freq: HashMap<&str, f64> = HashMap::new();
+    let mut degree: HashMap<&str, f64> = HashMap::new();
+    for phrase in &phrases {
+        for word in phrase {
+            *freq.entry(word).or_default() += 1.0;
+            *degree.entry(word).or_default() += phrase.len() as f64;
+        }
+    }
+    let mut scores: HashMap<String, f64> = HashMap::new();
+    for phrase in &phrases {
+        let score: f64 = phrase
+            .iter()
+            .map(|w| degree[w.as_str()] / freq[w.as_str()])
+            .sum();
+        // 同一短语多次出现只计算一次得分
+        scores.entry(phrase.join(" ")).or_insert(score);
+    }
+    normalize(scores.into_iter().collect(), top_k)
+}
+
+/// 提取关键词，method可选 tfidf、textrank，仅对中文生效
+pub fn extract(text: &str, lang: Option<&str>, top_k: usize, method: &str) -> Vec<Keyword> {
+    let lang = lang.unwrap_or_else(|| lang_detect::detect(text));
+    let mut stopwords = user_stopwords();
+    let list = match lang {
+        "zh_cn" | "zh_tw" => normalize(extract_chinese(text, top_k, method, &stopwords), top_k),
+        // 日韩文没有可用的关键词提取，不返回结果
+        "ja" | "ko" => Vec::new(),
+        // 内置停用词只有英文，其他语言使用配置目录stopwords下的停用词
+        "en" => {
+            stopwords.extend(EN_STOPWORDS.split_whitespace().map(String::from));
+            extract_rake(text, top_k, &stopwords)
+        }
+        _ => extract_rake(text, top_k, &stopwords),
+    };
+    let spaced = !matches!(lang, "zh_cn" | "zh_tw");
+    list.into_iter()
+        .map(|(word, weight)| Keyword {
+            positions: find_positions(text, &word, spaced),
+            word,
+            weight,
+        })
+        .collect()
+}
+
+#[tauri::command(async)]
+pub fn extract_keywords(
+    text: &str,
+    lang: Option<&str>,
+    top_k: Option<usize>,
+    method: Option<&str>,
+) -> Result<Vec<Keyword>, ()> {
+    Ok(extract(
+        text,
+        lang,
+        top_k.unwrap_or(10),
+        method.unwrap_or("tfidf"),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spans(text: &str, word: &str) -> Vec<(usize, usize)> {
+        find_positions(text, word, true)
+            .into_iter()
+            .map(|p| (p.char_start, p.char_end))
+            .collect()
+    }
+
+    #[test]
+    fn whole_words() {
+        assert_eq!(spans("Start the party with art.", "art"), [(21, 24)]);
+        assert_eq!(spans("Art and ART", "art"), [(0, 3), (8, 11)]);
+    }
+
+    #[test]
+    fn phrase_across_whitespace() {
+        assert_eq!(
+            spans(
+                "machine\nlearning and machine   learning",
+                "machine learning"
+            ),
+            [(0, 16), (21, 39)]
+        );
+        assert_eq!(find_positions("机器学习和学习", "学习", false).len(), 2);
+    }
+}
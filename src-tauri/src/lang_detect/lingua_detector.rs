@@ -0,0 +1,83 @@
+use super::LangDetector;
+use lingua::{Language, LanguageDetector, LanguageDetectorBuilder};
+use once_cell::sync::Lazy;
+
+const LANGUAGES: [Language; 27] = [
+    Language::Chinese,
+    Language::Japanese,
+    Language::English,
+    Language::Korean,
+    Language::French,
+    Language::Spanish,
+    Language::German,
+    Language::Russian,
+    Language::Italian,
+    Language::Portuguese,
+    Language::Turkish,
+    Language::Arabic,
+    Language::Vietnamese,
+    Language::Thai,
+    Language::Indonesian,
+    Language::Malay,
+    Language::Hindi,
+    Language::Mongolian,
+    Language::Bokmal,
+    Language::Nynorsk,
+    Language::Persian,
+    Language::Swedish,
+    Language::Dutch,
+    Language::Polish,
+    Language::Ukrainian,
+    Language::Greek,
+    Language::Hebrew,
+];
+
+/// lingua：准确率更高、支持语种更多，但模型加载较慢、占用内存较多
+pub struct LinguaDetector {
+    detector: LanguageDetector,
+}
+
+// 模型按需加载，首次识别时才会初始化
+pub static LINGUA: Lazy<LinguaDetector> = Lazy::new(|| LinguaDetector {
+    detector: LanguageDetectorBuilder::from_languages(&LANGUAGES).build(),
+});
+
+impl LangDetector for LinguaDetector {
+    fn name(&self) -> &'static str {
+        "lingua"
+    }
+
+    fn detect_iso639_3(&self, text: &str) -> Option<&'static str> {
+        let code = match self.detector.detect_language_of(text)? {
+            Language::Chinese => "zho",
+            Language::Japanese => "jpn",
+            Language::English => "eng",
+            Language::Korean => "kor",
+            Language::French => "fra",
+            Language::Spanish => "spa",
+            Language::German => "deu",
+            Language::Russian => "rus",
+            Language::Italian => "ita",
+            Language::Portuguese => "por",
+            Language::Turkish => "tur",
+            Language::Arabic => "ara",
+            Language::Vietnamese => "vie",
+            Language::Thai => "tha",
+            Language::Indonesian => "ind",
+            Language::Malay => "msa",
+            Language::Hindi => "hin",
+            Language::Mongolian => "mon",
+            Language::Bokmal => "nob",
+            Language::Nynorsk => "nno",
+            Language::Persian => "fas",
+            Language::Swedish => "swe",
+            Language::Dutch => "nld",
+            Language::Polish => "pol",
+            Language::Ukrainian => "ukr",
+            Language::Greek => "ell",
+            Language::Hebrew => "heb",
+            _ => return None,
+        };
+        Some(code)
+    }
+}
@@ -0,0 +1,249 @@
+#[cfg(feature = "lingua")]
+mod lingua_detector;
+mod script;
+#[cfg(feature = "whichlang")]
+mod whichlang_detector;
+
+use crate::{config::get_or_string, zh_convert};
+use script::LangSpan;
+
+/// 本地语种识别后端
+pub trait LangDetector: Send + Sync {
+    /// 后端名称，对应配置项 lang_detect_backend
+    fn name(&self) -> &'static str;
+
+    /// 识别文本语种，返回ISO 639-3代码
+    fn detect_iso639_3(&self, text: &str) -> Option<&'static str>;
+
+    /// 识别文本语种，返回Tosa的语种代码
+    fn detect(&self, text: &str) -> Option<&'static str> {
+        self.detect_iso639_3(text).and_then(tosa_code)
+    }
+}
+
+/// ISO 639-3代码转换为Tosa的语种代码，所有后端共用
+pub fn tosa_code(iso639_3: &str) -> Option<&'static str> {
+    let code = match iso639_3 {
+        "cmn" | "zho" => "zh_cn",
+        "jpn" => "ja",
+        "eng" => "en",
+        "kor" => "ko",
+        "fra" => "fr",
+        "spa" => "es",
+        "deu" => "de",
+        "rus" => "ru",
+        "nld" => "nl",
+        "swe" => "sv",
+        "ita" => "it",
+        "por" => "pt_pt",
+        "tur" => "tr",
+        "ara" => "ar",
+        "vie" => "vi",
+        "hin" => "hi",
+        "tha" => "th",
+        "ind" => "id",
+        "msa" | "zsm" => "ms",
+        "mon" | "khk" => "mn_cy",
+        "khm" => "km",
+        "nob" => "nb_no",
+        "nno" => "nn_no",
+        "fas" | "pes" => "fa",
+        "pol" => "pl",
+        "ukr" => "uk",
+        "ell" => "el",
+        "heb" => "he",
+        _ => return None,
+    };
+    Some(code)
+}
+
+/// Tosa的语种代码转换为BCP 47语言标签，用于TBX、TMX等交换格式
+pub fn bcp47(code: &str) -> String {
+    match code {
+        "zh_cn" => "zh-CN".to_string(),
+        "zh_tw" => "zh-TW".to_string(),
+        "pt_pt" => "pt-PT".to_string(),
+        "pt_br" => "pt-BR".to_string(),
+        "nb_no" => "nb-NO".to_string(),
+        "nn_no" => "nn-NO".to_string(),
+        "mn_cy" => "mn-Cyrl".to_string(),
+        "mn_mo" => "mn-Mong".to_string(),
+        _ => code.replace('_', "-"),
+    }
+}
+
+/// BCP 47语言标签转换为Tosa的语种代码，不区分大小写
+pub fn from_bcp47(tag: &str) -> String {
+    let tag = tag.trim().replace('_', "-").to_lowercase();
+    let mut parts = tag.split('-');
+    let lang = parts.next().unwrap_or_default();
+    let rest: Vec<&str> = parts.collect();
+    let has = |s: &str| rest.contains(&s);
+    match lang {
+        "zh" if has("tw") || has("hk") || has("mo") || has("hant") => "zh_tw",
+        "zh" => "zh_cn",
+        "pt" if has("br") => "pt_br",
+        "pt" => "pt_pt",
+        "nb" | "no" => "nb_no",
+        "nn" => "nn_no",
+        "mn" if has("mong") => "mn_mo",
+        "mn" => "mn_cy",
+        _ => lang,
+    }
+    .to_string()
+}
+
+/// 编译时启用的所有后端，第一个为默认后端
+fn detectors() -> Vec<&'static dyn LangDetector> {
+    #[allow(unused_mut)]
+    let mut list: Vec<&'static dyn LangDetector> = Vec::new();
+    #[cfg(feature = "whichlang")]
+    list.push(&whichlang_detector::WhichlangDetector);
+    #[cfg(feature = "lingua")]
+    list.push(&*lingua_detector::LINGUA);
+    list
+}
+
+fn current_detector() -> Option<&'static dyn LangDetector> {
+    let list = detectors();
+    let name = get_or_string("lang_detect_backend", String::new());
+    list.iter()
+        .find(|d| d.name() == name)
+        .or(list.first())
+        .copied()
+}
+
+// 片段中的拉丁字母少于该数量时不做统计检测，直接视为英语（如中文里夹杂的术语）
+const MIN_LATIN_RUN: usize = 12;
+
+pub fn detect(text: &str) -> &'static str {
+    // 先按文字判断，泰语、希伯来语等whichlang不支持的语种在这里就能确定
+    if let Some(lang) = script::detect_by_script(text) {
+        return lang;
+    }
+    match current_detector().and_then(|d| d.detect(text)) {
+        // 各后端都不区分简繁，按特征字再判断一次
+        Some("zh_cn") => zh_convert::detect_variant(text),
+        Some(lang) => lang,
+        None => "en",
+    }
+}
+
+/// 将混合语种的文本切分为单一语种的片段，相邻的同语种片段会合并
+pub fn detect_spans(text: &str) -> Vec<LangSpan> {
+    let mut spans: Vec<LangSpan> = Vec::new();
+    for (start, end) in script::script_runs(text) {
+        let run = &text[start..end];
+        let stats = script::ScriptStats::from_text(run);
+        let lang = if stats.latin == stats.total() && stats.latin < MIN_LATIN_RUN {
+            "en"
+        } else {
+            detect(run)
+        };
+        match spans.last_mut() {
+            Some(last) if last.lang == lang => {
+                last.end = end;
+                last.text.push_str(run);
+            }
+            _ => spans.push(LangSpan {
+                lang,
+                start,
+                end,
+                text: run.to_string(),
+            }),
+        }
+    }
+    spans
+}
+
+#[tauri::command]
+pub fn lang_detect(text: &str) -> Result<&str, ()> {
+    Ok(detect(text))
+}
+
+#[tauri::command]
+pub fn lang_detect_backends() -> Result<Vec<&'static str>, ()> {
+    Ok(detectors().iter().map(|d| d.name()).collect())
+}
+
+#[tauri::command]
+pub fn lang_detect_spans(text: &str) -> Result<Vec<LangSpan>, ()> {
+    Ok(detect_spans(text))
+}
+
+#[cfg(all(test, feature = "whichlang", feature = "lingua"))]
+mod tests {
+    use super::{lingua_detector::LINGUA, whichlang_detector::WhichlangDetector, LangDetector};
+
+    /// 两个后端都支持的语种
+    const CORPUS: &[(&str, &str)] = &[
+        ("zh_cn", "今天天气很好，我们一起去公园散步吧。"),
+        (
+            "ja",
+            "今日はとても良い天気なので、一緒に公園を散歩しましょう。",
+        ),
+        (
+            "en",
+            "The weather is very nice today, so let's take a walk in the park together.",
+        ),
+        (
+            "ko",
+            "오늘은 날씨가 아주 좋으니까 같이 공원에서 산책합시다.",
+        ),
+        (
+            "fr",
+            "Il fait très beau aujourd'hui, alors allons nous promener ensemble dans le parc.",
+        ),
+        (
+            "es",
+            "Hoy hace muy buen tiempo, así que vamos a dar un paseo juntos por el parque.",
+        ),
+        (
+            "de",
+            "Heute ist das Wetter sehr schön, also lass uns zusammen im Park spazieren gehen.",
+        ),
+        (
+            "ru",
+            "Сегодня очень хорошая погода, давайте вместе погуляем в парке.",
+        ),
+        (
+            "nl",
+            "Het is vandaag heel mooi weer, dus laten we samen een wandeling maken in het park.",
+        ),
+        (
+            "sv",
+            "Det är väldigt fint väder idag, så låt oss ta en promenad tillsammans i parken.",
+        ),
+        (
+            "it",
+            "Oggi il tempo è molto bello, quindi andiamo a fare una passeggiata insieme nel parco.",
+        ),
+        (
+            "pt_pt",
+            "Hoje o tempo está muito bom, então vamos dar um passeio juntos no parque.",
+        ),
+        (
+            "tr",
+            "Bugün hava çok güzel, o yüzden birlikte parkta yürüyüşe çıkalım.",
+        ),
+        (
+            "ar",
+            "الطقس جميل جدا اليوم، لذلك دعونا نتمشى معا في الحديقة.",
+        ),
+        (
+            "vi",
+            "Hôm nay thời tiết rất đẹp, vì vậy chúng ta hãy cùng nhau đi dạo trong công viên.",
+        ),
+        ("hi", "आज मौसम बहुत अच्छा है, तो चलो साथ में पार्क में टहलने चलते हैं।"),
+    ];
+
+    #[test]
+    fn backends_agree() {
+        for (code, text) in CORPUS {
+            let whichlang = WhichlangDetector.detect(text);
+            let lingua = LINGUA.detect(text);
+            assert_eq!(whichlang, lingua, "{}", text);
+            assert_eq!(whichlang, Some(*code), "{}", text);
+        }
+    }
+}
@@ -0,0 +1,299 @@
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Script {
+    Latin,
+    Han,
+    Hiragana,
+    Katakana,
+    Hangul,
+    Cyrillic,
+    Greek,
+    Hebrew,
+    Arabic,
+    Thai,
+    Devanagari,
+    Khmer,
+    Mongolian,
+}
+
+/// 按Unicode区块判断字符所属文字，空白、数字、标点等通用字符返回None
+pub fn script_of(c: char) -> Option<Script> {
+    let cp = c as u32;
+    let script = match cp {
+        0x41..=0x5A | 0x61..=0x7A => Script::Latin,
+        0xC0..=0x24F if cp != 0xD7 && cp != 0xF7 => Script::Latin,
+        0x1E00..=0x1EFF => Script::Latin,
+        0x370..=0x3FF | 0x1F00..=0x1FFF => Script::Greek,
+        0x400..=0x52F => Script::Cyrillic,
+        0x591..=0x5F4 | 0xFB1D..=0xFB4F => Script::Hebrew,
+        0x600..=0x6FF | 0x750..=0x77F | 0x8A0..=0x8FF | 0xFB50..=0xFDFF | 0xFE70..=0xFEFF
+            if c.is_alphabetic() =>
+        {
+            Script::Arabic
+        }
+        0x900..=0x97F => Script::Devanagari,
+        0xE00..=0xE7F => Script::Thai,
+        0x1100..=0x11FF | 0x3130..=0x318F | 0xA960..=0xA97F | 0xAC00..=0xD7FF => Script::Hangul,
+        0x1780..=0x17FF => Script::Khmer,
+        0x1800..=0x18AF => Script::Mongolian,
+        0x3040..=0x309F => Script::Hiragana,
+        0x30A0..=0x30FF | 0x31F0..=0x31FF | 0xFF66..=0xFF9F => Script::Katakana,
+        0x3005 | 0x3007 | 0x3400..=0x4DBF | 0x4E00..=0x9FFF | 0xF900..=0xFAFF => Script::Han,
+        0x20000..=0x2EBEF | 0x30000..=0x3134F => Script::Han,
+        _ => return None,
+    };
+    Some(script)
+}
+
+/// 文本中各文字的字符数量
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ScriptStats {
+    pub latin: usize,
+    pub han: usize,
+    pub kana: usize,
+    pub hangul: usize,
+    pub cyrillic: usize,
+    pub greek: usize,
+    pub hebrew: usize,
+    pub arabic: usize,
+    pub thai: usize,
+    pub devanagari: usize,
+    pub khmer: usize,
+    pub mongolian: usize,
+}
+
+impl ScriptStats {
+    pub fn from_text(text: &str) -> Self {
+        let mut stats = ScriptStats::default();
+        for c in text.chars() {
+            match script_of(c) {
+                Some(Script::Latin) => stats.latin += 1,
+                Some(Script::Han) => stats.han += 1,
+                Some(Script::Hiragana) | Some(Script::Katakana) => stats.kana += 1,
+                Some(Script::Hangul) => stats.hangul += 1,
+                Some(Script::Cyrillic) => stats.cyrillic += 1,
+                Some(Script::Greek) => stats.greek += 1,
+                Some(Script::Hebrew) => stats.hebrew += 1,
+                Some(Script::Arabic) => stats.arabic += 1,
+                Some(Script::Thai) => stats.thai += 1,
+                Some(Script::Devanagari) => stats.devanagari += 1,
+                Some(Script::Khmer) => stats.khmer += 1,
+                Some(Script::Mongolian) => stats.mongolian += 1,
+                None => {}
+            }
+        }
+        stats
+    }
+
+    /// 中日韩文字合计
+    pub fn cjk(&self) -> usize {
+        self.han + self.kana + self.hangul
+    }
+
+    pub fn total(&self) -> usize {
+        self.latin
+            + self.cjk()
+            + self.cyrillic
+            + self.greek
+            + self.hebrew
+            + self.arabic
+            + self.thai
+            + self.devanagari
+            + self.khmer
+            + self.mongolian
+    }
+}
+
+// 假名占中日文字的比例超过该值时认为是日语
+const KANA_RATIO: f64 = 0.1;
+// 谚文占中韩文字的比例超过该值时认为是韩语（韩语中会夹杂汉字）
+const HANGUL_RATIO: f64 = 0.3;
+
+/// 根据文字分布直接判定语种，只处理文字本身就能确定语种的情况；
+/// 拉丁字母、纯汉字等无法仅凭文字区分的返回None，交由统计模型处理
+pub fn detect_by_script(text: &str) -> Option<&'static str> {
+    let stats = ScriptStats::from_text(text);
+    let total = stats.total();
+    if total == 0 {
+        return None;
+    }
+    let cjk = stats.cjk();
+    // 取数量最多的文字，中日韩文字合并计算
+    let dominant = [
+        cjk,
+        stats.latin,
+        stats.cyrillic,
+        stats.greek,
+        stats.hebrew,
+        stats.arabic,
+        stats.thai,
+        stats.devanagari,
+        stats.khmer,
+        stats.mongolian,
+    ]
+    .into_iter()
+    .max()
+    .unwrap_or(0);
+
+    if dominant == cjk {
+        let ratio = |n: usize| n as f64 / cjk as f64;
+        if ratio(stats.hangul) >= HANGUL_RATIO {
+            return Some("ko");
+        }
+        if ratio(stats.kana) >= KANA_RATIO {
+            return Some("ja");
+        }
+        return None;
+    }
+    if dominant == stats.latin {
+        return None;
+    }
+    if dominant == stats.cyrillic {
+        return Some(cyrillic_lang(text));
+    }
+    if dominant == stats.arabic {
+        return Some(arabic_lang(text));
+    }
+    if dominant == stats.greek {
+        return Some("el");
+    }
+    if dominant == stats.hebrew {
+        return Some("he");
+    }
+    if dominant == stats.thai {
+        return Some("th");
+    }
+    if dominant == stats.devanagari {
+        return Some("hi");
+    }
+    if dominant == stats.khmer {
+        return Some("km");
+    }
+    Some("mn_mo")
+}
+
+/// 西里尔字母：乌克兰语、蒙古语(西里尔)有各自的特征字母，其余视为俄语
+fn cyrillic_lang(text: &str) -> &'static str {
+    let mut uk = 0;
+    let mut mn = 0;
+    let mut ru = 0;
+    for c in text.chars() {
+        match c {
+            'і' | 'ї' | 'є' | 'ґ' | 'І' | 'Ї' | 'Є' | 'Ґ' => uk += 1,
+            'ө' | 'ү' | 'Ө' | 'Ү' => mn += 1,
+            'ы' | 'э' | 'ъ' | 'ё' | 'Ы' | 'Э' | 'Ъ' | 'Ё' => ru += 1,
+            _ => {}
+        }
+    }
+    if mn > 0 && mn >= uk {
+        "mn_cy"
+    } else if uk > 0 && uk >= ru {
+        "uk"
+    } else {
+        "ru"
+    }
+}
+
+/// 阿拉伯字母：包含波斯语特有字母时认为是波斯语
+fn arabic_lang(text: &str) -> &'static str {
+    let persian = text
+        .chars()
+        .filter(|c| matches!(c, 'پ' | 'چ' | 'ژ' | 'گ' | 'ی' | 'ک'))
+        .count();
+    if persian > 0 {
+        "fa"
+    } else {
+        "ar"
+    }
+}
+
+/// 拉丁字母语种中whichlang不支持的部分，通过特征字母和高频功能词补充判断
+#[cfg_attr(not(feature = "whichlang"), allow(dead_code))]
+pub fn detect_latin_hint(text: &str) -> Option<&'static str> {
+    let lower = text.to_lowercase();
+    if lower
+        .chars()
+        .any(|c| matches!(c, 'ł' | 'ą' | 'ę' | 'ś' | 'ź' | 'ż' | 'ć' | 'ń'))
+    {
+        return Some("pl");
+    }
+    if lower.chars().any(|c| matches!(c, 'ø' | 'æ')) {
+        return Some("nb_no");
+    }
+    let words: Vec<&str> = lower
+        .split(|c: char| !c.is_alphabetic())
+        .filter(|w| !w.is_empty())
+        .collect();
+    if words.is_empty() {
+        return None;
+    }
+    let count = |list: &[&str]| words.iter().filter(|w| list.contains(w)).count();
+    let malay_id = count(&[
+        "yang", "dan", "ini", "itu", "dengan", "untuk", "tidak", "dari", "dalam", "akan", "ada",
+        "saya", "kami", "mereka", "adalah", "juga", "sudah", "bisa", "boleh", "tak", "kerana",
+        "sahaja",
+    ]);
+    // 功能词占比足够高才认定，避免英文中偶然出现的单词误判
+    if malay_id * 5 < words.len() || malay_id < 2 {
+        return None;
+    }
+    let malay = count(&["boleh", "tak", "kerana", "sahaja", "awak", "sangat"]);
+    let indonesian = count(&["bisa", "tidak", "karena", "saja", "kamu", "sekali"]);
+    if malay > indonesian {
+        Some("ms")
+    } else {
+        Some("id")
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RunKind {
+    Cjk,
+    Hangul,
+    Other(Script),
+}
+
+fn run_kind(script: Script) -> RunKind {
+    match script {
+        Script::Han | Script::Hiragana | Script::Katakana => RunKind::Cjk,
+        Script::Hangul => RunKind::Hangul,
+        s => RunKind::Other(s),
+    }
+}
+
+/// 按文字把文本切分为连续片段，空白、数字、标点归入前一个片段，返回(开始字节, 结束字节)
+pub fn script_runs(text: &str) -> Vec<(usize, usize)> {
+    let mut runs: Vec<(usize, usize)> = Vec::new();
+    let mut current: Option<RunKind> = None;
+    let mut start = 0;
+    for (i, c) in text.char_indices() {
+        let kind = match script_of(c) {
+            Some(s) => run_kind(s),
+            None => continue,
+        };
+        match current {
+            Some(k) if k == kind => {}
+            Some(_) => {
+                runs.push((start, i));
+                start = i;
+                current = Some(kind);
+            }
+            None => current = Some(kind),
+        }
+    }
+    if start < text.len() {
+        runs.push((start, text.len()));
+    }
+    runs
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LangSpan {
+    pub lang: &'static str,
+    /// 开始字节位置
+    pub start: usize,
+    /// 结束字节位置（不包含）
+    pub end: usize,
+    pub text: String,
+}
@@ -0,0 +1,40 @@
+use super::{script, LangDetector};
+
+/// whichlang：速度快，只支持16种语言
+pub struct WhichlangDetector;
+
+impl LangDetector for WhichlangDetector {
+    fn name(&self) -> &'static str {
+        "whichlang"
+    }
+
+    fn detect_iso639_3(&self, text: &str) -> Option<&'static str> {
+        let code = match whichlang::detect_language(text) {
+            whichlang::Lang::Cmn => "cmn",
+            whichlang::Lang::Jpn => "jpn",
+            whichlang::Lang::Eng => "eng",
+            whichlang::Lang::Kor => "kor",
+            whichlang::Lang::Fra => "fra",
+            whichlang::Lang::Spa => "spa",
+            whichlang::Lang::Deu => "deu",
+            whichlang::Lang::Rus => "rus",
+            whichlang::Lang::Nld => "nld",
+            whichlang::Lang::Swe => "swe",
+            whichlang::Lang::Ita => "ita",
+            whichlang::Lang::Por => "por",
+            whichlang::Lang::Tur => "tur",
+            whichlang::Lang::Ara => "ara",
+            whichlang::Lang::Vie => "vie",
+            whichlang::Lang::Hin => "hin",
+        };
+        Some(code)
+    }
+
+    fn detect(&self, text: &str) -> Option<&'static str> {
+        // whichlang不支持的拉丁字母语种先用特征词补充判断
+        if let Some(lang) = script::detect_latin_hint(text) {
+            return Some(lang);
+        }
+        self.detect_iso639_3(text).and_then(super::tosa_code)
+    }
+}
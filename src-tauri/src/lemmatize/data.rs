@@ -0,0 +1,76 @@
+// 不规则动词：原形 过去式 过去分词，多种写法用/分隔
+pub const IRREGULAR_VERBS: &str = "\
+arise arose arisen|awake awoke awoken|be was/were been|bear bore born/borne|beat beat beaten|\
+become became become|begin began begun|bend bent bent|bet bet bet|bind bound bound|bite bit bitten|\
+bleed bled bled|blow blew blown|break broke broken|breed bred bred|bring brought brought|\
+broadcast broadcast broadcast|build built built|burn burnt/burned burnt/burned|burst burst burst|\
+buy bought bought|cast cast cast|catch caught caught|choose chose chosen|cling clung clung|\
+come came come|cost cost cost|creep crept crept|cut cut cut|deal dealt dealt|dig dug dug|\
+do did done|draw drew drawn|dream dreamt/dreamed dreamt/dreamed|drink drank drunk|drive drove driven|\
+eat ate eaten|fall fell fallen|feed fed fed|feel felt felt|fight fought fought|find found found|\
+flee fled fled|fling flung flung|fly flew flown|forbid forbade forbidden|forecast forecast forecast|\
+forget forgot forgotten|forgive forgave forgiven|freeze froze frozen|get got got/gotten|\
+give gave given|go went gone|grind ground ground|grow grew grown|hang hung hung|have had had|\
+hear heard heard|hide hid hidden|hit hit hit|hold held held|hurt hurt hurt|keep kept kept|\
+kneel knelt knelt|know knew known|lay laid laid|lead led led|lean leant/leaned leant/leaned|\
+leap leapt/leaped leapt/leaped|learn learnt/learned learnt/learned|leave left left|lend lent lent|\
+let let let|lie lay lain|light lit/lighted lit/lighted|lose lost lost|make made made|mean meant meant|\
+meet met met|mislead misled misled|mistake mistook mistaken|misunderstand misunderstood misunderstood|\
+overcome overcame overcome|overtake overtook overtaken|pay paid paid|prove proved proven/proved|\
+put put put|quit quit quit|read read read|rid rid rid|ride rode ridden|ring rang rung|rise rose risen|\
+run ran run|saw sawed sawn|say said said|see saw seen|seek sought sought|sell sold sold|send sent sent|\
+set set set|sew sewed sewn|shake shook shaken|shed shed shed|shine shone shone|shoot shot shot|\
+show showed shown|shrink shrank shrunk|shut shut shut|sing sang sung|sink sank sunk|sit sat sat|\
+sleep slept slept|slide slid slid|smell smelt/smelled smelt/smelled|speak spoke spoken|\
+speed sped sped|spell spelt/spelled spelt/spelled|spend spent spent|spill spilt/spilled spilt/spilled|\
+spin spun spun|spit spat spat|split split split|spoil spoilt/spoiled spoilt/spoiled|spread spread spread|\
+spring sprang sprung|stand stood stood|steal stole stolen|stick stuck stuck|sting stung stung|\
+stink stank stunk|strike struck struck|strive strove striven|swear swore sworn|sweep swept swept|\
+swell swelled swollen|swim swam swum|swing swung swung|take took taken|teach taught taught|\
+tear tore torn|tell told told|think thought thought|throw threw thrown|thrust thrust thrust|\
+tread trod trodden|undergo underwent undergone|understand understood understood|\
+undertake undertook undertaken|upset upset upset|wake woke woken|wear wore worn|weave wove woven|\
+weep wept wept|win won won|wind wound wound|withdraw withdrew withdrawn|write wrote written";
+
+// 第三人称单数不规则的动词：原形 第三人称单数
+pub const IRREGULAR_THIRD_PERSON: &str = "be is|have has|do does|go goes";
+
+// 不规则名词复数：单数 复数
+pub const IRREGULAR_NOUNS: &str = "\
+man men|woman women|child children|person people|mouse mice|louse lice|goose geese|foot feet|\
+tooth teeth|ox oxen|penny pence|criterion criteria|phenomenon phenomena|datum data|\
+medium media|bacterium bacteria|curriculum curricula|memorandum memoranda|analysis analyses|\
+basis bases|crisis crises|diagnosis diagnoses|hypothesis hypotheses|thesis theses|axis axes|\
+cactus cacti|fungus fungi|nucleus nuclei|radius radii|stimulus stimuli|syllabus syllabi|\
+appendix appendices|index indices|matrix matrices|vertex vertices|\
+leaf leaves|life lives|knife knives|wife wives|wolf wolves|half halves|self selves|shelf shelves|\
+calf calves|loaf loaves|thief thieves|elf elves|sheaf sheaves|\
+potato potatoes|tomato tomatoes|hero heroes|echo echoes|veto vetoes|\
+bus buses|gas gases|plus pluses|lens lenses|bonus bonuses|virus viruses|campus campuses|\
+status statuses|focus focuses|chorus choruses|circus circuses|census censuses|atlas atlases|\
+canvas canvases|bias biases|iris irises|genius geniuses|octopus octopuses|surplus surpluses";
+
+// 单复数同形的名词
+pub const INVARIANT_NOUNS: &str = "sheep fish deer species series aircraft offspring means news \
+headquarters salmon trout swine moose bison";
+
+// 不规则形容词、副词：原级 比较级 最高级
+pub const IRREGULAR_ADJECTIVES: &str = "\
+good better best|well better best|bad worse worst|badly worse worst|ill worse worst|\
+many more most|much more most|little less least|far farther/further farthest/furthest|old older/elder oldest/eldest";
+
+// 以 -s、-ed、-ing 等结尾但本身就是原形的常用词，不做还原
+pub const NOT_INFLECTED: &str = "\
+his this thus us yes bus gas plus minus always perhaps news series species \
+means physics mathematics economics politics ethics lens canvas atlas bias chaos alias analysis \
+basis crisis thesis famous various serious previous obvious nervous curious dangerous jealous \
+glass class grass pass mass boss loss cross less unless across process success business address \
+red bed need speed seed feed deed weed indeed hundred sacred naked wicked wretched rugged \
+kindred shed shred bred fled led sled wed bled \
+thing nothing something anything everything king ring sing bring spring string swing wing \
+sting cling fling sling during ceiling morning evening wedding pudding building \
+darling sibling ping ding \
+her other mother father brother water after never ever over under number paper letter matter \
+summer winter corner dinner member order power river silver sister tower weather whether \
+either neither rather together however whatever wherever whenever \
+chest rest test west guest nest interest forest vest honest modest";
@@ -0,0 +1,608 @@
+mod data;
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::{
+    borrow::Cow,
+    collections::{HashMap, HashSet},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Inflection {
+    /// 原形
+    Base,
+    /// 名词复数
+    Plural,
+    /// 第三人称单数
+    ThirdPerson,
+    /// be 的 am、are
+    Present,
+    Past,
+    PastParticiple,
+    PresentParticiple,
+    Comparative,
+    Superlative,
+}
+
+impl Inflection {
+    /// 与词典结果 wfs 一致的中文名称
+    pub fn label(&self) -> &'static str {
+        match self {
+            Inflection::Base => "原形",
+            Inflection::Plural => "复数",
+            Inflection::ThirdPerson => "第三人称单数",
+            Inflection::Present => "现在式",
+            Inflection::Past => "过去式",
+            Inflection::PastParticiple => "过去分词",
+            Inflection::PresentParticiple => "现在分词",
+            Inflection::Comparative => "比较级",
+            Inflection::Superlative => "最高级",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Lemma {
+    pub lemma: String,
+    pub inflection: Inflection,
+}
+
+/// 与 IDictResult.wfs 结构一致
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WordForm {
+    pub name: Cow<'static, str>,
+    pub value: String,
+}
+
+struct Tables {
+    /// 变形 -> (原形, 变形类型)
+    forms: HashMap<&'static str, Vec<(&'static str, Inflection)>>,
+    /// 原形 -> (变形类型, 变形)，用于生成变形表
+    lemmas: HashMap<&'static str, Vec<(Inflection, &'static str)>>,
+    not_inflected: HashSet<&'static str>,
+    invariant: HashSet<&'static str>,
+}
+
+fn parse_table(tables: &mut Tables, data: &'static str, kinds: &[Inflection]) {
+    for line in data.split('|') {
+        let mut parts = line.split_whitespace();
+        let lemma = match parts.next() {
+            Some(l) => l,
+            None => continue,
+        };
+        for (kind, forms) in kinds.iter().zip(parts) {
+            for form in forms.split('/') {
+                tables.forms.entry(form).or_default().push((lemma, *kind));
+                tables.lemmas.entry(lemma).or_default().push((*kind, form));
+            }
+        }
+    }
+}
+
+static TABLES: Lazy<Tables> = Lazy::new(|| {
+    let mut tables = Tables {
+        forms: HashMap::new(),
+        lemmas: HashMap::new(),
+        not_inflected: data::NOT_INFLECTED.split_whitespace().collect(),
+        invariant: data::INVARIANT_NOUNS.split_whitespace().collect(),
+    };
+    parse_table(
+        &mut tables,
+        data::IRREGULAR_VERBS,
+        &[Inflection::Past, Inflection::PastParticiple],
+    );
+    parse_table(
+        &mut tables,
+        data::IRREGULAR_THIRD_PERSON,
+        &[Inflection::ThirdPerson],
+    );
+    parse_table(&mut tables, "be am/are", &[Inflection::Present]);
+    parse_table(&mut tables, data::IRREGULAR_NOUNS, &[Inflection::Plural]);
+    parse_table(
+        &mut tables,
+        data::IRREGULAR_ADJECTIVES,
+        &[Inflection::Comparative, Inflection::Superlative],
+    );
+    tables
+});
+
+fn is_vowel(c: char) -> bool {
+    matches!(c, 'a' | 'e' | 'i' | 'o' | 'u')
+}
+
+fn vowel_groups(word: &str) -> usize {
+    let mut count = 0;
+    let mut prev = false;
+    for c in word.chars() {
+        let v = is_vowel(c) || c == 'y' && count > 0;
+        if v && !prev {
+            count += 1;
+        }
+        prev = v;
+    }
+    count
+}
+
+/// 单音节且以 辅音-元音-辅音 结尾（w、x、y除外），如 stop、plan
+fn is_cvc(word: &str) -> bool {
+    let chars: Vec<char> = word.chars().collect();
+    let n = chars.len();
+    if n < 3 || vowel_groups(word) != 1 {
+        return false;
+    }
+    let (a, b, c) = (chars[n - 3], chars[n - 2], chars[n - 1]);
+    !is_vowel(a) && is_vowel(b) && !is_vowel(c) && !matches!(c, 'w' | 'x' | 'y')
+}
+
+/// 去掉 -ed、-ing 后的词干如何补e
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum StemE {
+    /// 补e优先，如 mak -> make、hop -> hope
+    Prefer,
+    /// 原样优先，补e备选，如 visit、decid -> decide
+    Allow,
+    /// 不补e，如 look、read、play
+    Never,
+}
+
+fn stem_e(stem: &str) -> StemE {
+    let chars: Vec<char> = stem.chars().collect();
+    let n = chars.len();
+    if n < 2 {
+        return StemE::Never;
+    }
+    let last = chars[n - 1];
+    let ends = |list: &[&str]| list.iter().any(|s| stem.ends_with(s));
+    // argu -> argue、danc -> dance、liv -> live、judg -> judge、pars -> parse
+    if matches!(last, 'u' | 'v' | 'c') || ends(&["dg", "iz", "yz", "rg", "ls", "ns", "ps", "rs"]) {
+        return StemE::Prefer;
+    }
+    if is_vowel(last) || matches!(last, 'w' | 'x' | 'y') {
+        return StemE::Never;
+    }
+    if !is_vowel(chars[n - 2]) {
+        // 辅音连缀一般不补e，如 walk、want、test
+        return if ends(&["ang", "eng"]) && !matches!(stem, "hang" | "bang" | "clang" | "twang") {
+            StemE::Prefer
+        } else if ends(&["th", "ast"]) {
+            StemE::Allow
+        } else {
+            StemE::Never
+        };
+    }
+    // 前面还有元音时为双元音，qu 中的 u 按辅音处理，如 quot -> quote
+    let digraph =
+        n >= 3 && is_vowel(chars[n - 3]) && !(n >= 4 && chars[n - 4] == 'q' && chars[n - 3] == 'u');
+    if digraph {
+        // 双元音后只有 s、z 补e，如 caus -> cause、freez -> freeze；look、read、wait 不补
+        return if matches!(last, 's' | 'z') || ends(&["creat", "iat", "uat"]) {
+            StemE::Prefer
+        } else {
+            StemE::Never
+        };
+    }
+    if vowel_groups(stem) == 1 {
+        // 单音节 单元音-辅音，如 hop -> hope、us -> use
+        return StemE::Prefer;
+    }
+    let prefer = [
+        "at", "id", "ir", "ur", "in", "is", "os", "ud", "ot", "ut", "um", "ap", "od", "as", "ag",
+        "ar",
+    ];
+    if ends(&prefer) {
+        // relat -> relate、decid -> decide、combin -> combine
+        StemE::Prefer
+    } else if ends(&["el", "em", "en", "er", "on", "op"]) {
+        // travel、open、order、mention、develop 不补e
+        StemE::Never
+    } else {
+        StemE::Allow
+    }
+}
+
+/// 词干末尾是否为双写辅音（不含常见的原形双写 ll、ss、ff、zz、dd）
+fn undouble(stem: &str) -> Option<&str> {
+    let bytes = stem.as_bytes();
+    let n = bytes.len();
+    if n >= 3 && bytes[n - 1] == bytes[n - 2] && b"bgmnprt".contains(&bytes[n - 1]) {
+        Some(&stem[..n - 1])
+    } else {
+        None
+    }
+}
+
+fn has_vowel(stem: &str) -> bool {
+    stem.chars().any(|c| is_vowel(c) || c == 'y')
+}
+
+/// -ed、-ing 的候选原形，按可能性排序
+fn verb_stems(stem: &str) -> Vec<String> {
+    if let Some(s) = undouble(stem) {
+        // 单音节按规则双写时只保留去掉双写的词干，如 bigg -> big
+        return if is_cvc(s) {
+            vec![s.to_string()]
+        } else {
+            vec![s.to_string(), stem.to_string()]
+        };
+    }
+    let with_e = format!("{}e", stem);
+    match stem_e(stem) {
+        StemE::Prefer => vec![with_e, stem.to_string()],
+        StemE::Allow => vec![stem.to_string(), with_e],
+        StemE::Never => vec![stem.to_string()],
+    }
+}
+
+/// 按规则还原，返回的候选按可能性排序
+fn by_rules(word: &str) -> Vec<Lemma> {
+    let mut list = Vec::new();
+    let mut push = |lemma: String, inflection: Inflection| {
+        if lemma.len() >= 2 && has_vowel(&lemma) {
+            list.push(Lemma { lemma, inflection });
+        }
+    };
+    if let Some(stem) = word.strip_suffix("ies").filter(|s| s.len() > 1) {
+        push(format!("{}y", stem), Inflection::Plural);
+        push(format!("{}y", stem), Inflection::ThirdPerson);
+    } else if let Some(stem) = word.strip_suffix("es").filter(|s| {
+        s.ends_with("ss") || s.ends_with(['x', 'z']) || s.ends_with("ch") || s.ends_with("sh")
+    }) {
+        push(stem.to_string(), Inflection::Plural);
+        push(stem.to_string(), Inflection::ThirdPerson);
+    } else if let Some(stem) = word
+        .strip_suffix('s')
+        .filter(|s| !s.ends_with(['s', 'u', 'i']))
+    {
+        push(stem.to_string(), Inflection::Plural);
+        push(stem.to_string(), Inflection::ThirdPerson);
+        // buses -> bus、tomatoes -> tomato，排在 houses -> house 之后
+        if let Some(stem) = word.strip_suffix("es").filter(|s| s.ends_with(['s', 'o'])) {
+            push(stem.to_string(), Inflection::Plural);
+            push(stem.to_string(), Inflection::ThirdPerson);
+        }
+    } else if let Some(stem) = word.strip_suffix("ied") {
+        let lemma = if stem.len() > 1 {
+            format!("{}y", stem)
+        } else {
+            format!("{}ie", stem)
+        };
+        push(lemma.clone(), Inflection::Past);
+        push(lemma, Inflection::PastParticiple);
+    } else if let Some(stem) = word.strip_suffix("ed") {
+        // agreed -> agree、used -> use
+        let stems = if stem.ends_with('e') {
+            vec![format!("{}e", stem)]
+        } else {
+            verb_stems(stem)
+        };
+        for s in stems {
+            push(s.clone(), Inflection::Past);
+            push(s, Inflection::PastParticiple);
+        }
+    } else if let Some(stem) = word.strip_suffix("ying").filter(|s| s.len() <= 2) {
+        // dying -> die、lying -> lie
+        push(format!("{}ie", stem), Inflection::PresentParticiple);
+    } else if let Some(stem) = word.strip_suffix("ing") {
+        for s in verb_stems(stem) {
+            push(s, Inflection::PresentParticiple);
+        }
+    } else if let Some(stem) = word.strip_suffix("iest") {
+        push(format!("{}y", stem), Inflection::Superlative);
+    } else if let Some(stem) = word.strip_suffix("est") {
+        for s in verb_stems(stem) {
+            push(s, Inflection::Superlative);
+        }
+    } else if let Some(stem) = word.strip_suffix("ier") {
+        push(format!("{}y", stem), Inflection::Comparative);
+    } else if let Some(stem) = word.strip_suffix("er").filter(|s| s.len() <= 5) {
+        // 以 -er 结尾的多为名词（teacher、computer），只对短词给出比较级候选，且排在原形之后
+        push(word.to_string(), Inflection::Base);
+        for s in verb_stems(stem) {
+            push(s, Inflection::Comparative);
+        }
+    }
+    list
+}
+
+/// 词形还原，返回所有可能的原形，按可能性排序，第一个为最可能的结果；无法还原时返回原词
+pub fn lemmatize_word(word: &str) -> Vec<Lemma> {
+    let word = word.trim().to_lowercase();
+    if word.is_empty() {
+        return Vec::new();
+    }
+    let tables = &*TABLES;
+    let mut list: Vec<Lemma> = Vec::new();
+    if let Some(forms) = tables.forms.get(word.as_str()) {
+        list.extend(forms.iter().map(|(lemma, inflection)| Lemma {
+            lemma: lemma.to_string(),
+            inflection: *inflection,
+        }));
+    }
+    let is_lemma = tables.lemmas.contains_key(word.as_str())
+        || tables.not_inflected.contains(word.as_str())
+        || tables.invariant.contains(word.as_str());
+    if list.is_empty() && !is_lemma {
+        list = by_rules(&word);
+    }
+    if is_lemma || list.is_empty() {
+        list.push(Lemma {
+            lemma: word.clone(),
+            inflection: Inflection::Base,
+        });
+    }
+    // 去掉重复的候选，保持顺序
+    let mut seen = HashSet::new();
+    list.retain(|l| seen.insert((l.lemma.clone(), l.inflection)));
+    list
+}
+
+/// 最可能的原形
+pub fn lemma_of(word: &str) -> String {
+    lemmatize_word(word)
+        .into_iter()
+        .next()
+        .map(|l| l.lemma)
+        .unwrap_or_default()
+}
+
+fn regular_s(word: &str) -> String {
+    if let Some(stem) = word
+        .strip_suffix('y')
+        .filter(|s| s.chars().last().map_or(false, |c| !is_vowel(c)))
+    {
+        format!("{}ies", stem)
+    } else if word.ends_with(['s', 'x', 'z', 'o']) || word.ends_with("ch") || word.ends_with("sh") {
+        format!("{}es", word)
+    } else {
+        format!("{}s", word)
+    }
+}
+
+/// 加 -ed、-ing、-er、-est 前的词干：双写辅音、去e、y变i
+fn regular_suffix(word: &str, suffix: &str) -> String {
+    let last = word.chars().last().unwrap_or_default();
+    if is_cvc(word) {
+        return format!("{}{}{}", word, last, suffix);
+    }
+    if suffix == "ing" {
+        if let Some(stem) = word.strip_suffix("ie") {
+            return format!("{}ying", stem);
+        }
+        if word.ends_with('e') && !word.ends_with("ee") && word.len() > 2 {
+            return format!("{}ing", &word[..word.len() - 1]);
+        }
+        return format!("{}ing", word);
+    }
+    if word.ends_with('e') {
+        return format!("{}{}", word, &suffix[1..]);
+    }
+    if let Some(stem) = word
+        .strip_suffix('y')
+        .filter(|s| s.chars().last().map_or(false, |c| !is_vowel(c)))
+    {
+        return format!("{}i{}", stem, suffix);
+    }
+    format!("{}{}", word, suffix)
+}
+
+fn irregular_forms(lemma: &str, kind: Inflection) -> Option<Vec<&'static str>> {
+    let forms: Vec<&'static str> = TABLES
+        .lemmas
+        .get(lemma)?
+        .iter()
+        .filter(|(k, _)| *k == kind)
+        .map(|(_, f)| *f)
+        .collect();
+    if forms.is_empty() {
+        None
+    } else {
+        Some(forms)
+    }
+}
+
+fn form(lemma: &str, kind: Inflection, regular: impl FnOnce() -> String) -> WordForm {
+    let value = match irregular_forms(lemma, kind) {
+        Some(forms) => forms.join("/"),
+        None => regular(),
+    };
+    WordForm {
+        name: kind.label().into(),
+        value,
+    }
+}
+
+/// 生成原形的变形表，pos 为 noun、verb、adj；未指定时只按不规则变形表推断词性，无法推断时返回空
+pub fn inflect_word(word: &str, pos: Option<&str>) -> Vec<WordForm> {
+    let lemma = word.trim().to_lowercase();
+    if lemma.is_empty() || lemma.contains(char::is_whitespace) {
+        return Vec::new();
+    }
+    let tables = &*TABLES;
+    let pos = match pos {
+        Some(pos) => pos,
+        None if irregular_forms(&lemma, Inflection::Comparative).is_some() => "adj",
+        None if irregular_forms(&lemma, Inflection::Past).is_some() => "verb",
+        None if irregular_forms(&lemma, Inflection::Plural).is_some() => "noun",
+        None => return Vec::new(),
+    };
+    let mut list = Vec::new();
+    if pos == "noun" {
+        if tables.invariant.contains(lemma.as_str()) {
+            list.push(WordForm {
+                name: Inflection::Plural.label().into(),
+                value: lemma.clone(),
+            });
+        } else {
+            list.push(form(&lemma, Inflection::Plural, || regular_s(&lemma)));
+        }
+    }
+    if pos == "verb" {
+        list.push(form(&lemma, Inflection::ThirdPerson, || regular_s(&lemma)));
+        list.push(form(&lemma, Inflection::Past, || {
+            regular_suffix(&lemma, "ed")
+        }));
+        list.push(form(&lemma, Inflection::PastParticiple, || {
+            regular_suffix(&lemma, "ed")
+        }));
+        list.push(form(&lemma, Inflection::PresentParticiple, || {
+            regular_suffix(&lemma, "ing")
+        }));
+    }
+    if pos == "adj" {
+        // 三个音节以上的形容词使用 more、most
+        let long = vowel_groups(&lemma) > 2
+            || (vowel_groups(&lemma) == 2 && !lemma.ends_with('y') && !lemma.ends_with("le"));
+        list.push(form(&lemma, Inflection::Comparative, || {
+            if long {
+                format!("more {}", lemma)
+            } else {
+                regular_suffix(&lemma, "er")
+            }
+        }));
+        list.push(form(&lemma, Inflection::Superlative, || {
+            if long {
+                format!("most {}", lemma)
+            } else {
+                regular_suffix(&lemma, "est")
+            }
+        }));
+    }
+    list
+}
+
+/// 词典词性转为 inflect_word 的 pos，如 n. -> noun、vt. -> verb
+pub fn pos_of_trait(trait_: &str) -> Option<&'static str> {
+    let key = trait_.trim().trim_end_matches(['.', '．']).to_lowercase();
+    match key.as_str() {
+        "n" | "noun" | "名" | "名词" => Some("noun"),
+        "v" | "vt" | "vi" | "verb" | "动" | "动词" | "及物动词" | "不及物动词" => {
+            Some("verb")
+        }
+        "a" | "adj" | "adjective" | "形" | "形容词" => Some("adj"),
+        _ => None,
+    }
+}
+
+/// 按词典给出的词性生成变形表，没有可用的词性时按 inflect_word 推断
+pub fn inflect_by_traits<'a>(
+    word: &str,
+    traits: impl IntoIterator<Item = &'a str>,
+) -> Vec<WordForm> {
+    let mut pos_list: Vec<&str> = Vec::new();
+    for pos in traits.into_iter().filter_map(pos_of_trait) {
+        if !pos_list.contains(&pos) {
+            pos_list.push(pos);
+        }
+    }
+    if pos_list.is_empty() {
+        return inflect_word(word, None);
+    }
+    let mut list: Vec<WordForm> = Vec::new();
+    for pos in pos_list {
+        for form in inflect_word(word, Some(pos)) {
+            if !list.iter().any(|f| f.name == form.name) {
+                list.push(form);
+            }
+        }
+    }
+    list
+}
+
+#[tauri::command]
+pub fn lemmatize(word: &str) -> Result<Vec<Lemma>, ()> {
+    Ok(lemmatize_word(word))
+}
+
+#[tauri::command]
+pub fn inflect(
+    word: &str,
+    pos: Option<&str>,
+    traits: Option<Vec<String>>,
+) -> Result<Vec<WordForm>, ()> {
+    Ok(match pos {
+        Some(pos) => inflect_word(word, Some(pos)),
+        None => inflect_by_traits(word, traits.iter().flatten().map(String::as_str)),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lemmas(word: &str) -> Vec<String> {
+        let mut list: Vec<String> = Vec::new();
+        for l in lemmatize_word(word) {
+            if !list.contains(&l.lemma) {
+                list.push(l.lemma);
+            }
+        }
+        list
+    }
+
+    #[test]
+    fn common_words() {
+        let cases = [
+            ("looked", "look"),
+            ("cooked", "cook"),
+            ("reading", "read"),
+            ("eating", "eat"),
+            ("speaking", "speak"),
+            ("waited", "wait"),
+            ("buses", "bus"),
+            ("played", "play"),
+            ("hoped", "hope"),
+            ("hopped", "hop"),
+            ("used", "use"),
+            ("making", "make"),
+            ("running", "run"),
+            ("caused", "cause"),
+            ("continued", "continue"),
+            ("changing", "change"),
+            ("created", "create"),
+            ("decided", "decide"),
+            ("opened", "open"),
+            ("visited", "visit"),
+            ("houses", "house"),
+            ("studies", "study"),
+            ("went", "go"),
+            ("children", "child"),
+            ("nicest", "nice"),
+        ];
+        for (word, lemma) in cases {
+            assert_eq!(lemma_of(word), lemma, "{}", word);
+        }
+    }
+
+    #[test]
+    fn no_spurious_e() {
+        for (word, lemma) in [
+            ("looked", "look"),
+            ("reading", "read"),
+            ("played", "play"),
+            ("walked", "walk"),
+        ] {
+            assert_eq!(lemmas(word), [lemma], "{}", word);
+        }
+        // -er 多为名词，比较级排在原词之后
+        assert_eq!(lemmas("bigger"), ["bigger", "big"]);
+    }
+
+    #[test]
+    fn inflect_needs_pos() {
+        assert!(inflect_word("big", None).is_empty());
+        let values = |list: Vec<WordForm>| list.into_iter().map(|f| f.value).collect::<Vec<_>>();
+        assert_eq!(
+            values(inflect_word("big", Some("adj"))),
+            ["bigger", "biggest"]
+        );
+        assert_eq!(
+            values(inflect_word("go", None)),
+            ["goes", "went", "gone", "going"]
+        );
+        assert_eq!(
+            values(inflect_by_traits("hope", ["n.", "vt."])),
+            ["hopes", "hopes", "hoped", "hoped", "hoping"]
+        );
+    }
+}
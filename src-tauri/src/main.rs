@@ -5,12 +5,31 @@
 
 mod cmd;
 mod config;
+mod dictionary;
+mod engine;
 mod event_handle;
+mod export;
 mod global;
+mod glossary;
+mod history;
 mod hotkey;
+mod keyword;
 mod lang_detect;
+mod lemmatize;
+mod naming;
+mod phonetic;
+mod prompt;
+mod romanize;
+mod segment;
+mod tm;
+mod tokenize;
+mod trans_cache;
+mod translator;
 mod tray;
+mod vocab_level;
+mod vocabulary;
 mod window;
+mod zh_convert;
 
 #[cfg(target_os = "windows")]
 mod ahk;
@@ -42,6 +61,14 @@ fn main() {
             std::process::exit(1);
         }
     }
+    // 命令行导出生词及历史记录：tosa export --output <文件> [选项]
+    // 命令行翻译：tosa translate [选项] <文本>
+    let args: Vec<String> = std::env::args().collect();
+    match args.get(1).map(String::as_str) {
+        Some("export") => std::process::exit(export::run_cli(&args[2..])),
+        Some("translate") => std::process::exit(translator::run_cli(&args[2..])),
+        _ => {}
+    }
     tauri::Builder::default()
         .plugin(tauri_plugin_context_menu::init())
         .plugin(tauri_plugin_positioner::init())
@@ -73,6 +100,17 @@ fn main() {
             info!("init config store");
             config::init_config();
 
+            // 加载词典较慢，放到后台线程，避免阻塞启动
+            std::thread::spawn(segment::init_jieba);
+            // 导入旧版本的历史记录并清理过期记录
+            std::thread::spawn(history::init);
+            // 定时提醒复习生词
+            vocabulary::init();
+            // 预加载本地大模型，避免首次翻译等待
+            translator::local_llm::warm_up_services();
+            // 启动离线翻译引擎
+            translator::libre::start_engines();
+
             #[cfg(target_os = "windows")]
             {
                 if !config::is_first_run() && config::get_or_bool("enable_ahk", false) {
@@ -87,7 +125,7 @@ fn main() {
 
             std::thread::spawn(move || {
                 window::create_trans_window();
-                 window::create_screenshot_window();
+                window::create_screenshot_window();
                 //window::create_mini_trans_window();
                 //window::show_mini_trans_window();
 
@@ -103,6 +141,72 @@ fn main() {
             cmd::unset_proxy,
             cmd::cut,
             cmd::active_window_is_self,
+            segment::jieba_user_words,
+            segment::jieba_add_word,
+            segment::jieba_remove_word,
+            segment::jieba_reload,
+            tokenize::tokenize,
+            keyword::extract_keywords,
+            romanize::romanize,
+            naming::naming,
+            naming::naming_replace,
+            lemmatize::lemmatize,
+            lemmatize::inflect,
+            vocab_level::vocab_levels,
+            dictionary::dict_list,
+            dictionary::dict_lookup,
+            dictionary::dict_search,
+            dictionary::dict_resource,
+            dictionary::dict_reload,
+            dictionary::ecdict::ecdict_lookup,
+            dictionary::merge::dict_merge,
+            phonetic::phonetic,
+            translator::translate,
+            translator::translate_providers,
+            translator::translate_cancel,
+            translator::local_llm::local_llm_models,
+            translator::local_llm::local_llm_translate,
+            translator::local_llm::local_llm_warm_up,
+            translator::libre::libre_translate,
+            translator::libre::libre_detect,
+            translator::libre::libre_languages,
+            engine::start_engine,
+            engine::stop_engine,
+            engine::engine_state,
+            glossary::glossary_terms,
+            glossary::glossary_save,
+            glossary::glossary_import,
+            glossary::glossary_export,
+            glossary::glossary_prepare,
+            glossary::glossary_restore,
+            history::history_add,
+            history::history_find,
+            history::history_hit,
+            history::history_page,
+            history::history_groups,
+            history::history_delete,
+            history::history_delete_group,
+            history::history_clear,
+            history::history_prune,
+            trans_cache::trans_cache_get,
+            trans_cache::trans_cache_put,
+            trans_cache::trans_cache_clear,
+            trans_cache::trans_cache_stats,
+            vocabulary::vocab_lookup,
+            vocabulary::vocab_star,
+            vocabulary::vocab_list,
+            vocabulary::vocab_due_count,
+            vocabulary::vocab_review,
+            vocabulary::vocab_remove,
+            export::export_data,
+            tm::tm_lookup,
+            tm::tm_add,
+            tm::tm_list,
+            tm::tm_remove,
+            tm::tm_import,
+            tm::tm_export,
+            prompt::prompt_presets,
+            prompt::prompt_run,
             config::get_config,
             config::set_config_by_key,
             ahk::read_script,
@@ -111,6 +215,9 @@ fn main() {
             ahk::kill_autohotkey,
             ahk::is_autohotkey_running,
             lang_detect::lang_detect,
+            lang_detect::lang_detect_backends,
+            lang_detect::lang_detect_spans,
+            zh_convert::zh_convert,
             window::show_trans_win,
             window::show_setting_window,
             event_handle::get_image_base64,
@@ -131,6 +238,7 @@ fn main() {
                 }
             }
             tauri::RunEvent::Exit => {
+                engine::stop_engine_worker();
                 #[cfg(target_os = "windows")]
                 {
                     hook::selection_stop();
@@ -0,0 +1,180 @@
+use crate::{
+    cmd,
+    config::get_or_array,
+    global::{NAMING, TRANSLATOR_LABEL},
+    romanize,
+    window::get_window,
+};
+use log::debug;
+use serde::Serialize;
+
+// 命名时去掉的虚词、冠词
+const FILLER_WORDS: &[&str] = &[
+    "a", "an", "the", "of", "to", "for", "and", "or", "in", "on", "at", "by", "with", "from", "is",
+    "are", "be", "been", "was", "were", "this", "that", "these", "those", "some", "any", "的",
+    "了", "地", "得", "着", "一个", "一些", "这个", "那个", "把", "被", "和", "与", "及",
+];
+
+#[derive(Debug, Clone, Serialize)]
+pub struct NamingCandidate {
+    /// camel、pascal、snake、screaming_snake、kebab、dot
+    pub style: &'static str,
+    pub value: String,
+}
+
+/// 拆分英文单词，同时拆开已有的驼峰、下划线等命名
+fn split_words(text: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    for part in text.split(|c: char| !c.is_alphanumeric()) {
+        let mut current = String::new();
+        let mut prev_lower = false;
+        for c in part.chars() {
+            if c.is_uppercase() && prev_lower && !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            prev_lower = c.is_lowercase() || c.is_numeric();
+            current.push(c);
+        }
+        if !current.is_empty() {
+            words.push(current);
+        }
+    }
+    words
+}
+
+fn has_han(text: &str) -> bool {
+    text.chars()
+        .any(|c| matches!(c as u32, 0x3400..=0x4DBF | 0x4E00..=0x9FFF | 0xF900..=0xFAFF))
+}
+
+/// 用户缩写词典，配置项 naming_abbreviations，每项格式为 单词:缩写，如 message:msg
+fn abbreviations() -> Vec<(String, String)> {
+    get_or_array("naming_abbreviations")
+        .iter()
+        .filter_map(|item| {
+            item.split_once(':')
+                .map(|(w, a)| (w.trim().to_lowercase(), a.trim().to_string()))
+        })
+        .filter(|(w, a)| !w.is_empty() && !a.is_empty())
+        .collect()
+}
+
+/// 把短语拆分为小写单词：中文先分词，仍是中文的词转为拼音；随后去掉虚词并应用缩写
+pub fn phrase_words(text: &str) -> Vec<String> {
+    let mut words: Vec<String> = Vec::new();
+    if has_han(text) {
+        for word in cmd::cut(text).unwrap_or_default() {
+            if FILLER_WORDS.contains(&word) {
+                continue;
+            }
+            if has_han(word) {
+                words.push(romanize::plain_pinyin(word));
+            } else {
+                words.extend(split_words(word));
+            }
+        }
+    } else {
+        words = split_words(text);
+    }
+    let words: Vec<String> = words
+        .into_iter()
+        .map(|w| w.to_lowercase())
+        .filter(|w| !w.is_empty())
+        .collect();
+    // 全部是虚词时保留原样，避免得到空名称
+    let filtered: Vec<String> = words
+        .iter()
+        .filter(|w| !FILLER_WORDS.contains(&w.as_str()))
+        .cloned()
+        .collect();
+    let words = if filtered.is_empty() { words } else { filtered };
+    let abbrs = abbreviations();
+    words
+        .into_iter()
+        .map(|w| match abbrs.iter().find(|(word, _)| *word == w) {
+            Some((_, abbr)) => abbr.clone(),
+            None => w,
+        })
+        .collect()
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(c) => c.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+pub fn candidates(words: &[String]) -> Vec<NamingCandidate> {
+    if words.is_empty() {
+        return Vec::new();
+    }
+    let pascal: String = words.iter().map(|w| capitalize(w)).collect();
+    let camel: String =
+        words[0].clone() + &words[1..].iter().map(|w| capitalize(w)).collect::<String>();
+    let join = |sep: &str| words.join(sep);
+    vec![
+        NamingCandidate {
+            style: "camel",
+            value: camel,
+        },
+        NamingCandidate {
+            style: "pascal",
+            value: pascal,
+        },
+        NamingCandidate {
+            style: "snake",
+            value: join("_"),
+        },
+        NamingCandidate {
+            style: "screaming_snake",
+            value: join("_").to_uppercase(),
+        },
+        NamingCandidate {
+            style: "kebab",
+            value: join("-"),
+        },
+        NamingCandidate {
+            style: "dot",
+            value: join("."),
+        },
+    ]
+}
+
+/// 生成变量命名候选，text一般为翻译后的英文短语，未翻译的中文会分词后转为拼音
+#[tauri::command]
+pub fn naming(text: &str) -> Result<Vec<NamingCandidate>, ()> {
+    Ok(candidates(&phrase_words(text)))
+}
+
+/// 用选中的命名替换当前选中的内容：隐藏翻译窗口后写入剪贴板并模拟粘贴
+#[tauri::command(async)]
+pub fn naming_replace(text: String) -> Result<(), String> {
+    if let Some(w) = get_window(TRANSLATOR_LABEL) {
+        let _ = w.hide();
+    }
+    paste(&text).map_err(|e| e.to_string())
+}
+
+fn paste(text: &str) -> Result<(), Box<dyn std::error::Error>> {
+    use arboard::Clipboard;
+    use enigo::*;
+    use std::{thread, time::Duration};
+
+    Clipboard::new()?.set_text(text)?;
+    // 等待焦点回到原窗口
+    thread::sleep(Duration::from_millis(100));
+    let mut enigo = Enigo::new();
+    #[cfg(target_os = "macos")]
+    let modifier = Key::Meta;
+    #[cfg(not(target_os = "macos"))]
+    let modifier = Key::Control;
+    enigo.key_down(modifier);
+    thread::sleep(Duration::from_millis(50));
+    enigo.key_click(Key::Layout('v'));
+    thread::sleep(Duration::from_millis(50));
+    enigo.key_up(modifier);
+    debug!("{} replace selection: {}", NAMING, text);
+    Ok(())
+}
@@ -0,0 +1,8 @@
+// 英式发音中 æ 读作长音 ɑː 的常用单词（bath类）
+pub const BATH_WORDS: &str = "dance chance advance glance france plant grant branch aunt \
+can't cant example sample demand command half calf laugh rather answer after ask bath \
+path class glass grass pass past fast last cast mask task castle master";
+
+// 美式 ɑː 在英式中多读作 ɒ，这些单词仍读 ɑː（palm类），r 前及词尾的按音素判断
+pub const PALM_WORDS: &str = "father drama llama saga lava calm palm balm psalm qualm alms \
+almond embalm napalm";
@@ -0,0 +1,492 @@
+mod data;
+
+use crate::{global::BASE_PATH, lemmatize};
+use flate2::read::GzDecoder;
+use log::{info, warn};
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    io::Read,
+};
+
+// 内置发音表，由 data/cmudict.py 从CMUdict生成，每行格式为 单词\t音素
+static BUNDLED: &[u8] = include_bytes!("../../data/cmudict.tsv.gz");
+
+// 词典目录下的CMUdict文件，按顺序查找第一个存在的文件
+const CMUDICT_FILE_NAMES: &[&str] = &["cmudict.dict", "cmudict-0.7b", "cmudict.txt"];
+
+const VOWELS: &[&str] = &[
+    "AA", "AE", "AH", "AO", "AW", "AY", "EH", "ER", "EY", "IH", "IY", "OW", "OY", "UH", "UW",
+];
+
+// 可以作为音节开头的辅音组合，用于确定重音符号的位置
+const ONSETS: &[&str] = &[
+    "P R", "P L", "B R", "B L", "T R", "D R", "K R", "K L", "G R", "G L", "F R", "F L", "TH R",
+    "SH R", "S P", "S T", "S K", "S M", "S N", "S L", "S W", "T W", "K W", "D W", "P Y", "B Y",
+    "K Y", "G Y", "F Y", "V Y", "M Y", "HH Y", "S P R", "S P L", "S T R", "S K R", "S K W",
+    "S K Y",
+];
+
+#[derive(Debug, Clone, Serialize)]
+pub struct WordPhonetic {
+    pub word: String,
+    pub us: String,
+    pub uk: String,
+    /// 发音来源：cmudict、builtin、rules
+    pub source: &'static str,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Phonetic {
+    pub text: String,
+    /// 短语为每个单词的音标以空格连接
+    pub us: String,
+    pub uk: String,
+    pub words: Vec<WordPhonetic>,
+}
+
+fn parse_dict(content: &str, sep: char, table: &mut HashMap<String, Vec<String>>) {
+    for line in content.split(sep) {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with(";;;") || line.starts_with('#') {
+            continue;
+        }
+        // 去掉行尾注释，多音词的其它读音如 word(2) 忽略
+        let line = line.split('#').next().unwrap_or_default();
+        let (word, phones) = match line.split_once(char::is_whitespace) {
+            Some(p) => p,
+            None => continue,
+        };
+        if word.ends_with(')') {
+            continue;
+        }
+        table
+            .entry(word.to_lowercase())
+            .or_insert_with(|| phones.split_whitespace().map(String::from).collect());
+    }
+}
+
+static CMUDICT: Lazy<HashMap<String, Vec<String>>> = Lazy::new(|| {
+    let mut table = HashMap::new();
+    let dir = BASE_PATH.get().unwrap().join("dictionaries");
+    for name in CMUDICT_FILE_NAMES {
+        // 0.7b为Latin-1编码，按字节读取后转换，避免非UTF-8字符导致读取失败
+        if let Ok(bytes) = fs::read(dir.join(name)) {
+            parse_dict(&String::from_utf8_lossy(&bytes), '\n', &mut table);
+            info!("cmudict loaded: {} words", table.len());
+            break;
+        }
+    }
+    table
+});
+
+static BUILTIN: Lazy<HashMap<String, Vec<String>>> = Lazy::new(|| {
+    let mut table = HashMap::new();
+    let mut content = String::new();
+    match GzDecoder::new(BUNDLED).read_to_string(&mut content) {
+        Ok(_) => parse_dict(&content, '\n', &mut table),
+        Err(e) => warn!("解压内置发音表失败：{}", e),
+    }
+    table
+});
+
+static BATH_WORDS: Lazy<HashSet<&'static str>> =
+    Lazy::new(|| data::BATH_WORDS.split_whitespace().collect());
+
+static PALM_WORDS: Lazy<HashSet<&'static str>> =
+    Lazy::new(|| data::PALM_WORDS.split_whitespace().collect());
+
+fn split_phone(phone: &str) -> (&str, Option<u8>) {
+    match phone.chars().last() {
+        Some(c @ '0'..='2') => (&phone[..phone.len() - 1], Some(c as u8 - b'0')),
+        _ => (phone, None),
+    }
+}
+
+fn is_vowel(phone: &str) -> bool {
+    VOWELS.contains(&split_phone(phone).0)
+}
+
+/// 拼读规则：按最长匹配把字母组合转为ARPAbet音素，首个元音为重读，其余元音弱读
+fn letters_to_phones(word: &str) -> Vec<String> {
+    let w: Vec<char> = word
+        .to_lowercase()
+        .chars()
+        .filter(|c| c.is_ascii_alphabetic())
+        .collect();
+    let n = w.len();
+    let is_v = |c: char| "aeiou".contains(c);
+    let at = |i: usize| w.get(i).copied().unwrap_or(' ');
+    let vowel_count = w.iter().filter(|c| is_v(**c)).count();
+    // 词尾不发音的e，单词中需有其它元音
+    let silent_e = n > 2 && w[n - 1] == 'e' && !is_v(w[n - 2]) && vowel_count > 1;
+    // 元音 + 单个辅音 + 词尾e 或 le 时读长音，如 make、table
+    let long_vowel = |i: usize| {
+        !is_v(at(i + 1))
+            && ((silent_e && i + 3 == n) || (i + 4 == n && at(i + 2) == 'l' && at(i + 3) == 'e'))
+    };
+
+    let mut out: Vec<&str> = Vec::new();
+    let mut i = 0;
+    while i < n {
+        let rest: String = w[i..].iter().collect();
+        let end = i + 1 == n;
+        let prev = if i > 0 { at(i - 1) } else { ' ' };
+        let next = at(i + 1);
+        // 多字母组合，按长度优先
+        let (phones, len): (&[&str], usize) = if rest.starts_with("tion") {
+            (&["SH", "AH0", "N"], 4)
+        } else if rest.starts_with("sion") {
+            (&["ZH", "AH0", "N"], 4)
+        } else if rest.starts_with("ture") {
+            (&["CH", "ER0"], 4)
+        } else if rest.starts_with("eigh") {
+            (&["EY"], 4)
+        } else if rest.starts_with("ough") || rest.starts_with("augh") {
+            (&["AO"], 4)
+        } else if rest.starts_with("igh") {
+            (&["AY"], 3)
+        } else if rest.starts_with("tch") {
+            (&["CH"], 3)
+        } else if rest.starts_with("dge") {
+            (&["JH"], 3)
+        } else if rest == "le" && i > 0 && !is_v(prev) {
+            (&["AH0", "L"], 2)
+        } else if rest == "ed" && n > 3 {
+            match prev {
+                't' | 'd' => (&["IH0", "D"], 2),
+                'p' | 'k' | 'f' | 's' | 'x' | 'h' | 'c' => (&["T"], 2),
+                _ => (&["D"], 2),
+            }
+        } else if rest == "ous" {
+            (&["AH0", "S"], 3)
+        } else if rest == "es" && n > 3 && "sxzhcg".contains(prev) {
+            (&["IH0", "Z"], 2)
+        } else if i == 0 && (rest.starts_with("kn") || rest.starts_with("wr")) {
+            (if next == 'n' { &["N"] } else { &["R"] }, 2)
+        } else {
+            match (at(i), next) {
+                ('p', 'h') => (&["F"], 2),
+                ('s', 'h') => (&["SH"], 2),
+                ('c', 'h') => (&["CH"], 2),
+                ('t', 'h') => (&["TH"], 2),
+                ('w', 'h') => (&["W"], 2),
+                ('c', 'k') => (&["K"], 2),
+                ('n', 'g') => (&["NG"], 2),
+                ('n', 'k') => (&["NG", "K"], 2),
+                ('q', 'u') => (&["K", "W"], 2),
+                ('g', 'h') => (&[], 2),
+                ('e', 'e') | ('e', 'a') => (&["IY"], 2),
+                ('o', 'o') => (&["UW"], 2),
+                ('o', 'u') => (&["AW"], 2),
+                ('o', 'w') => (&["OW"], 2),
+                ('o', 'i') | ('o', 'y') => (&["OY"], 2),
+                ('a', 'i') | ('a', 'y') | ('e', 'i') => (&["EY"], 2),
+                ('a', 'u') | ('a', 'w') => (&["AO"], 2),
+                ('o', 'a') => (&["OW"], 2),
+                ('e', 'w') => (&["UW"], 2),
+                ('e', 'y') if i + 2 == n => (&["IY"], 2),
+                ('i', 'e') if i + 2 == n => (&["IY"], 2),
+                ('u', 'e') if i + 2 == n => (&["UW"], 2),
+                ('a', 'r') if !is_v(at(i + 2)) => (&["AA", "R"], 2),
+                ('o', 'r') if !is_v(at(i + 2)) => (&["AO", "R"], 2),
+                ('e', 'r') | ('i', 'r') | ('u', 'r') if !is_v(at(i + 2)) => (&["ER"], 2),
+                ('e', _) if end && silent_e => (&[], 1),
+                ('a', _) if long_vowel(i) => (&["EY"], 1),
+                ('e', _) if long_vowel(i) => (&["IY"], 1),
+                ('i', _) if long_vowel(i) => (&["AY"], 1),
+                ('o', _) if long_vowel(i) => (&["OW"], 1),
+                ('u', _) if long_vowel(i) => (&["Y", "UW"], 1),
+                ('a', _) => (&["AE"], 1),
+                ('e', _) => (if end { &["IY"] } else { &["EH"] }, 1),
+                ('i', _) => (&["IH"], 1),
+                ('o', _) => (if end { &["OW"] } else { &["AA"] }, 1),
+                ('u', _) => (&["AH"], 1),
+                ('y', _) if i == 0 => (&["Y"], 1),
+                ('y', _) if end => (if vowel_count > 0 { &["IY"] } else { &["AY"] }, 1),
+                ('y', _) => (&["IH"], 1),
+                ('c', 'e' | 'i' | 'y') => (&["S"], 1),
+                ('g', 'e' | 'i' | 'y') => (&["JH"], 1),
+                ('x', _) => (&["K", "S"], 1),
+                ('s', _) if end && !"ptkf".contains(prev) => (&["Z"], 1),
+                ('b', _) => (&["B"], 1),
+                ('c', _) | ('k', _) | ('q', _) => (&["K"], 1),
+                ('d', _) => (&["D"], 1),
+                ('f', _) => (&["F"], 1),
+                ('g', _) => (&["G"], 1),
+                ('h', _) => (&["HH"], 1),
+                ('j', _) => (&["JH"], 1),
+                ('l', _) => (&["L"], 1),
+                ('m', _) => (&["M"], 1),
+                ('n', _) => (&["N"], 1),
+                ('p', _) => (&["P"], 1),
+                ('r', _) => (&["R"], 1),
+                ('s', _) => (&["S"], 1),
+                ('t', _) => (&["T"], 1),
+                ('v', _) => (&["V"], 1),
+                ('w', _) => (&["W"], 1),
+                ('z', _) => (&["Z"], 1),
+                _ => (&[], 1),
+            }
+        };
+        // 双写辅音只读一次
+        let double = len == 1 && i > 0 && at(i) == prev && !is_v(prev);
+        if !double {
+            out.extend_from_slice(phones);
+        }
+        i += len;
+    }
+
+    // 首个元音重读，其余元音弱读，弱读的短元音读作 ə
+    let mut stressed = false;
+    out.iter()
+        .map(|p| {
+            let (base, stress) = split_phone(p);
+            if !VOWELS.contains(&base) {
+                return p.to_string();
+            }
+            if stress.is_some() {
+                return p.to_string();
+            }
+            if !stressed {
+                stressed = true;
+                return format!("{}1", base);
+            }
+            match base {
+                "AE" | "AA" | "AH" | "EH" => "AH0".to_string(),
+                _ => format!("{}0", base),
+            }
+        })
+        .collect()
+}
+
+/// 重音符号放在重读音节开头：从元音向前，尽量多地把辅音划入该音节（须为合法的音节开头）
+fn stress_positions(phones: &[(&str, Option<u8>)]) -> Vec<(usize, char)> {
+    let vowel_count = phones.iter().filter(|(p, _)| VOWELS.contains(p)).count();
+    if vowel_count < 2 {
+        return Vec::new();
+    }
+    let mut marks = Vec::new();
+    let mut prev_vowel: Option<usize> = None;
+    for (i, (phone, stress)) in phones.iter().enumerate() {
+        if !VOWELS.contains(phone) {
+            continue;
+        }
+        let lower = prev_vowel.map_or(0, |v| v + 1);
+        let mut start = i;
+        while start > lower {
+            let cluster: Vec<&str> = phones[start - 1..i].iter().map(|(p, _)| *p).collect();
+            let valid = match cluster.len() {
+                1 => cluster[0] != "NG",
+                _ => ONSETS.contains(&cluster.join(" ").as_str()),
+            };
+            if !valid {
+                break;
+            }
+            start -= 1;
+        }
+        // 单词开头的辅音都属于第一个音节
+        if prev_vowel.is_none() {
+            start = 0;
+        }
+        match stress {
+            Some(1) => marks.push((start, 'ˈ')),
+            Some(2) => marks.push((start, 'ˌ')),
+            _ => {}
+        }
+        prev_vowel = Some(i);
+    }
+    marks
+}
+
+fn consonant_ipa(phone: &str) -> &'static str {
+    match phone {
+        "B" => "b",
+        "CH" => "tʃ",
+        "D" => "d",
+        "DH" => "ð",
+        "F" => "f",
+        "G" => "ɡ",
+        "HH" => "h",
+        "JH" => "dʒ",
+        "K" => "k",
+        "L" => "l",
+        "M" => "m",
+        "N" => "n",
+        "NG" => "ŋ",
+        "P" => "p",
+        "R" => "r",
+        "S" => "s",
+        "SH" => "ʃ",
+        "T" => "t",
+        "TH" => "θ",
+        "V" => "v",
+        "W" => "w",
+        "Y" => "j",
+        "Z" => "z",
+        "ZH" => "ʒ",
+        _ => "",
+    }
+}
+
+fn us_vowel(phone: &str, stress: Option<u8>, last: bool) -> &'static str {
+    let stressed = stress.map_or(true, |s| s > 0);
+    match phone {
+        "AA" => "ɑː",
+        "AE" => "æ",
+        "AH" if stressed => "ʌ",
+        "AH" => "ə",
+        "AO" => "ɔː",
+        "AW" => "aʊ",
+        "AY" => "aɪ",
+        "EH" => "e",
+        "ER" if stressed => "ɜːr",
+        "ER" => "ər",
+        "EY" => "eɪ",
+        "IH" => "ɪ",
+        // 词尾弱读的 i 不标长音，如 happy
+        "IY" if !stressed && last => "i",
+        "IY" => "iː",
+        "OW" => "oʊ",
+        "OY" => "ɔɪ",
+        "UH" => "ʊ",
+        "UW" => "uː",
+        _ => "",
+    }
+}
+
+/// 英式发音：非元音前的 r 不发音并改变前面的元音，oʊ 读作 əʊ，
+/// ɑː 除 r 前、词尾及palm类单词外读作 ɒ，ŋ、ɡ、f、θ 前的 ɔː 读作 ɒ，bath类单词的 æ 读作 ɑː。
+/// rest 为该元音之后的音素
+fn uk_vowel(
+    word: &str,
+    phone: &str,
+    stress: Option<u8>,
+    last: bool,
+    rest: &[&str],
+) -> &'static str {
+    let stressed = stress.map_or(true, |s| s > 0);
+    let next = rest.first().copied().unwrap_or_default();
+    let before_r = next == "R" && !rest.get(1).map_or(false, |p| VOWELS.contains(p));
+    match phone {
+        "AA" if before_r || rest.is_empty() => "ɑː",
+        "AA" if PALM_WORDS.contains(word) => "ɑː",
+        "AA" => "ɒ",
+        "AE" if stressed && BATH_WORDS.contains(word) => "ɑː",
+        "AO" if matches!(next, "NG" | "G" | "F" | "TH") => "ɒ",
+        "AO" => "ɔː",
+        "AW" if before_r => "aʊə",
+        "AY" if before_r => "aɪə",
+        "EH" | "EY" if before_r => "eə",
+        "IH" | "IY" if before_r => "ɪə",
+        "UH" | "UW" if before_r => "ʊə",
+        "ER" if stressed => "ɜː",
+        "ER" => "ə",
+        "OW" => "əʊ",
+        "IY" if !stressed && last => "i",
+        _ => us_vowel(phone, stress, last),
+    }
+}
+
+/// ARPAbet音素转为国际音标
+fn phones_to_ipa(word: &str, phones: &[String], uk: bool) -> String {
+    let parsed: Vec<(&str, Option<u8>)> = phones.iter().map(|p| split_phone(p)).collect();
+    let bases: Vec<&str> = parsed.iter().map(|(p, _)| *p).collect();
+    let marks = stress_positions(&parsed);
+    let last_vowel = parsed.iter().rposition(|(p, _)| VOWELS.contains(p));
+    let mut out = String::new();
+    for (i, (phone, stress)) in parsed.iter().enumerate() {
+        if let Some((_, mark)) = marks.iter().find(|(pos, _)| *pos == i) {
+            out.push(*mark);
+        }
+        let next_is_vowel = parsed.get(i + 1).map_or(false, |(p, _)| VOWELS.contains(p));
+        if VOWELS.contains(phone) {
+            let last = Some(i) == last_vowel && i + 1 == parsed.len();
+            if uk {
+                out.push_str(uk_vowel(word, phone, *stress, last, &bases[i + 1..]));
+            } else {
+                out.push_str(us_vowel(phone, *stress, last));
+            }
+        } else if *phone == "R" && uk && !next_is_vowel {
+            // 英式发音中元音前的 r 才发音
+            continue;
+        } else {
+            out.push_str(consonant_ipa(phone));
+        }
+    }
+    out
+}
+
+/// 查询单词的ARPAbet音素：CMUdict、内置词表，仍找不到时使用拼读规则
+fn word_phones(word: &str) -> (Vec<String>, &'static str) {
+    let lower = word.to_lowercase();
+    if let Some(p) = CMUDICT.get(&lower) {
+        return (p.clone(), "cmudict");
+    }
+    if let Some(p) = BUILTIN.get(&lower) {
+        return (p.clone(), "builtin");
+    }
+    (letters_to_phones(&lower), "rules")
+}
+
+pub fn word_phonetic(word: &str) -> WordPhonetic {
+    let lower = word.to_lowercase();
+    let (phones, source) = word_phones(&lower);
+    // bath、palm类单词的屈折形式按原形判断
+    let lemma = lemmatize::lemma_of(&lower);
+    let bath_key = if BATH_WORDS.contains(lower.as_str()) || lemma.is_empty() {
+        lower.as_str()
+    } else {
+        lemma.as_str()
+    };
+    WordPhonetic {
+        word: word.to_string(),
+        us: phones_to_ipa(&lower, &phones, false),
+        uk: phones_to_ipa(bath_key, &phones, true),
+        source,
+    }
+}
+
+/// 逐词标注音标，非英文单词忽略
+pub fn phonetic_text(text: &str) -> Phonetic {
+    let words: Vec<WordPhonetic> = text
+        .split(|c: char| !(c.is_ascii_alphabetic() || c == '\''))
+        .map(|w| w.trim_matches('\''))
+        .filter(|w| !w.is_empty())
+        .map(word_phonetic)
+        .collect();
+    let join = |f: fn(&WordPhonetic) -> &str| words.iter().map(f).collect::<Vec<&str>>().join(" ");
+    Phonetic {
+        text: text.to_string(),
+        us: join(|w| &w.us),
+        uk: join(|w| &w.uk),
+        words,
+    }
+}
+
+#[tauri::command(async)]
+pub fn phonetic(text: &str) -> Result<Phonetic, ()> {
+    Ok(phonetic_text(text))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn uk(word: &str, phones: &str) -> String {
+        let phones: Vec<String> = phones.split(' ').map(String::from).collect();
+        phones_to_ipa(word, &phones, true)
+    }
+
+    #[test]
+    fn uk_palm_vowel() {
+        assert_eq!(uk("doll", "D AA1 L"), "dɒl");
+        assert_eq!(uk("bomb", "B AA1 M"), "bɒm");
+        assert_eq!(uk("golf", "G AA1 L F"), "ɡɒlf");
+        assert_eq!(uk("calm", "K AA1 M"), "kɑːm");
+        assert_eq!(uk("palm", "P AA1 M"), "pɑːm");
+        assert_eq!(uk("car", "K AA1 R"), "kɑː");
+    }
+}
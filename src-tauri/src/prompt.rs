@@ -0,0 +1,248 @@
+use crate::{
+    config::{get_config_with_default, get_or_string},
+    global::TRANSLATOR_LABEL,
+    glossary, lang_detect, translator,
+    window::emit_to,
+};
+use log::debug;
+use serde::{Deserialize, Serialize};
+
+/// 提示词改写的托盘菜单、快捷键名称前缀，如 prompt:polish
+pub const PROMPT_PREFIX: &str = "prompt:";
+/// 通知翻译窗口执行提示词改写
+pub const PROMPT_EVENT: &str = "translator://prompt";
+
+const GLOSSARY_VAR: &str = "{glossary}";
+
+fn default_true() -> bool {
+    true
+}
+
+/// 提示词模板，可使用变量 {text} {from} {to} {app} {glossary}
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PromptPreset {
+    pub id: String,
+    pub name: String,
+    /// 系统提示词
+    #[serde(default)]
+    pub system: String,
+    pub prompt: String,
+    /// 使用的大模型服务 id 或名称，为空时使用配置 prompt_service
+    #[serde(default)]
+    pub service: String,
+    #[serde(default)]
+    pub hotkey: String,
+    /// 显示在托盘菜单中
+    #[serde(default = "default_true")]
+    pub tray: bool,
+    /// 显示在划词工具栏中
+    #[serde(default = "default_true")]
+    pub selection: bool,
+    #[serde(default, skip_deserializing)]
+    pub builtin: bool,
+}
+
+const SYSTEM: &str = "You are a professional writing assistant. Only output the result, without any explanation or quotation marks.\n{glossary}";
+
+/// 内置预设：id、名称、提示词
+const BUILTIN: &[(&str, &str, &str)] = &[
+    (
+        "polish",
+        "润色",
+        "Polish the following {from} text to make it clearer, more natural and fluent, keeping its original meaning and language:\n\"\"\"\n{text}\n\"\"\"",
+    ),
+    (
+        "summarize",
+        "总结",
+        "Summarize the key points of the following text in {to}, concisely:\n\"\"\"\n{text}\n\"\"\"",
+    ),
+    (
+        "grammar",
+        "语法讲解",
+        "Explain the grammar of the following {from} text in {to}: break down the sentence structure, explain key grammar points and usage, and point out any mistakes:\n\"\"\"\n{text}\n\"\"\"",
+    ),
+    (
+        "formal",
+        "正式语气",
+        "Rewrite the following {from} text in a formal, professional tone suitable for {app}, keeping its original meaning and language:\n\"\"\"\n{text}\n\"\"\"",
+    ),
+    (
+        "casual",
+        "口语化",
+        "Rewrite the following {from} text in a casual, friendly and conversational tone, keeping its original meaning and language:\n\"\"\"\n{text}\n\"\"\"",
+    ),
+    (
+        "typo",
+        "纠正错别字",
+        "Fix the typos, spelling and grammatical errors in the following {from} text. Do not change its meaning, style or language:\n\"\"\"\n{text}\n\"\"\"",
+    ),
+];
+
+fn builtin_presets() -> Vec<PromptPreset> {
+    BUILTIN
+        .iter()
+        .map(|(id, name, prompt)| PromptPreset {
+            id: id.to_string(),
+            name: name.to_string(),
+            system: SYSTEM.to_string(),
+            prompt: prompt.to_string(),
+            service: String::new(),
+            hotkey: String::new(),
+            tray: true,
+            selection: true,
+            builtin: true,
+        })
+        .collect()
+}
+
+/// 内置预设和配置 prompt_presets 中的自定义预设，id 与内置预设相同时覆盖内置预设
+pub fn presets() -> Vec<PromptPreset> {
+    let custom: Vec<PromptPreset> = get_config_with_default("prompt_presets", Vec::new(), |v| {
+        serde_json::from_value(v.clone()).ok()
+    });
+    let mut list = builtin_presets();
+    for preset in custom {
+        if preset.id.trim().is_empty() || preset.prompt.trim().is_empty() {
+            continue;
+        }
+        match list.iter_mut().find(|p| p.id == preset.id) {
+            Some(p) => {
+                *p = PromptPreset {
+                    builtin: true,
+                    ..preset
+                }
+            }
+            None => list.push(preset),
+        }
+    }
+    list
+}
+
+pub fn preset(id: &str) -> Option<PromptPreset> {
+    presets().into_iter().find(|p| p.id == id)
+}
+
+/// 模板变量，from to 为语种的英文名称
+pub struct PromptVars<'a> {
+    pub text: &'a str,
+    pub from: &'a str,
+    pub to: &'a str,
+    pub app: &'a str,
+    pub glossary: &'a str,
+}
+
+/// 替换模板变量，原文最后替换，避免原文中的占位符被替换
+pub fn render(template: &str, vars: &PromptVars) -> String {
+    let app = if vars.app.is_empty() {
+        "the current application"
+    } else {
+        vars.app
+    };
+    let result = template
+        .replace("{from}", vars.from)
+        .replace("{to}", vars.to)
+        .replace("{app}", app);
+    // 术语表为空时去掉所在的行
+    let result = if vars.glossary.is_empty() {
+        result
+            .lines()
+            .filter(|line| line.trim() != GLOSSARY_VAR)
+            .collect::<Vec<_>>()
+            .join("\n")
+            .replace(GLOSSARY_VAR, "")
+    } else {
+        result.replace(GLOSSARY_VAR, vars.glossary)
+    };
+    result.replace("{text}", vars.text).trim().to_string()
+}
+
+/// 当前活动窗口的应用名称，活动窗口为本应用时为空
+pub fn active_app() -> String {
+    match active_win_pos_rs::get_active_window() {
+        Ok(w) if w.process_id != std::process::id() as u64 => w.app_name,
+        _ => String::new(),
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PromptRequest {
+    /// 请求标识，用于取消和匹配 translator://partial 事件
+    pub id: String,
+    pub preset: String,
+    pub text: String,
+    /// 来源应用，未设置时为当前活动窗口
+    #[serde(default)]
+    pub app: Option<String>,
+    /// 目标语种，未设置时使用配置 to
+    #[serde(default)]
+    pub to: Option<String>,
+}
+
+pub async fn run(request: PromptRequest) -> Result<translator::Completion, String> {
+    let text = request.text.trim();
+    if text.is_empty() {
+        return Err("内容为空".to_string());
+    }
+    let preset =
+        preset(&request.preset).ok_or_else(|| format!("未找到提示词预设：{}", request.preset))?;
+    let from = lang_detect::detect(text);
+    let to = request
+        .to
+        .filter(|s| !s.is_empty())
+        .unwrap_or_else(|| get_or_string("to", "zh_cn".to_string()));
+    let app = request.app.unwrap_or_else(active_app);
+    let glossary = glossary::prompt_instruction(text, from, &to);
+    let vars = PromptVars {
+        text,
+        from: translator::language_name(from),
+        to: translator::language_name(&to),
+        app: &app,
+        glossary: &glossary,
+    };
+    let service = if preset.service.is_empty() {
+        get_or_string("prompt_service", String::new())
+    } else {
+        preset.service.clone()
+    };
+    debug!("prompt {} {} -> {}", preset.id, from, to);
+    translator::complete(
+        &request.id,
+        &service,
+        &render(&preset.system, &vars),
+        &render(&preset.prompt, &vars),
+    )
+    .await
+}
+
+/// 快捷键、托盘菜单触发：获取选中的文本，在翻译窗口中执行
+pub fn invoke_preset(id: &str, text: String) {
+    let text = text.trim();
+    if text.is_empty() {
+        return;
+    }
+    let Some(preset) = preset(id) else {
+        return;
+    };
+    emit_to(
+        TRANSLATOR_LABEL,
+        PROMPT_EVENT,
+        serde_json::json!({
+            "preset": preset.id,
+            "name": preset.name,
+            "text": text,
+            "app": active_app(),
+        }),
+    );
+}
+
+/// 所有预设，包括内置预设
+#[tauri::command]
+pub fn prompt_presets() -> Result<Vec<PromptPreset>, ()> {
+    Ok(presets())
+}
+
+/// 执行提示词改写，结果通过 translator://partial 事件逐段返回
+#[tauri::command]
+pub async fn prompt_run(request: PromptRequest) -> Result<translator::Completion, String> {
+    run(request).await
+}
@@ -0,0 +1,152 @@
+// 韩语罗马字表记法（Revised Romanization），按音节分解为初声、中声、终声后转写
+
+const INITIALS: [&str; 19] = [
+    "g", "kk", "n", "d", "tt", "r", "m", "b", "pp", "s", "ss", "", "j", "jj", "ch", "k", "t", "p",
+    "h",
+];
+const MEDIALS: [&str; 21] = [
+    "a", "ae", "ya", "yae", "eo", "e", "yeo", "ye", "o", "wa", "wae", "oe", "yo", "u", "wo", "we",
+    "wi", "yu", "eu", "ui", "i",
+];
+// 终声在词尾或辅音前的代表音
+const FINALS: [&str; 28] = [
+    "", "k", "k", "k", "n", "n", "n", "t", "l", "k", "m", "l", "l", "l", "p", "l", "m", "p", "p",
+    "t", "t", "ng", "t", "t", "k", "t", "p", "t",
+];
+// 终声后接元音（初声ㅇ）时连读：(留在本音节的部分, 移到下一音节的初声)
+const LIAISON: [(&str, &str); 28] = [
+    ("", ""),
+    ("", "g"),
+    ("", "kk"),
+    ("k", "s"),
+    ("", "n"),
+    ("n", "j"),
+    ("", "n"),
+    ("", "d"),
+    ("", "r"),
+    ("l", "g"),
+    ("l", "m"),
+    ("l", "b"),
+    ("l", "s"),
+    ("l", "t"),
+    ("l", "p"),
+    ("", "r"),
+    ("", "m"),
+    ("", "b"),
+    ("p", "s"),
+    ("", "s"),
+    ("", "ss"),
+    ("ng", ""),
+    ("", "j"),
+    ("", "ch"),
+    ("", "k"),
+    ("", "t"),
+    ("", "p"),
+    ("", ""),
+];
+
+const INITIAL_IEUNG: usize = 11;
+const INITIAL_NIEUN: usize = 2;
+const INITIAL_RIEUL: usize = 5;
+const INITIAL_MIEUM: usize = 6;
+const MEDIAL_I: usize = 20;
+const FINAL_NIEUN: usize = 4;
+const FINAL_TIKEUT: usize = 7;
+const FINAL_THIEUTH: usize = 25;
+
+fn decompose(c: char) -> Option<(usize, usize, usize)> {
+    let cp = c as u32;
+    if !(0xAC00..=0xD7A3).contains(&cp) {
+        return None;
+    }
+    let index = (cp - 0xAC00) as usize;
+    Some((index / 588, (index % 588) / 28, index % 28))
+}
+
+/// 转写一个语节，处理连读、鼻音化和流音化，非谚文字符原样保留
+pub fn romanize(text: &str) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let syllables: Vec<Option<(usize, usize, usize)>> =
+        chars.iter().map(|c| decompose(*c)).collect();
+    let mut out = String::new();
+    // 上一音节终声移过来的初声
+    let mut carried: Option<&str> = None;
+    for (i, syllable) in syllables.iter().enumerate() {
+        let (initial, medial, final_) = match syllable {
+            Some(s) => *s,
+            None => {
+                out.push(chars[i]);
+                carried = None;
+                continue;
+            }
+        };
+        match carried.take() {
+            Some(c) => out.push_str(c),
+            None => out.push_str(INITIALS[initial]),
+        }
+        out.push_str(MEDIALS[medial]);
+        let next = syllables.get(i + 1).copied().flatten();
+        let final_roman = match next {
+            Some((next_initial, next_medial, _)) if final_ > 0 => {
+                if next_initial == INITIAL_IEUNG
+                    && next_medial == MEDIAL_I
+                    && final_ == FINAL_TIKEUT
+                {
+                    // 腭化：ㄷ、ㅌ后接이读作ㅈ、ㅊ
+                    carried = Some("j");
+                    ""
+                } else if next_initial == INITIAL_IEUNG
+                    && next_medial == MEDIAL_I
+                    && final_ == FINAL_THIEUTH
+                {
+                    carried = Some("ch");
+                    ""
+                } else if next_initial == INITIAL_IEUNG {
+                    let (remain, moved) = LIAISON[final_];
+                    if !moved.is_empty() {
+                        carried = Some(moved);
+                    }
+                    remain
+                } else if next_initial == INITIAL_NIEUN || next_initial == INITIAL_MIEUM {
+                    // 鼻音化：ㄱ、ㄷ、ㅂ类终声在ㄴ、ㅁ前读作ㅇ、ㄴ、ㅁ
+                    match FINALS[final_] {
+                        "k" => "ng",
+                        "t" => "n",
+                        "p" => "m",
+                        "l" if next_initial == INITIAL_NIEUN => {
+                            carried = Some("l");
+                            "l"
+                        }
+                        f => f,
+                    }
+                } else if next_initial == INITIAL_RIEUL {
+                    // ㄹ前的ㄴ、ㄹ读作ll，其余终声后的ㄹ读作ㄴ
+                    match FINALS[final_] {
+                        "l" => {
+                            carried = Some("l");
+                            "l"
+                        }
+                        "n" if final_ == FINAL_NIEUN => {
+                            carried = Some("l");
+                            "l"
+                        }
+                        f => {
+                            carried = Some("n");
+                            match f {
+                                "k" => "ng",
+                                "t" => "n",
+                                "p" => "m",
+                                f => f,
+                            }
+                        }
+                    }
+                } else {
+                    FINALS[final_]
+                }
+            }
+            _ => FINALS[final_],
+        };
+        out.push_str(final_roman);
+    }
+    out
+}
@@ -0,0 +1,148 @@
+use once_cell::sync::Lazy;
+use pinyin::ToPinyin;
+use std::collections::HashMap;
+
+// 常见多音字词组的读音，数字声调，5为轻声；分词后整词命中时使用，未命中逐字取默认读音
+const POLYPHONE_PHRASES: &str = "\
+银行:yin2,hang2 行业:hang2,ye4 行情:hang2,qing2 同行:tong2,hang2 内行:nei4,hang2 外行:wai4,hang2 \
+行列:hang2,lie4 排行:pai2,hang2 行长:hang2,zhang3 一行:yi4,hang2 \
+长大:zhang3,da4 成长:cheng2,zhang3 校长:xiao4,zhang3 长辈:zhang3,bei4 生长:sheng1,zhang3 \
+增长:zeng1,zhang3 部长:bu4,zhang3 市长:shi4,zhang3 队长:dui4,zhang3 家长:jia1,zhang3 \
+重新:chong2,xin1 重复:chong2,fu4 重庆:chong2,qing4 重叠:chong2,die2 重阳:chong2,yang2 重来:chong2,lai2 \
+音乐:yin1,yue4 乐器:yue4,qi4 乐队:yue4,dui4 乐曲:yue4,qu3 乐谱:yue4,pu3 \
+了解:liao3,jie3 了不起:liao3,bu4,qi3 明了:ming2,liao3 \
+睡觉:shui4,jiao4 午觉:wu3,jiao4 \
+还是:hai2,shi4 还有:hai2,you3 还要:hai2,yao4 还在:hai2,zai4 还没:hai2,mei2 \
+得到:de2,dao4 获得:huo4,de2 取得:qu3,de2 值得:zhi2,de5 觉得:jue2,de5 记得:ji4,de5 \
+难得:nan2,de2 不得不:bu4,de2,bu4 得分:de2,fen1 \
+数学:shu4,xue2 数据:shu4,ju4 数字:shu4,zi4 数量:shu4,liang4 数目:shu4,mu4 \
+地方:di4,fang1 地图:di4,tu2 地区:di4,qu1 \
+着急:zhao2,ji2 着火:zhao2,huo3 睡着:shui4,zhao2 着手:zhuo2,shou3 着重:zhuo2,zhong4 \
+着想:zhuo2,xiang3 衣着:yi1,zhuo2 \
+便宜:pian2,yi5 大便:da4,bian4 方便:fang1,bian4 \
+会计:kuai4,ji4 \
+朝阳:zhao1,yang2 朝代:chao2,dai4 朝鲜:chao2,xian3 \
+和平:he2,ping2 暖和:nuan3,huo5 搅和:jiao3,huo5 \
+好奇:hao4,qi2 爱好:ai4,hao4 好客:hao4,ke4 \
+为了:wei4,le5 因为:yin1,wei4 为什么:wei4,shen2,me5 认为:ren4,wei2 作为:zuo4,wei2 成为:cheng2,wei2 \
+调查:diao4,cha2 调动:diao4,dong4 音调:yin1,diao4 声调:sheng1,diao4 空调:kong1,tiao2 调整:tiao2,zheng3 \
+都市:du1,shi4 首都:shou3,du1 \
+传记:zhuan4,ji4 自传:zi4,zhuan4 \
+差不多:cha4,bu5,duo1 差别:cha1,bie2 出差:chu1,chai1 差距:cha1,ju4 \
+中奖:zhong4,jiang3 打中:da3,zhong4 \
+教书:jiao1,shu1 \
+发现:fa1,xian4 头发:tou2,fa5 理发:li3,fa4 \
+处理:chu3,li3 处分:chu3,fen4 到处:dao4,chu4 好处:hao3,chu5 \
+应该:ying1,gai1 答应:da1,ying5 应用:ying4,yong4 \
+参差:cen1,ci1 人参:ren2,shen1 \
+尽管:jin3,guan3 尽量:jin3,liang4 \
+角色:jue2,se4 主角:zhu3,jue2 \
+薄荷:bo4,he5 \
+似的:shi4,de5 \
+大夫:dai4,fu5 \
+东西:dong1,xi5 什么:shen2,me5 怎么:zen3,me5 这么:zhe4,me5 那么:na4,me5 多么:duo1,me5 \
+";
+
+static PHRASES: Lazy<HashMap<&'static str, Vec<&'static str>>> = Lazy::new(|| {
+    POLYPHONE_PHRASES
+        .split_whitespace()
+        .filter_map(|pair| pair.split_once(':'))
+        .map(|(word, py)| (word, py.split(',').collect()))
+        .collect()
+});
+
+/// 拼音声调的显示方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToneStyle {
+    /// 声调符号，如 zhōng
+    Mark,
+    /// 数字声调，如 zhong1，轻声为5
+    Number,
+    /// 不带声调
+    None,
+}
+
+impl ToneStyle {
+    pub fn parse(style: &str) -> ToneStyle {
+        match style {
+            "num" | "number" => ToneStyle::Number,
+            "none" | "plain" => ToneStyle::None,
+            _ => ToneStyle::Mark,
+        }
+    }
+}
+
+const TONE_MARKS: [(char, [char; 4]); 6] = [
+    ('a', ['ā', 'á', 'ǎ', 'à']),
+    ('e', ['ē', 'é', 'ě', 'è']),
+    ('i', ['ī', 'í', 'ǐ', 'ì']),
+    ('o', ['ō', 'ó', 'ǒ', 'ò']),
+    ('u', ['ū', 'ú', 'ǔ', 'ù']),
+    ('ü', ['ǖ', 'ǘ', 'ǚ', 'ǜ']),
+];
+
+/// 把数字声调的拼音转换为指定格式；标调规则：有a、e标在a、e上，ou标在o上，否则标在最后一个元音上
+fn format_syllable(numbered: &str, style: ToneStyle) -> String {
+    let (base, tone) = match numbered.chars().last().and_then(|c| c.to_digit(10)) {
+        Some(t) => (&numbered[..numbered.len() - 1], t),
+        None => (numbered, 5),
+    };
+    let base = base.replace('v', "ü");
+    match style {
+        ToneStyle::Number => format!("{}{}", base.replace('ü', "v"), tone),
+        ToneStyle::None => base,
+        ToneStyle::Mark => {
+            if !(1..=4).contains(&tone) {
+                return base;
+            }
+            let chars: Vec<char> = base.chars().collect();
+            let is_vowel = |c: &char| TONE_MARKS.iter().any(|(v, _)| v == c);
+            let index = chars
+                .iter()
+                .position(|c| *c == 'a' || *c == 'e')
+                .or_else(|| {
+                    if base.contains("ou") {
+                        chars.iter().position(|c| *c == 'o')
+                    } else {
+                        None
+                    }
+                })
+                .or_else(|| chars.iter().rposition(is_vowel));
+            match index {
+                Some(i) => chars
+                    .iter()
+                    .enumerate()
+                    .map(|(j, c)| {
+                        if i == j {
+                            TONE_MARKS.iter().find(|(v, _)| v == c).unwrap().1[tone as usize - 1]
+                        } else {
+                            *c
+                        }
+                    })
+                    .collect(),
+                None => base,
+            }
+        }
+    }
+}
+
+/// 单字的默认读音，数字声调
+fn char_pinyin(c: char) -> Option<String> {
+    c.to_pinyin().map(|p| p.with_tone_num_end().to_string())
+}
+
+/// 一个词中每个字的拼音，非汉字为None；整词在多音字词表中时使用词表读音
+pub fn word_pinyin(word: &str, style: ToneStyle) -> Vec<(char, Option<String>)> {
+    let chars: Vec<char> = word.chars().collect();
+    match PHRASES.get(word) {
+        Some(list) if list.len() == chars.len() => chars
+            .into_iter()
+            .zip(list.iter())
+            .map(|(c, py)| (c, Some(format_syllable(py, style))))
+            .collect(),
+        _ => chars
+            .into_iter()
+            .map(|c| (c, char_pinyin(c).map(|py| format_syllable(&py, style))))
+            .collect(),
+    }
+}
@@ -0,0 +1,141 @@
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+
+// 平假名与黑本式罗马字对照，拗音放在前面以便优先匹配两个字符
+const HEPBURN: &str = "\
+きゃ:kya きゅ:kyu きょ:kyo しゃ:sha しゅ:shu しょ:sho ちゃ:cha ちゅ:chu ちょ:cho \
+にゃ:nya にゅ:nyu にょ:nyo ひゃ:hya ひゅ:hyu ひょ:hyo みゃ:mya みゅ:myu みょ:myo \
+りゃ:rya りゅ:ryu りょ:ryo ぎゃ:gya ぎゅ:gyu ぎょ:gyo じゃ:ja じゅ:ju じょ:jo \
+ぢゃ:ja ぢゅ:ju ぢょ:jo びゃ:bya びゅ:byu びょ:byo ぴゃ:pya ぴゅ:pyu ぴょ:pyo \
+しぇ:she ちぇ:che じぇ:je てぃ:ti でぃ:di とぅ:tu どぅ:du でゅ:dyu ふぁ:fa ふぃ:fi ふぇ:fe ふぉ:fo \
+うぃ:wi うぇ:we うぉ:wo ゔぁ:va ゔぃ:vi ゔぇ:ve ゔぉ:vo つぁ:tsa つぃ:tsi つぇ:tse つぉ:tso \
+あ:a い:i う:u え:e お:o か:ka き:ki く:ku け:ke こ:ko さ:sa し:shi す:su せ:se そ:so \
+た:ta ち:chi つ:tsu て:te と:to な:na に:ni ぬ:nu ね:ne の:no は:ha ひ:hi ふ:fu へ:he ほ:ho \
+ま:ma み:mi む:mu め:me も:mo や:ya ゆ:yu よ:yo ら:ra り:ri る:ru れ:re ろ:ro \
+わ:wa ゐ:i ゑ:e を:o ん:n が:ga ぎ:gi ぐ:gu げ:ge ご:go ざ:za じ:ji ず:zu ぜ:ze ぞ:zo \
+だ:da ぢ:ji づ:zu で:de ど:do ば:ba び:bi ぶ:bu べ:be ぼ:bo ぱ:pa ぴ:pi ぷ:pu ぺ:pe ぽ:po \
+ゔ:vu ぁ:a ぃ:i ぅ:u ぇ:e ぉ:o ゃ:ya ゅ:yu ょ:yo ゎ:wa \
+";
+
+static TABLE: Lazy<HashMap<&'static str, &'static str>> = Lazy::new(|| {
+    HEPBURN
+        .split_whitespace()
+        .filter_map(|pair| pair.split_once(':'))
+        .collect()
+});
+
+pub fn is_kana(c: char) -> bool {
+    matches!(c as u32, 0x3041..=0x309F | 0x30A1..=0x30FF | 0xFF66..=0xFF9F)
+}
+
+pub fn is_kanji(c: char) -> bool {
+    matches!(c as u32, 0x3005 | 0x3007 | 0x3400..=0x4DBF | 0x4E00..=0x9FFF | 0xF900..=0xFAFF)
+}
+
+/// 片假名转平假名，其余字符不变
+pub fn to_hiragana(text: &str) -> String {
+    text.chars()
+        .map(|c| match c as u32 {
+            0x30A1..=0x30F6 => char::from_u32(c as u32 - 0x60).unwrap_or(c),
+            _ => c,
+        })
+        .collect()
+}
+
+/// 假名转黑本式罗马字：促音重复下一个辅音（ch前为t），长音符号重复前一个元音，
+/// ん在元音、y前加撇号以免歧义；非假名字符原样保留
+pub fn to_romaji(kana: &str) -> String {
+    let chars: Vec<char> = to_hiragana(kana).chars().collect();
+    let mut out = String::new();
+    let mut sokuon = false;
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c == 'っ' {
+            sokuon = true;
+            i += 1;
+            continue;
+        }
+        if c == 'ー' {
+            if let Some(v) = out.chars().last().filter(|v| "aeiou".contains(*v)) {
+                out.push(v);
+            }
+            i += 1;
+            continue;
+        }
+        let two: String = chars[i..chars.len().min(i + 2)].iter().collect();
+        let (roman, len) = match TABLE.get(two.as_str()) {
+            Some(r) if two.chars().count() == 2 => (Some(*r), 2),
+            _ => (TABLE.get(c.to_string().as_str()).copied(), 1),
+        };
+        match roman {
+            Some(r) => {
+                if sokuon {
+                    if r.starts_with("ch") {
+                        out.push('t');
+                    } else if let Some(first) = r.chars().next().filter(|f| !"aeiou".contains(*f)) {
+                        out.push(first);
+                    }
+                }
+                if out.ends_with('n')
+                    && i > 0
+                    && chars[i - 1] == 'ん'
+                    && (r.starts_with(|f: char| "aeiouy".contains(f)))
+                {
+                    out.push('\'');
+                }
+                out.push_str(r);
+            }
+            None => {
+                if sokuon {
+                    // 句末的促音
+                    out.push('\'');
+                }
+                out.extend(&chars[i..i + len]);
+            }
+        }
+        sokuon = false;
+        i += len;
+    }
+    out
+}
+
+/// 把带送假名的词拆分为汉字部分和假名部分，只为汉字部分标注读音，
+/// 返回 (字节开始, 字节结束, 振假名)，振假名为None的部分不需要标注
+pub fn split_okurigana(surface: &str, reading: &str) -> Vec<(usize, usize, Option<String>)> {
+    let reading = to_hiragana(reading);
+    if !surface.chars().any(is_kanji) || to_hiragana(surface) == reading {
+        return vec![(0, surface.len(), None)];
+    }
+    let s: Vec<(usize, char)> = surface.char_indices().collect();
+    let r: Vec<char> = reading.chars().collect();
+    let hira = |c: char| to_hiragana(&c.to_string()).chars().next().unwrap_or(c);
+    // 去掉首尾相同的假名
+    let mut prefix = 0;
+    while prefix < s.len()
+        && prefix < r.len()
+        && !is_kanji(s[prefix].1)
+        && hira(s[prefix].1) == r[prefix]
+    {
+        prefix += 1;
+    }
+    let mut suffix = 0;
+    while suffix < s.len() - prefix
+        && suffix < r.len() - prefix
+        && !is_kanji(s[s.len() - 1 - suffix].1)
+        && hira(s[s.len() - 1 - suffix].1) == r[r.len() - 1 - suffix]
+    {
+        suffix += 1;
+    }
+    let byte = |i: usize| s.get(i).map(|(b, _)| *b).unwrap_or(surface.len());
+    let ruby: String = r[prefix..r.len() - suffix].iter().collect();
+    let mut parts = Vec::new();
+    if prefix > 0 {
+        parts.push((0, byte(prefix), None));
+    }
+    parts.push((byte(prefix), byte(s.len() - suffix), Some(ruby)));
+    if suffix > 0 {
+        parts.push((byte(s.len() - suffix), surface.len(), None));
+    }
+    parts
+}
@@ -0,0 +1,181 @@
+mod hangul;
+mod hanzi;
+mod kana;
+
+use crate::{lang_detect, tokenize};
+use hanzi::ToneStyle;
+use serde::Serialize;
+
+/// 注音结果，按片段顺序覆盖整个原文，前端按ruby方式显示在文字上方
+#[derive(Debug, Clone, Serialize)]
+pub struct Ruby {
+    pub text: String,
+    /// 开始字节位置
+    pub start: usize,
+    /// 结束字节位置（不包含）
+    pub end: usize,
+    /// 开始字符位置
+    pub char_start: usize,
+    /// 结束字符位置（不包含）
+    pub char_end: usize,
+    /// 显示在上方的注音：中文为拼音，日语为振假名（仅汉字部分），韩语为罗马字
+    pub ruby: Option<String>,
+    /// 罗马字转写：中文为拼音，日语为黑本式罗马字，韩语为罗马字
+    pub roman: Option<String>,
+    pub lang: String,
+}
+
+/// (开始字节, 结束字节, 注音, 罗马字)
+type Piece = (usize, usize, Option<String>, Option<String>);
+
+/// 中文按分词结果查多音字词表，逐字输出拼音，便于逐字对齐显示
+fn chinese(text: &str, lang: &str, style: ToneStyle) -> Vec<Piece> {
+    let mut pieces = Vec::new();
+    for token in tokenize::tokenize_text(text, Some(lang)) {
+        let mut offset = token.start;
+        for (c, py) in hanzi::word_pinyin(&token.text, style) {
+            let end = offset + c.len_utf8();
+            pieces.push((offset, end, py.clone(), py));
+            offset = end;
+        }
+    }
+    pieces
+}
+
+fn korean(text: &str) -> Vec<Piece> {
+    tokenize::tokenize_text(text, Some("ko"))
+        .into_iter()
+        .map(|t| {
+            let roman = if t
+                .text
+                .chars()
+                .any(|c| ('\u{AC00}'..='\u{D7A3}').contains(&c))
+            {
+                Some(hangul::romanize(&t.text))
+            } else {
+                None
+            };
+            (t.start, t.end, roman.clone(), roman)
+        })
+        .collect()
+}
+
+/// 日语词典分词时使用词典中的读音标注振假名，助词は、へ读作wa、e
+#[cfg(feature = "lindera")]
+fn japanese(text: &str) -> Result<Vec<Piece>, String> {
+    let list = match tokenize::cut_japanese_with_details(text) {
+        Some(list) => list,
+        None => return japanese_by_kana(text),
+    };
+    let mut pieces = Vec::new();
+    for ((start, end, tag), details) in list {
+        let surface = &text[start..end];
+        // ipadic的第8项为读音（片假名），未登录词为*
+        let reading = details.get(7).filter(|r| *r != "*").cloned();
+        let is_particle = tag.as_deref() == Some("助詞");
+        match reading {
+            Some(_) if is_particle && (surface == "は" || surface == "へ") => {
+                let roman = if surface == "は" { "wa" } else { "e" };
+                pieces.push((start, end, None, Some(roman.to_string())));
+            }
+            Some(reading) => {
+                for (s, e, furigana) in kana::split_okurigana(surface, &reading) {
+                    let roman = match &furigana {
+                        Some(f) => kana::to_romaji(f),
+                        None => kana::to_romaji(&surface[s..e]),
+                    };
+                    pieces.push((start + s, start + e, furigana, Some(roman)));
+                }
+            }
+            None => pieces.push((start, end, None, kana_roman(surface))),
+        }
+    }
+    Ok(pieces)
+}
+
+#[cfg(not(feature = "lindera"))]
+fn japanese(text: &str) -> Result<Vec<Piece>, String> {
+    japanese_by_kana(text)
+}
+
+/// 无词典时只能转写假名，汉字没有读音，含汉字时返回错误
+fn japanese_by_kana(text: &str) -> Result<Vec<Piece>, String> {
+    if text.chars().any(kana::is_kanji) {
+        return Err("没有可用的日语词典，无法标注汉字读音，需启用 lindera 功能编译".to_string());
+    }
+    Ok(tokenize::tokenize_text(text, Some("ja"))
+        .into_iter()
+        .map(|t| (t.start, t.end, None, kana_roman(&t.text)))
+        .collect())
+}
+
+fn kana_roman(text: &str) -> Option<String> {
+    if text.chars().any(kana::is_kana) {
+        Some(kana::to_romaji(text))
+    } else {
+        None
+    }
+}
+
+fn other(text: &str) -> Vec<Piece> {
+    vec![(0, text.len(), None, None)]
+}
+
+/// 不带声调的拼音，直接拼接，用于生成变量名等
+pub fn plain_pinyin(word: &str) -> String {
+    hanzi::word_pinyin(word, ToneStyle::None)
+        .into_iter()
+        .map(|(c, py)| py.unwrap_or_else(|| c.to_string()))
+        .collect()
+}
+
+/// 生成注音，未指定语种时按语种切分片段分别处理；日语汉字没有可用的词典时返回错误
+pub fn romanize_text(
+    text: &str,
+    lang: Option<&str>,
+    style: ToneStyle,
+) -> Result<Vec<Ruby>, String> {
+    let spans: Vec<(usize, usize, &str)> = match lang {
+        Some(l) => vec![(0, text.len(), l)],
+        None => lang_detect::detect_spans(text)
+            .into_iter()
+            .map(|s| (s.start, s.end, s.lang))
+            .collect(),
+    };
+    let mut list = Vec::new();
+    let mut last_byte = 0;
+    let mut last_char = 0;
+    for (span_start, span_end, span_lang) in spans {
+        let span = &text[span_start..span_end];
+        let pieces = match span_lang {
+            "zh_cn" | "zh_tw" => chinese(span, span_lang, style),
+            "ja" => japanese(span)?,
+            "ko" => korean(span),
+            _ => other(span),
+        };
+        for (s, e, ruby, roman) in pieces {
+            let (start, end) = (span_start + s, span_start + e);
+            let char_start = last_char + text[last_byte..start].chars().count();
+            let char_end = char_start + text[start..end].chars().count();
+            last_byte = end;
+            last_char = char_end;
+            list.push(Ruby {
+                text: text[start..end].to_string(),
+                start,
+                end,
+                char_start,
+                char_end,
+                ruby,
+                roman,
+                lang: span_lang.to_string(),
+            });
+        }
+    }
+    Ok(list)
+}
+
+/// tone可选 mark（声调符号）、num（数字声调）、none（不带声调），仅对中文生效
+#[tauri::command(async)]
+pub fn romanize(text: &str, lang: Option<&str>, tone: Option<&str>) -> Result<Vec<Ruby>, String> {
+    romanize_text(text, lang, ToneStyle::parse(tone.unwrap_or("mark")))
+}
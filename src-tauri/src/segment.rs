@@ -0,0 +1,183 @@
+use crate::global::{BASE_PATH, JIEBA};
+use jieba_rs::Jieba;
+use log::{debug, info};
+use serde::Serialize;
+use std::{fs, io::BufReader, path::PathBuf};
+
+pub static DICT_DIR_NAME: &str = "dict";
+pub static USER_DICT_FILE_NAME: &str = "user.dict.txt";
+
+#[derive(Debug, Clone, Serialize)]
+pub struct UserWord {
+    pub word: String,
+    pub freq: Option<usize>,
+    pub tag: Option<String>,
+}
+
+impl UserWord {
+    fn parse(line: &str) -> Option<UserWord> {
+        let mut parts = line.split_whitespace();
+        let word = parts.next()?.to_string();
+        let freq = parts.next().and_then(|f| f.parse::<usize>().ok());
+        let tag = parts.next().map(String::from);
+        Some(UserWord { word, freq, tag })
+    }
+
+    fn to_line(&self) -> String {
+        // jieba词典格式：词语 词频 词性，词频、词性可省略，但有词性时必须有词频
+        match (&self.freq, &self.tag) {
+            (Some(f), Some(t)) => format!("{} {} {}", self.word, f, t),
+            (Some(f), None) => format!("{} {}", self.word, f),
+            (None, _) => self.word.clone(),
+        }
+    }
+}
+
+fn dict_dir() -> PathBuf {
+    BASE_PATH.get().unwrap().join(DICT_DIR_NAME)
+}
+
+fn user_dict_path() -> PathBuf {
+    dict_dir().join(USER_DICT_FILE_NAME)
+}
+
+/// 用户词典目录下的所有txt文件，按文件名排序，保证加载顺序稳定
+fn user_dict_files() -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = match fs::read_dir(dict_dir()) {
+        Ok(dir) => dir
+            .filter_map(|e| e.ok().map(|e| e.path()))
+            .filter(|p| p.is_file() && p.extension().map_or(false, |e| e == "txt"))
+            .collect(),
+        Err(_) => Vec::new(),
+    };
+    files.sort();
+    files
+}
+
+fn build_jieba() -> Jieba {
+    let start = std::time::Instant::now();
+    let mut jieba = Jieba::new();
+    for path in user_dict_files() {
+        let file = match fs::File::open(&path) {
+            Ok(f) => f,
+            Err(e) => {
+                debug!("open user dict {:?} error: {:?}", path, e);
+                continue;
+            }
+        };
+        if let Err(e) = jieba.load_dict(&mut BufReader::new(file)) {
+            debug!("load user dict {:?} error: {:?}", path, e);
+        } else {
+            debug!("load user dict: {:?}", path);
+        }
+    }
+    info!("jieba loaded: {:?}", start.elapsed());
+    jieba
+}
+
+/// 初始化分词器，启动时在后台线程调用，已初始化时直接返回
+pub fn init_jieba() {
+    if JIEBA.read().unwrap().is_some() {
+        return;
+    }
+    let jieba = build_jieba();
+    let mut guard = JIEBA.write().unwrap();
+    if guard.is_none() {
+        *guard = Some(jieba);
+    }
+}
+
+/// 重新加载用户词典，新的分词器构建完成后再替换，不影响正在进行的分词
+pub fn reload_jieba() {
+    let jieba = build_jieba();
+    *JIEBA.write().unwrap() = Some(jieba);
+}
+
+/// 使用分词器，若后台初始化尚未完成则在当前线程完成初始化
+pub fn with_jieba<R>(f: impl FnOnce(&Jieba) -> R) -> R {
+    {
+        let guard = JIEBA.read().unwrap();
+        if let Some(jieba) = guard.as_ref() {
+            return f(jieba);
+        }
+    }
+    init_jieba();
+    let guard = JIEBA.read().unwrap();
+    f(guard.as_ref().unwrap())
+}
+
+fn read_user_words() -> Vec<UserWord> {
+    match fs::read_to_string(user_dict_path()) {
+        Ok(content) => content.lines().filter_map(UserWord::parse).collect(),
+        Err(_) => Vec::new(),
+    }
+}
+
+fn write_user_words(words: &[UserWord]) -> Result<(), String> {
+    fs::create_dir_all(dict_dir()).map_err(|e| e.to_string())?;
+    let content = words
+        .iter()
+        .map(|w| w.to_line())
+        .collect::<Vec<String>>()
+        .join("\n");
+    fs::write(user_dict_path(), content).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn jieba_user_words() -> Result<Vec<UserWord>, ()> {
+    Ok(read_user_words())
+}
+
+#[tauri::command]
+pub fn jieba_add_word(
+    word: String,
+    freq: Option<usize>,
+    tag: Option<String>,
+) -> Result<usize, String> {
+    let word = word.trim().to_string();
+    if word.is_empty() || word.contains(char::is_whitespace) {
+        return Err("word is empty or contains whitespace".to_string());
+    }
+    // 未指定词频时按jieba的建议词频，保证该词能被切分出来
+    let freq = match freq {
+        Some(f) => f,
+        None => with_jieba(|jieba| jieba.suggest_freq(&word)),
+    };
+    let mut words = read_user_words();
+    words.retain(|w| w.word != word);
+    words.push(UserWord {
+        word: word.clone(),
+        freq: Some(freq),
+        tag: tag.clone(),
+    });
+    write_user_words(&words)?;
+    // jieba支持直接添加词语，不需要重建；后台初始化尚未完成时先在当前线程完成
+    init_jieba();
+    let mut guard = JIEBA.write().unwrap();
+    let freq = guard
+        .as_mut()
+        .unwrap()
+        .add_word(&word, Some(freq), tag.as_deref());
+    debug!("jieba add word: {} {}", word, freq);
+    Ok(freq)
+}
+
+#[tauri::command(async)]
+pub fn jieba_remove_word(word: String) -> Result<bool, String> {
+    let mut words = read_user_words();
+    let len = words.len();
+    words.retain(|w| w.word != word);
+    if words.len() == len {
+        return Ok(false);
+    }
+    write_user_words(&words)?;
+    // jieba不支持删除词语，只能重建
+    reload_jieba();
+    Ok(true)
+}
+
+#[tauri::command(async)]
+pub fn jieba_reload() -> Result<(), ()> {
+    reload_jieba();
+    Ok(())
+}
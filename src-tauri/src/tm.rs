@@ -0,0 +1,593 @@
+use crate::{
+    config::{get_config_with_default, get_or_bool},
+    global::BASE_PATH,
+    glossary::{escape_xml, xml_lang},
+    lang_detect,
+};
+use log::{debug, info, warn};
+use once_cell::sync::Lazy;
+use rusqlite::{params, params_from_iter, Connection, OptionalExtension, Row};
+use serde::Serialize;
+use std::{
+    collections::HashSet,
+    fs,
+    path::{Path, PathBuf},
+    sync::Mutex,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+const TM_FILE_NAME: &str = "tm.db";
+/// 模糊匹配时最多比较的候选句段数
+const MAX_CANDIDATES: usize = 50;
+/// 超过该长度（字符数）的文本只进行精确匹配
+const MAX_FUZZY_LENGTH: usize = 1000;
+/// 查询时使用的 n-gram 数量上限，避免超出 SQLite 的参数数量限制
+const MAX_QUERY_GRAMS: usize = 500;
+const DEFAULT_THRESHOLD: u64 = 75;
+const DEFAULT_LIMIT: usize = 5;
+
+static CONNECTION: Lazy<Mutex<Option<Connection>>> = Lazy::new(|| Mutex::new(None));
+
+/// 翻译记忆中的句段，语种为Tosa的语种代码
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Segment {
+    pub id: i64,
+    pub source: String,
+    pub target: String,
+    pub from: String,
+    pub to: String,
+    /// 来源，如翻译服务名称或导入的文件名
+    pub origin: String,
+    pub created: i64,
+    pub updated: i64,
+}
+
+/// 匹配结果，score 为匹配度（0 - 100），100 为精确匹配
+#[derive(Debug, Clone, Serialize)]
+pub struct Match {
+    #[serde(flatten)]
+    pub segment: Segment,
+    pub score: u8,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Page {
+    pub total: i64,
+    pub items: Vec<Segment>,
+}
+
+fn db_path() -> PathBuf {
+    BASE_PATH.get().unwrap().join(TM_FILE_NAME)
+}
+
+fn now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or_default()
+}
+
+fn open() -> rusqlite::Result<Connection> {
+    let conn = Connection::open(db_path())?;
+    conn.execute_batch(
+        "PRAGMA journal_mode = WAL;
+         PRAGMA synchronous = NORMAL;
+         PRAGMA foreign_keys = ON;
+         CREATE TABLE IF NOT EXISTS segments (
+             id INTEGER PRIMARY KEY AUTOINCREMENT,
+             from_lang TEXT NOT NULL,
+             to_lang TEXT NOT NULL,
+             source TEXT NOT NULL,
+             target TEXT NOT NULL,
+             normalized TEXT NOT NULL,
+             length INTEGER NOT NULL,
+             origin TEXT NOT NULL DEFAULT '',
+             created INTEGER NOT NULL,
+             updated INTEGER NOT NULL,
+             UNIQUE (from_lang, to_lang, normalized)
+         );
+         CREATE TABLE IF NOT EXISTS grams (
+             gram TEXT NOT NULL,
+             segment INTEGER NOT NULL REFERENCES segments(id) ON DELETE CASCADE,
+             PRIMARY KEY (gram, segment)
+         ) WITHOUT ROWID;
+         CREATE INDEX IF NOT EXISTS grams_segment ON grams(segment);
+         -- 早期版本以秒记录时间，统一为毫秒
+         UPDATE segments SET created = created * 1000, updated = updated * 1000
+         WHERE updated < 100000000000;",
+    )?;
+    info!("translation memory opened: {:?}", db_path());
+    Ok(conn)
+}
+
+/// 使用数据库连接执行操作，首次使用时打开数据库
+fn with_conn<T>(f: impl FnOnce(&mut Connection) -> rusqlite::Result<T>) -> Result<T, String> {
+    let mut guard = CONNECTION.lock().unwrap();
+    if guard.is_none() {
+        *guard = Some(open().map_err(|e| format!("打开翻译记忆库失败：{}", e))?);
+    }
+    f(guard.as_mut().unwrap()).map_err(|e| format!("翻译记忆库操作失败：{}", e))
+}
+
+/// 匹配时忽略大小写及多余的空白
+fn normalize(text: &str) -> String {
+    text.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+/// 字符二元组，同时适用于以空格分词的语言和中日韩文本
+fn grams(normalized: &str) -> Vec<String> {
+    let chars: Vec<char> = normalized.chars().collect();
+    if chars.len() < 2 {
+        return vec![normalized.to_string()];
+    }
+    let mut seen = HashSet::new();
+    chars
+        .windows(2)
+        .map(|w| w.iter().collect::<String>())
+        .filter(|g| seen.insert(g.clone()))
+        .collect()
+}
+
+fn levenshtein(a: &[char], b: &[char]) -> usize {
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut cur = vec![0; b.len() + 1];
+    for (i, ca) in a.iter().enumerate() {
+        cur[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let cost = usize::from(ca != cb);
+            cur[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(cur[j] + 1);
+        }
+        std::mem::swap(&mut prev, &mut cur);
+    }
+    prev[b.len()]
+}
+
+/// 基于编辑距离的匹配度，按较长文本的长度归一化
+fn similarity(a: &str, b: &str) -> u8 {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let max = a.len().max(b.len());
+    if max == 0 {
+        return 100;
+    }
+    let score = (1.0 - levenshtein(&a, &b) as f64 / max as f64) * 100.0;
+    // 非精确匹配最高为99
+    (score.floor() as u8).min(99)
+}
+
+const SEGMENT_COLUMNS: &str = "id, source, target, from_lang, to_lang, origin, created, updated";
+
+fn segment(row: &Row) -> rusqlite::Result<Segment> {
+    Ok(Segment {
+        id: row.get(0)?,
+        source: row.get(1)?,
+        target: row.get(2)?,
+        from: row.get(3)?,
+        to: row.get(4)?,
+        origin: row.get(5)?,
+        created: row.get(6)?,
+        updated: row.get(7)?,
+    })
+}
+
+/// 写入句段，原文归一化后相同的句段会被覆盖
+fn upsert(
+    conn: &Connection,
+    source: &str,
+    target: &str,
+    from: &str,
+    to: &str,
+    origin: &str,
+) -> rusqlite::Result<Option<i64>> {
+    let (source, target) = (source.trim(), target.trim());
+    if source.is_empty() || target.is_empty() || from.is_empty() || to.is_empty() {
+        return Ok(None);
+    }
+    let normalized = normalize(source);
+    let time = now();
+    let id: i64 = conn.query_row(
+        "INSERT INTO segments (from_lang, to_lang, source, target, normalized, length, origin, created, updated)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?8)
+         ON CONFLICT (from_lang, to_lang, normalized)
+         DO UPDATE SET source = excluded.source, target = excluded.target,
+                       origin = excluded.origin, updated = excluded.updated
+         RETURNING id",
+        params![
+            from,
+            to,
+            source,
+            target,
+            normalized,
+            normalized.chars().count() as i64,
+            origin,
+            time
+        ],
+        |r| r.get(0),
+    )?;
+    let mut stmt =
+        conn.prepare_cached("INSERT OR IGNORE INTO grams (gram, segment) VALUES (?1, ?2)")?;
+    for gram in grams(&normalized) {
+        stmt.execute(params![gram, id])?;
+    }
+    Ok(Some(id))
+}
+
+pub fn add(
+    source: &str,
+    target: &str,
+    from: &str,
+    to: &str,
+    origin: &str,
+) -> Result<Option<i64>, String> {
+    with_conn(|conn| upsert(conn, source, target, from, to, origin))
+}
+
+fn exact(
+    conn: &Connection,
+    normalized: &str,
+    from: &str,
+    to: &str,
+) -> rusqlite::Result<Option<Segment>> {
+    conn.query_row(
+        &format!(
+            "SELECT {} FROM segments WHERE to_lang = ?1 AND (?2 = '' OR from_lang = ?2) AND normalized = ?3 \
+             ORDER BY updated DESC LIMIT 1",
+            SEGMENT_COLUMNS
+        ),
+        params![to, from, normalized],
+        segment,
+    )
+    .optional()
+}
+
+/// 按共有的 n-gram 数量筛选候选句段，长度差异过大的句段不可能达到最低匹配度
+fn fuzzy(
+    conn: &Connection,
+    normalized: &str,
+    from: &str,
+    to: &str,
+    threshold: u8,
+) -> rusqlite::Result<Vec<Match>> {
+    let length = normalized.chars().count();
+    if length == 0 || length > MAX_FUZZY_LENGTH {
+        return Ok(Vec::new());
+    }
+    let ratio = threshold.max(1) as f64 / 100.0;
+    let min = (length as f64 * ratio).floor() as i64;
+    let max = (length as f64 / ratio).ceil() as i64;
+    let query: Vec<String> = grams(normalized)
+        .into_iter()
+        .take(MAX_QUERY_GRAMS)
+        .collect();
+    let sql = format!(
+        "SELECT s.id, s.source, s.target, s.from_lang, s.to_lang, s.origin, s.created, s.updated, s.normalized
+         FROM grams g JOIN segments s ON s.id = g.segment
+         WHERE g.gram IN ({}) AND s.to_lang = ? AND (? = '' OR s.from_lang = ?)
+               AND s.length BETWEEN ? AND ? AND s.normalized <> ?
+         GROUP BY s.id ORDER BY COUNT(*) DESC, s.updated DESC LIMIT {}",
+        vec!["?"; query.len()].join(","),
+        MAX_CANDIDATES
+    );
+    let mut values: Vec<rusqlite::types::Value> = query.into_iter().map(Into::into).collect();
+    values.push(to.to_string().into());
+    values.push(from.to_string().into());
+    values.push(from.to_string().into());
+    values.push(min.into());
+    values.push(max.into());
+    values.push(normalized.to_string().into());
+    let mut stmt = conn.prepare(&sql)?;
+    let rows = stmt.query_map(params_from_iter(values), |r| {
+        Ok((segment(r)?, r.get::<_, String>(8)?))
+    })?;
+    let mut list = Vec::new();
+    for row in rows {
+        let (segment, candidate) = row?;
+        let score = similarity(normalized, &candidate);
+        if score >= threshold {
+            list.push(Match { segment, score });
+        }
+    }
+    Ok(list)
+}
+
+/// 查找精确及模糊匹配的句段，按匹配度从高到低排序，from 为空时匹配所有源语种
+pub fn lookup(
+    text: &str,
+    from: &str,
+    to: &str,
+    threshold: u8,
+    limit: usize,
+) -> Result<Vec<Match>, String> {
+    let normalized = normalize(text);
+    if normalized.is_empty() || to.is_empty() {
+        return Ok(Vec::new());
+    }
+    let from = if from == "auto" { "" } else { from };
+    with_conn(|conn| {
+        let mut list = Vec::new();
+        if let Some(segment) = exact(conn, &normalized, from, to)? {
+            list.push(Match {
+                segment,
+                score: 100,
+            });
+        }
+        if threshold < 100 {
+            list.extend(fuzzy(conn, &normalized, from, to, threshold)?);
+        }
+        list.sort_by(|a, b| {
+            b.score
+                .cmp(&a.score)
+                .then(b.segment.updated.cmp(&a.segment.updated))
+        });
+        list.truncate(limit);
+        Ok(list)
+    })
+}
+
+/// 使用配置项 tm_threshold 作为最低匹配度查找，未启用翻译记忆时返回空列表
+pub fn lookup_with_config(text: &str, from: &str, to: &str) -> Vec<Match> {
+    if !get_or_bool("tm_enable", true) {
+        return Vec::new();
+    }
+    let threshold =
+        get_config_with_default("tm_threshold", DEFAULT_THRESHOLD, |v| v.as_u64()).min(100) as u8;
+    lookup(text, from, to, threshold, DEFAULT_LIMIT).unwrap_or_else(|e| {
+        warn!("translation memory lookup error: {}", e);
+        Vec::new()
+    })
+}
+
+pub fn list(keyword: &str, from: &str, to: &str, offset: i64, limit: i64) -> Result<Page, String> {
+    let like = format!(
+        "%{}%",
+        keyword.trim().replace('%', "\\%").replace('_', "\\_")
+    );
+    let filter = "(?1 = '' OR from_lang = ?1) AND (?2 = '' OR to_lang = ?2) \
+                  AND (source LIKE ?3 ESCAPE '\\' OR target LIKE ?3 ESCAPE '\\')";
+    with_conn(|conn| {
+        let total = conn.query_row(
+            &format!("SELECT COUNT(*) FROM segments WHERE {}", filter),
+            params![from, to, like],
+            |r| r.get(0),
+        )?;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM segments WHERE {} ORDER BY updated DESC, id DESC LIMIT ?4 OFFSET ?5",
+            SEGMENT_COLUMNS, filter
+        ))?;
+        let items = stmt
+            .query_map(params![from, to, like, limit, offset], segment)?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(Page { total, items })
+    })
+}
+
+pub fn remove(ids: &[i64]) -> Result<usize, String> {
+    with_conn(|conn| {
+        let tx = conn.transaction()?;
+        let mut count = 0;
+        for id in ids {
+            count += tx.execute("DELETE FROM segments WHERE id = ?1", [id])?;
+        }
+        tx.commit()?;
+        Ok(count)
+    })
+}
+
+/// 句段文本，忽略 bpt、ept、ph 等内联标记中的原始格式代码
+fn seg_text(seg: roxmltree::Node) -> String {
+    seg.descendants()
+        .filter(|n| n.is_text())
+        .filter(|n| {
+            !n.ancestors()
+                .take_while(|a| *a != seg)
+                .any(|a| matches!(a.tag_name().name(), "bpt" | "ept" | "ph" | "it" | "ut"))
+        })
+        .filter_map(|n| n.text())
+        .collect::<String>()
+        .trim()
+        .to_string()
+}
+
+/// 解析TMX，返回 (源语种, 目标语种, 原文, 译文)。
+/// 未指定语种对时以 srclang 为源语种，每个翻译单元中的其他语种均作为目标语种
+fn import_tmx(
+    content: &str,
+    from: &str,
+    to: &str,
+) -> Result<Vec<(String, String, String, String)>, String> {
+    let doc = roxmltree::Document::parse(content).map_err(|e| format!("解析TMX失败：{}", e))?;
+    let header_src = doc
+        .descendants()
+        .find(|n| n.has_tag_name("header"))
+        .and_then(|n| n.attribute("srclang"))
+        .filter(|s| *s != "*all*")
+        .map(lang_detect::from_bcp47);
+    let mut list = Vec::new();
+    for tu in doc.descendants().filter(|n| n.has_tag_name("tu")) {
+        let tuvs: Vec<(String, String)> = tu
+            .children()
+            .filter(|n| n.has_tag_name("tuv"))
+            .filter_map(|n| {
+                let seg = n.children().find(|c| c.has_tag_name("seg"))?;
+                Some((xml_lang(n)?, seg_text(seg)))
+            })
+            .filter(|(_, text)| !text.is_empty())
+            .collect();
+        let find = |lang: &str| tuvs.iter().find(|(l, _)| l == lang);
+        if !from.is_empty() && !to.is_empty() {
+            if let (Some((_, source)), Some((_, target))) = (find(from), find(to)) {
+                list.push((
+                    from.to_string(),
+                    to.to_string(),
+                    source.clone(),
+                    target.clone(),
+                ));
+            }
+            continue;
+        }
+        let src = tu
+            .attribute("srclang")
+            .filter(|s| *s != "*all*")
+            .map(lang_detect::from_bcp47)
+            .or_else(|| header_src.clone());
+        let source = match src.as_deref().and_then(find).or_else(|| tuvs.first()) {
+            Some(source) => source.clone(),
+            None => continue,
+        };
+        for (lang, target) in tuvs.iter().filter(|(l, _)| *l != source.0) {
+            list.push((
+                source.0.clone(),
+                lang.clone(),
+                source.1.clone(),
+                target.clone(),
+            ));
+        }
+    }
+    Ok(list)
+}
+
+fn export_tmx(list: &[Segment]) -> String {
+    let srclang = match list.first() {
+        Some(first) if list.iter().all(|s| s.from == first.from) => lang_detect::bcp47(&first.from),
+        _ => "*all*".to_string(),
+    };
+    let mut xml =
+        String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<tmx version=\"1.4\">\n");
+    xml.push_str(&format!(
+        "  <header creationtool=\"Tosa\" creationtoolversion=\"{}\" datatype=\"plaintext\" segtype=\"sentence\" \
+         adminlang=\"en\" srclang=\"{}\" o-tmf=\"Tosa\"/>\n  <body>\n",
+        env!("CARGO_PKG_VERSION"),
+        srclang
+    ));
+    for segment in list {
+        xml.push_str(&format!(
+            "    <tu srclang=\"{}\">\n",
+            lang_detect::bcp47(&segment.from)
+        ));
+        if !segment.origin.is_empty() {
+            xml.push_str(&format!(
+                "      <prop type=\"x-origin\">{}</prop>\n",
+                escape_xml(&segment.origin)
+            ));
+        }
+        for (lang, text) in [
+            (&segment.from, &segment.source),
+            (&segment.to, &segment.target),
+        ] {
+            xml.push_str(&format!(
+                "      <tuv xml:lang=\"{}\"><seg>{}</seg></tuv>\n",
+                lang_detect::bcp47(lang),
+                escape_xml(text)
+            ));
+        }
+        xml.push_str("    </tu>\n");
+    }
+    xml.push_str("  </body>\n</tmx>\n");
+    xml
+}
+
+/// 导入TMX文件，返回导入的句段数
+pub fn import_file(path: &Path, from: &str, to: &str) -> Result<usize, String> {
+    let content = fs::read_to_string(path).map_err(|e| format!("读取文件失败：{}", e))?;
+    let list = import_tmx(content.trim_start_matches('\u{feff}'), from, to)?;
+    if list.is_empty() {
+        return Err("文件中没有可导入的句段，请检查语种对是否正确".to_string());
+    }
+    let origin = path
+        .file_name()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let count = with_conn(|conn| {
+        let tx = conn.transaction()?;
+        let mut count = 0;
+        for (from, to, source, target) in &list {
+            if upsert(&tx, source, target, from, to, &origin)?.is_some() {
+                count += 1;
+            }
+        }
+        tx.commit()?;
+        Ok(count)
+    })?;
+    debug!("imported {} segments from {:?}", count, path);
+    Ok(count)
+}
+
+/// 导出为TMX文件，语种为空时导出所有语种
+pub fn export_file(path: &Path, from: &str, to: &str) -> Result<usize, String> {
+    let list = with_conn(|conn| {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM segments WHERE (?1 = '' OR from_lang = ?1) AND (?2 = '' OR to_lang = ?2) ORDER BY id",
+            SEGMENT_COLUMNS
+        ))?;
+        let rows = stmt.query_map(params![from, to], segment)?;
+        rows.collect::<rusqlite::Result<Vec<_>>>()
+    })?;
+    fs::write(path, export_tmx(&list)).map_err(|e| format!("写入文件失败：{}", e))?;
+    Ok(list.len())
+}
+
+/// 查找翻译记忆，from to 为Tosa的语种代码，from 为 auto 或空时匹配所有源语种
+#[tauri::command]
+pub fn tm_lookup(text: String, from: String, to: String) -> Result<Vec<Match>, ()> {
+    Ok(lookup_with_config(&text, &from, &to))
+}
+
+#[tauri::command]
+pub fn tm_add(
+    source: String,
+    target: String,
+    from: String,
+    to: String,
+    origin: String,
+) -> Result<Option<i64>, String> {
+    add(&source, &target, &from, &to, &origin)
+}
+
+#[tauri::command]
+pub fn tm_list(
+    keyword: String,
+    from: String,
+    to: String,
+    offset: i64,
+    limit: i64,
+) -> Result<Page, String> {
+    list(&keyword, &from, &to, offset, limit)
+}
+
+#[tauri::command]
+pub fn tm_remove(ids: Vec<i64>) -> Result<usize, String> {
+    remove(&ids)
+}
+
+#[tauri::command]
+pub fn tm_import(path: String, from: String, to: String) -> Result<usize, String> {
+    import_file(Path::new(&path), &from, &to)
+}
+
+#[tauri::command]
+pub fn tm_export(path: String, from: String, to: String) -> Result<usize, String> {
+    export_file(Path::new(&path), &from, &to)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fuzzy_score() {
+        assert_eq!(normalize("  Hello\n  World "), "hello world");
+        assert_eq!(similarity("hello world", "hello world"), 99);
+        assert_eq!(similarity("hello world", "hello word"), 90);
+        assert_eq!(similarity("今天天气很好", "今天天气不好"), 83);
+        assert_eq!(grams("abab"), ["ab", "ba"]);
+    }
+
+    #[test]
+    fn millisecond_time() {
+        assert!(now() > 1_000_000_000_000);
+    }
+}
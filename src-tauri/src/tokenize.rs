@@ -0,0 +1,278 @@
+use crate::{lang_detect, segment::with_jieba};
+use serde::Serialize;
+use unicode_segmentation::UnicodeSegmentation;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TokenKind {
+    Word,
+    Number,
+    Punctuation,
+    Whitespace,
+}
+
+impl TokenKind {
+    pub fn of(text: &str) -> TokenKind {
+        if text.chars().all(char::is_whitespace) {
+            TokenKind::Whitespace
+        } else if text.chars().any(char::is_numeric)
+            && text
+                .chars()
+                .all(|c| c.is_numeric() || matches!(c, '.' | ',' | '%' | '-' | '+'))
+        {
+            TokenKind::Number
+        } else if text.chars().all(|c| !c.is_alphanumeric()) {
+            TokenKind::Punctuation
+        } else {
+            TokenKind::Word
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Token {
+    pub text: String,
+    /// 开始字节位置
+    pub start: usize,
+    /// 结束字节位置（不包含）
+    pub end: usize,
+    /// 开始字符位置
+    pub char_start: usize,
+    /// 结束字符位置（不包含）
+    pub char_end: usize,
+    /// 词性，中文为jieba词性，日韩为词典词性
+    pub tag: Option<String>,
+    pub kind: TokenKind,
+    /// 语种代码，指定语种时为参数中的语种
+    pub lang: String,
+}
+
+/// 分词结果片段：(相对开始字节, 相对结束字节, 词性)
+type Piece = (usize, usize, Option<String>);
+
+fn cut_chinese(text: &str) -> Vec<Piece> {
+    with_jieba(|jieba| {
+        let mut pieces = Vec::new();
+        let mut offset = 0;
+        for tag in jieba.tag(text, true) {
+            let end = offset + tag.word.len();
+            pieces.push((offset, end, Some(tag.tag.to_string())));
+            offset = end;
+        }
+        pieces
+    })
+}
+
+/// 按Unicode断词规则（UAX #29）切分，适用于以空格分词的语言
+fn cut_word_bounds(text: &str) -> Vec<Piece> {
+    text.split_word_bound_indices()
+        .map(|(i, w)| (i, i + w.len(), None))
+        .collect()
+}
+
+#[cfg(feature = "lindera")]
+mod dict {
+    use super::Piece;
+    use lindera_core::mode::Mode;
+    use lindera_dictionary::{DictionaryConfig, DictionaryKind};
+    use lindera_tokenizer::tokenizer::{Tokenizer, TokenizerConfig};
+    use log::debug;
+    use once_cell::sync::Lazy;
+    use std::sync::Mutex;
+
+    fn build(kind: DictionaryKind) -> Option<Tokenizer> {
+        let config = TokenizerConfig {
+            dictionary: DictionaryConfig {
+                kind: Some(kind),
+                path: None,
+            },
+            user_dictionary: None,
+            mode: Mode::Normal,
+        };
+        match Tokenizer::from_config(config) {
+            Ok(t) => Some(t),
+            Err(e) => {
+                debug!("load lindera dictionary error: {:?}", e);
+                None
+            }
+        }
+    }
+
+    static IPADIC: Lazy<Mutex<Option<Tokenizer>>> =
+        Lazy::new(|| Mutex::new(build(DictionaryKind::IPADIC)));
+    static KO_DIC: Lazy<Mutex<Option<Tokenizer>>> =
+        Lazy::new(|| Mutex::new(build(DictionaryKind::KoDic)));
+
+    /// 返回分词结果，词典中的第一项为词性，ipadic的第八项为读音（片假名）
+    fn cut(tokenizer: &Mutex<Option<Tokenizer>>, text: &str) -> Option<Vec<(Piece, Vec<String>)>> {
+        let guard = tokenizer.lock().unwrap();
+        let mut tokens = guard.as_ref()?.tokenize(text).ok()?;
+        Some(
+            tokens
+                .iter_mut()
+                .map(|t| {
+                    let details: Vec<String> = t
+                        .get_details()
+                        .map(|d| d.iter().map(|s| s.to_string()).collect())
+                        .unwrap_or_default();
+                    let tag = details.first().filter(|s| *s != "*").cloned();
+                    ((t.byte_start, t.byte_end, tag), details)
+                })
+                .collect(),
+        )
+    }
+
+    pub fn cut_japanese(text: &str) -> Option<Vec<(Piece, Vec<String>)>> {
+        cut(&IPADIC, text)
+    }
+
+    pub fn cut_korean(text: &str) -> Option<Vec<Piece>> {
+        cut(&KO_DIC, text).map(|list| list.into_iter().map(|(p, _)| p).collect())
+    }
+
+    pub fn loaded(lang: &str) -> bool {
+        let tokenizer = if lang == "ja" { &IPADIC } else { &KO_DIC };
+        tokenizer.lock().unwrap().is_some()
+    }
+}
+
+#[cfg(feature = "lindera")]
+pub use dict::cut_japanese as cut_japanese_with_details;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum JaClass {
+    Kanji,
+    Hiragana,
+    Katakana,
+    Other,
+}
+
+fn ja_class(c: char) -> JaClass {
+    match c as u32 {
+        0x3005 | 0x3007 | 0x3400..=0x4DBF | 0x4E00..=0x9FFF | 0xF900..=0xFAFF => JaClass::Kanji,
+        0x3040..=0x309F => JaClass::Hiragana,
+        // 长音符号“ー”归入片假名
+        0x30A0..=0x30FF | 0x31F0..=0x31FF | 0xFF66..=0xFF9F => JaClass::Katakana,
+        _ => JaClass::Other,
+    }
+}
+
+/// 未启用词典时的日语分词：按汉字、平假名、片假名切分连续片段，其余按断词规则处理
+fn cut_japanese_by_script(text: &str) -> Vec<Piece> {
+    let mut pieces = Vec::new();
+    let mut start = 0;
+    let mut current: Option<JaClass> = None;
+    let mut flush = |pieces: &mut Vec<Piece>, class: Option<JaClass>, start: usize, end: usize| {
+        if start >= end {
+            return;
+        }
+        if class == Some(JaClass::Other) {
+            for (s, e, t) in cut_word_bounds(&text[start..end]) {
+                pieces.push((start + s, start + e, t));
+            }
+        } else {
+            pieces.push((start, end, None));
+        }
+    };
+    for (i, c) in text.char_indices() {
+        let class = ja_class(c);
+        if current != Some(class) {
+            flush(&mut pieces, current, start, i);
+            start = i;
+            current = Some(class);
+        }
+    }
+    flush(&mut pieces, current, start, text.len());
+    pieces
+}
+
+fn cut_japanese(text: &str) -> Vec<Piece> {
+    #[cfg(feature = "lindera")]
+    if let Some(list) = dict::cut_japanese(text) {
+        return list.into_iter().map(|(p, _)| p).collect();
+    }
+    cut_japanese_by_script(text)
+}
+
+fn cut_korean(text: &str) -> Vec<Piece> {
+    #[cfg(feature = "lindera")]
+    if let Some(list) = dict::cut_korean(text) {
+        return list;
+    }
+    // 韩语以空格分隔语节，未启用词典时按断词规则切分
+    cut_word_bounds(text)
+}
+
+/// 日语、韩语是否可以使用词典分词，需编译时启用 lindera 功能，其余语种不需要词典
+pub fn has_dictionary(lang: &str) -> bool {
+    match lang {
+        #[cfg(feature = "lindera")]
+        "ja" | "ko" => dict::loaded(lang),
+        #[cfg(not(feature = "lindera"))]
+        "ja" | "ko" => false,
+        _ => true,
+    }
+}
+
+/// 只有中日韩需要专门的分词方式，其余语种按断词规则切分
+fn cut_by_lang(text: &str, lang: &str) -> Vec<Piece> {
+    match lang {
+        "zh_cn" | "zh_tw" => cut_chinese(text),
+        "ja" => cut_japanese(text),
+        "ko" => cut_korean(text),
+        _ => cut_word_bounds(text),
+    }
+}
+
+/// 分词，未指定语种时先按语种切分片段，每个片段使用对应语种的分词方式
+pub fn tokenize_text(text: &str, lang: Option<&str>) -> Vec<Token> {
+    let spans: Vec<(usize, usize, &str)> = match lang {
+        Some(l) => vec![(0, text.len(), l)],
+        None => lang_detect::detect_spans(text)
+            .into_iter()
+            .map(|s| (s.start, s.end, s.lang))
+            .collect(),
+    };
+    let mut tokens = Vec::new();
+    // 增量计算字符位置，避免每个词都从头计数
+    let mut last_byte = 0;
+    let mut last_char = 0;
+    for (span_start, span_end, span_lang) in spans {
+        for (s, e, tag) in cut_by_lang(&text[span_start..span_end], span_lang) {
+            let (start, end) = (span_start + s, span_start + e);
+            let word = &text[start..end];
+            let char_start = last_char + text[last_byte..start].chars().count();
+            let char_end = char_start + word.chars().count();
+            last_byte = end;
+            last_char = char_end;
+            tokens.push(Token {
+                text: word.to_string(),
+                start,
+                end,
+                char_start,
+                char_end,
+                tag,
+                kind: TokenKind::of(word),
+                lang: span_lang.to_string(),
+            });
+        }
+    }
+    tokens
+}
+
+/// 日语、韩语没有可用的词典时返回错误，不返回按文字类型粗略切分的结果
+#[tauri::command]
+pub fn tokenize(text: &str, lang: Option<&str>) -> Result<Vec<Token>, String> {
+    let tokens = tokenize_text(text, lang);
+    if let Some(token) = tokens.iter().find(|t| !has_dictionary(&t.lang)) {
+        return Err(format!(
+            "没有可用的{}词典，需启用 lindera 功能编译",
+            if token.lang == "ja" {
+                "日语"
+            } else {
+                "韩语"
+            }
+        ));
+    }
+    Ok(tokens)
+}
@@ -0,0 +1,341 @@
+use crate::{
+    config::{get_config_with_default, get_or_bool},
+    global::BASE_PATH,
+    glossary::Prepared,
+};
+use log::{debug, info, warn};
+use once_cell::sync::Lazy;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::Serialize;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::{
+    collections::HashSet,
+    path::PathBuf,
+    sync::Mutex,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+const CACHE_FILE_NAME: &str = "trans_cache.db";
+/// 默认缓存7天
+const DEFAULT_TTL_HOURS: i64 = 24 * 7;
+/// 默认最多占用 32MB
+const DEFAULT_SIZE_MB: i64 = 32;
+
+static CONNECTION: Lazy<Mutex<Option<Connection>>> = Lazy::new(|| Mutex::new(None));
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Stats {
+    pub count: i64,
+    /// 占用的字节数
+    pub size: i64,
+}
+
+fn db_path() -> PathBuf {
+    BASE_PATH.get().unwrap().join(CACHE_FILE_NAME)
+}
+
+fn now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or_default()
+}
+
+fn open() -> rusqlite::Result<Connection> {
+    let conn = Connection::open(db_path())?;
+    conn.execute_batch(
+        "PRAGMA journal_mode = WAL;
+         PRAGMA synchronous = NORMAL;
+         CREATE TABLE IF NOT EXISTS entries (
+             key TEXT PRIMARY KEY,
+             service TEXT NOT NULL,
+             fingerprint TEXT NOT NULL,
+             value TEXT NOT NULL,
+             size INTEGER NOT NULL,
+             created INTEGER NOT NULL,
+             accessed INTEGER NOT NULL
+         );
+         CREATE INDEX IF NOT EXISTS entries_accessed ON entries(accessed);
+         CREATE INDEX IF NOT EXISTS entries_created ON entries(created);
+         CREATE INDEX IF NOT EXISTS entries_service ON entries(service, fingerprint);",
+    )?;
+    info!("translate cache opened: {:?}", db_path());
+    Ok(conn)
+}
+
+/// 使用数据库连接执行操作，首次使用时打开数据库
+fn with_conn<T>(f: impl FnOnce(&mut Connection) -> rusqlite::Result<T>) -> Result<T, String> {
+    let mut guard = CONNECTION.lock().unwrap();
+    if guard.is_none() {
+        *guard = Some(open().map_err(|e| format!("打开翻译缓存失败：{}", e))?);
+    }
+    f(guard.as_mut().unwrap()).map_err(|e| format!("翻译缓存操作失败：{}", e))
+}
+
+fn ttl() -> i64 {
+    get_config_with_default("trans_cache_ttl", DEFAULT_TTL_HOURS, |v| v.as_i64()) * 60 * 60 * 1000
+}
+
+fn max_size() -> i64 {
+    get_config_with_default("trans_cache_size", DEFAULT_SIZE_MB, |v| v.as_i64()) * 1024 * 1024
+}
+
+/// 依次写入每个字段的长度及内容，避免字段拼接后产生歧义
+fn digest(fields: &[&str]) -> String {
+    let mut hasher = Sha256::new();
+    for field in fields {
+        hasher.update((field.len() as u64).to_le_bytes());
+        hasher.update(field.as_bytes());
+    }
+    format!("{:x}", hasher.finalize())
+}
+
+/// 按键排序后序列化，保证相同的参数得到相同的指纹
+fn canonical(value: &Value) -> String {
+    match value {
+        Value::Object(map) => {
+            let mut keys: Vec<&String> = map.keys().collect();
+            keys.sort();
+            let items: Vec<String> = keys
+                .into_iter()
+                .map(|k| format!("{}:{}", Value::from(k.as_str()), canonical(&map[k])))
+                .collect();
+            format!("{{{}}}", items.join(","))
+        }
+        Value::Array(list) => format!(
+            "[{}]",
+            list.iter().map(canonical).collect::<Vec<_>>().join(",")
+        ),
+        _ => value.to_string(),
+    }
+}
+
+/// 服务配置的指纹，服务类型或参数（如模型、提示词）变化时随之变化
+pub fn fingerprint(name: &str, params: &Value) -> String {
+    digest(&[name, &canonical(params)])
+}
+
+/// 统一换行符并去掉首尾及行尾的空白
+fn normalize(text: &str) -> String {
+    text.trim()
+        .lines()
+        .map(|l| l.trim_end())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// glossary 为匹配到的术语，见 Prepared::terms_key
+fn key(
+    service: &str,
+    fingerprint: &str,
+    glossary: &str,
+    text: &str,
+    from: &str,
+    to: &str,
+) -> String {
+    digest(&[service, fingerprint, glossary, &normalize(text), from, to])
+}
+
+/// 配置中所有服务的 id 及指纹，id 未设置时为服务名称，与前端 config.id || config.name 一致
+fn service_fingerprints() -> Vec<(String, String)> {
+    let list = get_config_with_default("trans_services", Vec::new(), |v| v.as_array().cloned());
+    list.iter()
+        .filter_map(|item| {
+            let name = item.get("name")?.as_str()?;
+            let id = item
+                .get("id")
+                .and_then(Value::as_str)
+                .filter(|s| !s.is_empty())
+                .unwrap_or(name);
+            let params = item
+                .get("params")
+                .cloned()
+                .unwrap_or(Value::Object(Default::default()));
+            Some((id.to_string(), fingerprint(name, &params)))
+        })
+        .collect()
+}
+
+fn service_fingerprint(service: &str) -> Option<String> {
+    service_fingerprints()
+        .into_iter()
+        .find(|(id, _)| id == service)
+        .map(|(_, fingerprint)| fingerprint)
+}
+
+pub fn enabled() -> bool {
+    get_or_bool("use_cache", true)
+}
+
+/// 读取缓存，过期的缓存会被删除，命中时更新访问时间
+pub fn get(
+    service: &str,
+    fingerprint: &str,
+    glossary: &str,
+    text: &str,
+    from: &str,
+    to: &str,
+) -> Option<Value> {
+    let key = key(service, fingerprint, glossary, text, from, to);
+    let expire = now() - ttl();
+    with_conn(|conn| {
+        let row: Option<(String, i64)> = conn
+            .query_row(
+                "SELECT value, created FROM entries WHERE key = ?1",
+                [&key],
+                |r| Ok((r.get(0)?, r.get(1)?)),
+            )
+            .optional()?;
+        match row {
+            Some((_, created)) if created < expire => {
+                conn.execute("DELETE FROM entries WHERE key = ?1", [&key])?;
+                Ok(None)
+            }
+            Some((value, _)) => {
+                conn.execute(
+                    "UPDATE entries SET accessed = ?2 WHERE key = ?1",
+                    params![key, now()],
+                )?;
+                Ok(serde_json::from_str(&value).ok())
+            }
+            None => Ok(None),
+        }
+    })
+    .unwrap_or_else(|e| {
+        warn!("{}", e);
+        None
+    })
+}
+
+/// 写入缓存，之后清理过期的缓存，超过容量时按最近最少使用清理
+pub fn put(
+    service: &str,
+    fingerprint: &str,
+    glossary: &str,
+    text: &str,
+    from: &str,
+    to: &str,
+    value: &Value,
+) {
+    let key = key(service, fingerprint, glossary, text, from, to);
+    let value = value.to_string();
+    let size = (key.len() + value.len()) as i64;
+    let (time, expire, max_size) = (now(), now() - ttl(), max_size());
+    let res = with_conn(|conn| {
+        conn.execute(
+            "INSERT OR REPLACE INTO entries (key, service, fingerprint, value, size, created, accessed)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?6)",
+            params![key, service, fingerprint, value, size, time],
+        )?;
+        let mut count = conn.execute("DELETE FROM entries WHERE created < ?1", [expire])?;
+        count += conn.execute(
+            "DELETE FROM entries WHERE key IN (
+                 SELECT key FROM (
+                     SELECT key, SUM(size) OVER (ORDER BY accessed DESC, rowid DESC) AS total FROM entries
+                 ) WHERE total > ?1
+             )",
+            [max_size],
+        )?;
+        Ok(count)
+    });
+    match res {
+        Ok(n) if n > 0 => debug!("translate cache evicted {} entries", n),
+        Err(e) => warn!("{}", e),
+        _ => {}
+    }
+}
+
+/// 删除服务已删除或参数已变化的缓存
+pub fn invalidate() -> usize {
+    let current: HashSet<(String, String)> = service_fingerprints().into_iter().collect();
+    let res = with_conn(|conn| {
+        let stale: Vec<(String, String)> = conn
+            .prepare("SELECT DISTINCT service, fingerprint FROM entries")?
+            .query_map([], |r| Ok((r.get(0)?, r.get(1)?)))?
+            .filter_map(|r| r.ok())
+            .filter(|pair| !current.contains(pair))
+            .collect();
+        let mut count = 0;
+        for (service, fingerprint) in stale {
+            count += conn.execute(
+                "DELETE FROM entries WHERE service = ?1 AND fingerprint = ?2",
+                params![service, fingerprint],
+            )?;
+        }
+        Ok(count)
+    });
+    match res {
+        Ok(n) => {
+            if n > 0 {
+                debug!("translate cache invalidated {} entries", n);
+            }
+            n
+        }
+        Err(e) => {
+            warn!("{}", e);
+            0
+        }
+    }
+}
+
+pub fn clear() -> Result<usize, String> {
+    with_conn(|conn| conn.execute("DELETE FROM entries", []))
+}
+
+pub fn stats() -> Result<Stats, String> {
+    with_conn(|conn| {
+        conn.query_row(
+            "SELECT COUNT(*), COALESCE(SUM(size), 0) FROM entries",
+            [],
+            |r| {
+                Ok(Stats {
+                    count: r.get(0)?,
+                    size: r.get(1)?,
+                })
+            },
+        )
+    })
+}
+
+/// 读取缓存，service 为服务配置的 id，from to 为服务的语种代码
+#[tauri::command]
+pub fn trans_cache_get(
+    service: String,
+    text: String,
+    from: String,
+    to: String,
+    glossary: Option<Prepared>,
+) -> Result<Option<Value>, ()> {
+    if !enabled() {
+        return Ok(None);
+    }
+    let glossary = glossary.map(|g| g.terms_key()).unwrap_or_default();
+    Ok(service_fingerprint(&service).and_then(|f| get(&service, &f, &glossary, &text, &from, &to)))
+}
+
+#[tauri::command]
+pub fn trans_cache_put(
+    service: String,
+    text: String,
+    from: String,
+    to: String,
+    value: Value,
+    glossary: Option<Prepared>,
+) -> Result<(), ()> {
+    if let Some(fingerprint) = service_fingerprint(&service) {
+        let glossary = glossary.map(|g| g.terms_key()).unwrap_or_default();
+        put(&service, &fingerprint, &glossary, &text, &from, &to, &value);
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn trans_cache_clear() -> Result<usize, String> {
+    clear()
+}
+
+#[tauri::command]
+pub fn trans_cache_stats() -> Result<Stats, String> {
+    stats()
+}
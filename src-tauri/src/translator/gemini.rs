@@ -0,0 +1,186 @@
+use super::{
+    client, fill_prompt,
+    openai::LANGUAGES,
+    param_str, read_json,
+    stream::{read_sse, StreamSink, Usage},
+    trim_quotes, Capabilities, Params, TranslationProvider,
+};
+use async_trait::async_trait;
+use serde_json::{json, Value};
+
+const DEFAULT_URL: &str =
+    "https://generativelanguage.googleapis.com/v1beta/models/gemini-pro:generateContent";
+const DEFAULT_PROMPT: &str = r#"[
+	{
+		"role": "user",
+		"parts": [{ "text": "You are a professional translation engine, please translate the text into a colloquial, professional, elegant and fluent content, without the style of machine translation. You must only translate the text content, never interpret it." }]
+	},
+	{
+		"role": "model",
+		"parts": [{ "text": "Ok, I will only translate the text content, never interpret it." }]
+	},
+	{
+		"role": "user",
+		"parts": [{ "text": "Translate into Chinese\n\"\"\"\nhello\n\"\"\"" }]
+	},
+	{
+		"role": "model",
+		"parts": [{ "text": "你好" }]
+	},
+	{
+		"role": "user",
+		"parts": [{ "text": "Translate into $to\n\"\"\"\n$text\n\"\"\"" }]
+	}
+]"#;
+
+fn request(
+    params: &Params,
+    contents: Value,
+    stream: bool,
+) -> Result<reqwest::RequestBuilder, String> {
+    let api_key = param_str(params, "apiKey", "");
+    if api_key.is_empty() {
+        return Err("Api Key is required".to_string());
+    }
+    let url = param_str(params, "url", DEFAULT_URL);
+    let mut req = if stream {
+        // 流式接口为 streamGenerateContent，alt=sse 时以SSE格式返回
+        client()
+            .post(url.replace(":generateContent", ":streamGenerateContent"))
+            .query(&[("alt", "sse")])
+    } else {
+        client().post(url)
+    };
+    req = req.query(&[("key", api_key)]).json(&json!({
+        "contents": contents,
+        "safetySettings": [
+            { "category": "HARM_CATEGORY_HARASSMENT", "threshold": "BLOCK_NONE" },
+            { "category": "HARM_CATEGORY_HATE_SPEECH", "threshold": "BLOCK_NONE" },
+            { "category": "HARM_CATEGORY_SEXUALLY_EXPLICIT", "threshold": "BLOCK_NONE" },
+            { "category": "HARM_CATEGORY_DANGEROUS_CONTENT", "threshold": "BLOCK_NONE" }
+        ]
+    }));
+    Ok(req)
+}
+
+/// 拼接候选结果中所有片段的文本
+fn candidate_text(data: &Value) -> String {
+    data["candidates"][0]["content"]["parts"]
+        .as_array()
+        .map(|parts| parts.iter().filter_map(|p| p["text"].as_str()).collect())
+        .unwrap_or_default()
+}
+
+/// 读取 streamGenerateContent 的SSE响应
+pub(super) async fn generate_stream(
+    req: reqwest::RequestBuilder,
+    sink: &mut StreamSink<'_>,
+) -> Result<(String, Option<Usage>), String> {
+    let mut result = String::new();
+    let mut usage = None;
+    read_sse(req.send().await, sink.timeout, |data| {
+        let data: Value = serde_json::from_str(data).map_err(|e| format!("{}: {}", e, data))?;
+        if let Some(error) = data.get("error") {
+            return Err(error.to_string());
+        }
+        let delta = candidate_text(&data);
+        result.push_str(&delta);
+        sink.delta(&delta);
+        // 每个数据块都带有累计的用量，取最后一次
+        if let Some(meta) = data.get("usageMetadata") {
+            let get = |key: &str| meta[key].as_u64().unwrap_or_default();
+            usage = Some(Usage {
+                prompt_tokens: get("promptTokenCount"),
+                completion_tokens: get("candidatesTokenCount"),
+                total_tokens: get("totalTokenCount"),
+            });
+        }
+        Ok(false)
+    })
+    .await?;
+    let result = trim_quotes(&result);
+    if result.is_empty() {
+        return Err("翻译结果为空".to_string());
+    }
+    Ok((result, usage))
+}
+
+pub struct GeminiPro;
+
+#[async_trait]
+impl TranslationProvider for GeminiPro {
+    fn name(&self) -> &'static str {
+        "gemini-pro"
+    }
+
+    fn languages(&self) -> &'static [(&'static str, &'static str)] {
+        LANGUAGES
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            translate: true,
+            stream: true,
+            prompt: true,
+            ..Default::default()
+        }
+    }
+
+    async fn translate(
+        &self,
+        params: &Params,
+        text: &str,
+        from: &str,
+        to: &str,
+    ) -> Result<String, String> {
+        let contents = fill_prompt(
+            param_str(params, "prompt", DEFAULT_PROMPT),
+            text,
+            from,
+            to,
+            param_str(params, "glossary", ""),
+        )?;
+        let req = request(params, contents, false)?;
+        let data = read_json(req.send().await).await?;
+        let content = candidate_text(&data);
+        if content.trim().is_empty() {
+            return Err(data.to_string());
+        }
+        Ok(trim_quotes(&content))
+    }
+
+    async fn translate_stream(
+        &self,
+        params: &Params,
+        text: &str,
+        from: &str,
+        to: &str,
+        sink: &mut StreamSink<'_>,
+    ) -> Result<(String, Option<Usage>), String> {
+        let contents = fill_prompt(
+            param_str(params, "prompt", DEFAULT_PROMPT),
+            text,
+            from,
+            to,
+            param_str(params, "glossary", ""),
+        )?;
+        generate_stream(request(params, contents, true)?, sink).await
+    }
+
+    async fn complete(
+        &self,
+        params: &Params,
+        system: &str,
+        prompt: &str,
+        sink: &mut StreamSink<'_>,
+    ) -> Result<(String, Option<Usage>), String> {
+        // 以对话的形式传入系统提示词
+        let mut contents = Vec::new();
+        if !system.trim().is_empty() {
+            contents.push(json!({ "role": "user", "parts": [{ "text": system }] }));
+            contents.push(json!({ "role": "model", "parts": [{ "text": "OK" }] }));
+        }
+        contents.push(json!({ "role": "user", "parts": [{ "text": prompt }] }));
+        generate_stream(request(params, Value::Array(contents), true)?, sink).await
+    }
+}
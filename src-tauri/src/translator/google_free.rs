@@ -0,0 +1,164 @@
+use super::{client, param_str, read_json, Capabilities, Params, TranslationProvider};
+use crate::dictionary::{DictResult, Explanation, Pronunciation};
+use async_trait::async_trait;
+use serde_json::Value;
+
+const DEFAULT_URL: &str = "https://translate.google.com";
+
+const LANGUAGES: &[(&str, &str)] = &[
+    ("auto", "auto"),
+    ("zh_cn", "zh-CN"),
+    ("zh_tw", "zh-TW"),
+    ("ja", "ja"),
+    ("en", "en"),
+    ("ko", "ko"),
+    ("fr", "fr"),
+    ("es", "es"),
+    ("ru", "ru"),
+    ("de", "de"),
+    ("it", "it"),
+    ("tr", "tr"),
+    ("pt_pt", "pt"),
+    ("pt_br", "pt"),
+    ("vi", "vi"),
+    ("id", "id"),
+    ("th", "th"),
+    ("ms", "ms"),
+    ("ar", "ar"),
+    ("hi", "hi"),
+    ("mn_cy", "mn"),
+    ("km", "km"),
+    ("nb_no", "no"),
+    ("nn_no", "no"),
+    ("fa", "fa"),
+    ("sv", "sv"),
+    ("pl", "pl"),
+    ("nl", "nl"),
+];
+
+/// 谷歌网页翻译接口，可修改为cloudflare代理
+pub struct GoogleFree;
+
+async fn request(params: &Params, text: &str, from: &str, to: &str) -> Result<Value, String> {
+    let url = format!(
+        "{}/translate_a/single?dt=at&dt=bd&dt=ex&dt=ld&dt=md&dt=qca&dt=rw&dt=rm&dt=ss&dt=t",
+        param_str(params, "url", DEFAULT_URL).trim_end_matches('/')
+    );
+    let res = client()
+        .get(url)
+        .query(&[
+            ("client", "gtx"),
+            ("sl", from),
+            ("tl", to),
+            ("hl", to),
+            ("ie", "UTF-8"),
+            ("oe", "UTF-8"),
+            ("otf", "1"),
+            ("ssel", "0"),
+            ("tsel", "0"),
+            ("kc", "7"),
+            ("q", text),
+        ])
+        .send()
+        .await;
+    read_json(res).await
+}
+
+/// 翻译模式的结果为多个句子，依次拼接
+fn join_sentences(data: &Value) -> String {
+    data[0]
+        .as_array()
+        .map(|list| {
+            list.iter()
+                .filter_map(|r| r[0].as_str())
+                .collect::<String>()
+        })
+        .unwrap_or_default()
+        .trim()
+        .to_string()
+}
+
+#[async_trait]
+impl TranslationProvider for GoogleFree {
+    fn name(&self) -> &'static str {
+        "google-free"
+    }
+
+    fn languages(&self) -> &'static [(&'static str, &'static str)] {
+        LANGUAGES
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            detect: true,
+            translate: true,
+            dict: true,
+            ..Default::default()
+        }
+    }
+
+    async fn detect(&self, params: &Params, text: &str) -> Result<String, String> {
+        let data = request(params, text, "auto", "zh-CN").await?;
+        Ok(data[2].as_str().unwrap_or("en").to_string())
+    }
+
+    async fn translate(
+        &self,
+        params: &Params,
+        text: &str,
+        from: &str,
+        to: &str,
+    ) -> Result<String, String> {
+        let data = request(params, text, from, to).await?;
+        if !data[1].is_null() {
+            if let Some(s) = data[0][0][0].as_str() {
+                return Ok(s.to_string());
+            }
+        }
+        Ok(join_sentences(&data))
+    }
+
+    async fn dict(
+        &self,
+        params: &Params,
+        text: &str,
+        from: &str,
+        to: &str,
+    ) -> Result<DictResult, String> {
+        let data = request(params, text, from, to).await?;
+        // 没有词典数据时为翻译模式，交由文本翻译处理
+        let entries = match data[1].as_array() {
+            Some(list) => list,
+            None => return Err("未找到词典释义".to_string()),
+        };
+        let mut result = DictResult::new(data[0][0][0].as_str().unwrap_or(text), "");
+        if let Some(symbol) = data[0][1][3].as_str() {
+            result.pronunciations.push(Pronunciation {
+                region: String::new(),
+                symbol: symbol.to_string(),
+                voice: None,
+            });
+        }
+        for entry in entries {
+            let explains: Vec<String> = entry[2]
+                .as_array()
+                .map(|list| {
+                    list.iter()
+                        .filter_map(|x| x[0].as_str().map(String::from))
+                        .collect()
+                })
+                .unwrap_or_default();
+            result.explanations.push(Explanation {
+                trait_: entry[0].as_str().unwrap_or_default().to_string(),
+                explains,
+            });
+        }
+        if let Some(list) = data[13][0].as_array() {
+            result.sentence = list
+                .iter()
+                .filter_map(|x| x[0].as_str().map(String::from))
+                .collect();
+        }
+        Ok(result)
+    }
+}
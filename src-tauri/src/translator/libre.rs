@@ -0,0 +1,301 @@
+use super::{client, param_bool, param_str, read_json, Capabilities, Params, TranslationProvider};
+use crate::{
+    config::get_config_with_default,
+    engine::{self, EngineSpec},
+};
+use async_trait::async_trait;
+use log::{debug, warn};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+const NAME: &str = "libre-translate";
+const DEFAULT_URL: &str = "http://127.0.0.1:5000";
+const DEFAULT_COMMAND: &str = "libretranslate";
+const DEFAULT_ARGS: &str = "--host 127.0.0.1 --port 5000";
+/// 没有直接的语言包时经英文中转
+const PIVOT: &str = "en";
+/// 已安装语言包的缓存时间
+const LANGUAGES_TTL: Duration = Duration::from_secs(600);
+/// 引擎启动后加载语言模型的最长等待时间
+const READY_TIMEOUT: Duration = Duration::from_secs(60);
+
+const LANGUAGES: &[(&str, &str)] = &[
+    ("auto", "auto"),
+    ("zh_cn", "zh"),
+    ("zh_tw", "zt"),
+    ("en", "en"),
+    ("ja", "ja"),
+    ("ko", "ko"),
+    ("fr", "fr"),
+    ("es", "es"),
+    ("ru", "ru"),
+    ("de", "de"),
+    ("it", "it"),
+    ("tr", "tr"),
+    ("pt_pt", "pt"),
+    ("pt_br", "pt"),
+    ("vi", "vi"),
+    ("id", "id"),
+    ("th", "th"),
+    ("ms", "ms"),
+    ("ar", "ar"),
+    ("hi", "hi"),
+    ("nb_no", "nb"),
+    ("fa", "fa"),
+    ("sv", "sv"),
+    ("pl", "pl"),
+    ("nl", "nl"),
+];
+
+/// /languages 接口返回的语种及可翻译的目标语种
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Language {
+    pub code: String,
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub targets: Vec<String>,
+}
+
+struct CachedLanguages {
+    time: Instant,
+    /// 缓存时的引擎进程序号，引擎重启后需要重新等待服务就绪
+    generation: u64,
+    list: Arc<Vec<Language>>,
+}
+
+static LANGUAGES_CACHE: Lazy<Mutex<HashMap<String, CachedLanguages>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+fn base_url(params: &Params) -> &str {
+    param_str(params, "url", DEFAULT_URL).trim_end_matches('/')
+}
+
+fn engine_spec(params: &Params) -> EngineSpec {
+    EngineSpec::new(
+        param_str(params, "command", DEFAULT_COMMAND),
+        param_str(params, "args", DEFAULT_ARGS),
+    )
+}
+
+fn use_engine(params: &Params) -> bool {
+    param_bool(params, "engine", false)
+}
+
+/// 需要由应用启动引擎时确保引擎已运行
+fn prepare(params: &Params) -> Result<&str, String> {
+    if use_engine(params) {
+        engine::start_engine_worker(&engine_spec(params))?;
+    }
+    Ok(base_url(params))
+}
+
+fn with_api_key(mut body: Value, params: &Params) -> Value {
+    let api_key = param_str(params, "apiKey", "");
+    if !api_key.is_empty() {
+        body["api_key"] = json!(api_key);
+    }
+    body
+}
+
+async fn fetch_languages(url: &str) -> Result<Vec<Language>, String> {
+    let data = read_json(client().get(format!("{}/languages", url)).send().await).await?;
+    serde_json::from_value(data).map_err(|e| e.to_string())
+}
+
+/// 已安装的语言包，引擎刚启动时等待服务就绪
+pub async fn languages(params: &Params) -> Result<Arc<Vec<Language>>, String> {
+    let url = prepare(params)?;
+    let generation = if use_engine(params) {
+        engine::generation()
+    } else {
+        0
+    };
+    if let Some(cached) = LANGUAGES_CACHE.lock().unwrap().get(url) {
+        if cached.generation == generation && cached.time.elapsed() < LANGUAGES_TTL {
+            return Ok(cached.list.clone());
+        }
+    }
+    let deadline = Instant::now() + READY_TIMEOUT;
+    loop {
+        match fetch_languages(url).await {
+            Ok(list) => {
+                let list = Arc::new(list);
+                LANGUAGES_CACHE.lock().unwrap().insert(
+                    url.to_string(),
+                    CachedLanguages {
+                        time: Instant::now(),
+                        generation,
+                        list: list.clone(),
+                    },
+                );
+                return Ok(list);
+            }
+            Err(e)
+                if use_engine(params)
+                    && engine::is_engine_running()
+                    && Instant::now() < deadline =>
+            {
+                debug!("engine not ready: {}", e);
+                tokio::time::sleep(Duration::from_millis(500)).await;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+fn has_pair(languages: &[Language], from: &str, to: &str) -> bool {
+    languages
+        .iter()
+        .any(|l| l.code == from && l.targets.iter().any(|t| t == to))
+}
+
+/// 翻译路径，没有直接的语言包时经英文中转
+fn route<'a>(
+    languages: &[Language],
+    from: &'a str,
+    to: &'a str,
+) -> Option<Vec<(&'a str, &'a str)>> {
+    if has_pair(languages, from, to) {
+        return Some(vec![(from, to)]);
+    }
+    if from != PIVOT
+        && to != PIVOT
+        && has_pair(languages, from, PIVOT)
+        && has_pair(languages, PIVOT, to)
+    {
+        return Some(vec![(from, PIVOT), (PIVOT, to)]);
+    }
+    None
+}
+
+async fn translate_pair(
+    params: &Params,
+    text: &str,
+    from: &str,
+    to: &str,
+) -> Result<String, String> {
+    let body = with_api_key(
+        json!({ "q": text, "source": from, "target": to, "format": "text" }),
+        params,
+    );
+    let data = read_json(
+        client()
+            .post(format!("{}/translate", base_url(params)))
+            .json(&body)
+            .send()
+            .await,
+    )
+    .await?;
+    match data["translatedText"].as_str() {
+        Some(result) => Ok(result.to_string()),
+        None => Err(data.to_string()),
+    }
+}
+
+/// 运行启用的服务配置的本地引擎，返回是否有启用的服务使用本地引擎
+pub fn start_engines() -> bool {
+    let services = get_config_with_default("trans_services", Vec::new(), |v| v.as_array().cloned());
+    for service in services {
+        if service["name"] != NAME || !service["enable"].as_bool().unwrap_or(false) {
+            continue;
+        }
+        let params = service["params"].as_object().cloned().unwrap_or_default();
+        if use_engine(&params) {
+            if let Err(e) = engine::start_engine_worker(&engine_spec(&params)) {
+                warn!("{}", e);
+            }
+            // 只能运行一个引擎进程
+            return true;
+        }
+    }
+    false
+}
+
+pub struct LibreTranslate;
+
+#[async_trait]
+impl TranslationProvider for LibreTranslate {
+    fn name(&self) -> &'static str {
+        NAME
+    }
+
+    fn languages(&self) -> &'static [(&'static str, &'static str)] {
+        LANGUAGES
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            detect: true,
+            translate: true,
+            ..Default::default()
+        }
+    }
+
+    async fn detect(&self, params: &Params, text: &str) -> Result<String, String> {
+        let url = prepare(params)?;
+        let body = with_api_key(json!({ "q": text }), params);
+        let data = read_json(
+            client()
+                .post(format!("{}/detect", url))
+                .json(&body)
+                .send()
+                .await,
+        )
+        .await?;
+        match data[0]["language"].as_str() {
+            Some(lang) => Ok(lang.to_string()),
+            None => Err(data.to_string()),
+        }
+    }
+
+    async fn translate(
+        &self,
+        params: &Params,
+        text: &str,
+        from: &str,
+        to: &str,
+    ) -> Result<String, String> {
+        let languages = languages(params).await?;
+        let from = if from == "auto" {
+            self.detect(params, text).await?
+        } else {
+            from.to_string()
+        };
+        let route = route(&languages, &from, to)
+            .ok_or_else(|| format!("本地引擎未安装 {} -> {} 的语言包", from, to))?;
+        let mut result = text.to_string();
+        for (from, to) in route {
+            result = translate_pair(params, &result, from, to).await?;
+        }
+        Ok(result)
+    }
+}
+
+/// 供前端插件调用，from to 为服务语种
+#[tauri::command]
+pub async fn libre_translate(
+    params: Params,
+    text: String,
+    from: String,
+    to: String,
+) -> Result<String, String> {
+    LibreTranslate.translate(&params, &text, &from, &to).await
+}
+
+#[tauri::command]
+pub async fn libre_detect(params: Params, text: String) -> Result<String, String> {
+    LibreTranslate.detect(&params, &text).await
+}
+
+/// 本地引擎已安装的语言包
+#[tauri::command]
+pub async fn libre_languages(params: Params) -> Result<Vec<Language>, String> {
+    Ok(languages(&params).await?.to_vec())
+}
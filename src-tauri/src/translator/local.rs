@@ -0,0 +1,94 @@
+use super::{param_bool, param_str, Capabilities, Params, TranslationProvider};
+use crate::{
+    dictionary::{ecdict, DictResult},
+    zh_convert::Converter,
+};
+use async_trait::async_trait;
+
+/// 本地简繁转换
+pub struct OpenCC;
+
+#[async_trait]
+impl TranslationProvider for OpenCC {
+    fn name(&self) -> &'static str {
+        "opencc"
+    }
+
+    fn languages(&self) -> &'static [(&'static str, &'static str)] {
+        &[("zh_cn", "zh_cn"), ("zh_tw", "zh_tw")]
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            translate: true,
+            ..Default::default()
+        }
+    }
+
+    async fn translate(
+        &self,
+        params: &Params,
+        text: &str,
+        from: &str,
+        to: &str,
+    ) -> Result<String, String> {
+        if from == to {
+            return Ok(text.to_string());
+        }
+        let region = param_str(params, "region", "tw");
+        let phrase = if param_bool(params, "phrase", true) && region != "t" {
+            "p"
+        } else {
+            ""
+        };
+        let mode = match (to, region) {
+            ("zh_tw", "t") => "s2t".to_string(),
+            ("zh_tw", _) => format!("s2{}{}", region, phrase),
+            ("zh_cn", "t") => "t2s".to_string(),
+            ("zh_cn", _) => format!("{}2s{}", region, phrase),
+            _ => return Err("仅支持简体中文与繁体中文之间的转换".to_string()),
+        };
+        Converter::from_mode(&mode)
+            .map(|c| c.convert(text))
+            .ok_or_else(|| format!("unsupported convert mode: {}", mode))
+    }
+}
+
+/// 本地ECDICT英汉词典
+pub struct ECDict;
+
+#[async_trait]
+impl TranslationProvider for ECDict {
+    fn name(&self) -> &'static str {
+        "ecdict"
+    }
+
+    fn languages(&self) -> &'static [(&'static str, &'static str)] {
+        &[("en", "en"), ("zh_cn", "zh_cn")]
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            dict: true,
+            ..Default::default()
+        }
+    }
+
+    async fn dict(
+        &self,
+        _params: &Params,
+        text: &str,
+        from: &str,
+        to: &str,
+    ) -> Result<DictResult, String> {
+        if from != "en" || to != "zh_cn" {
+            return Err("仅支持英文查询中文释义".to_string());
+        }
+        let text = text.to_string();
+        // 查询SQLite为阻塞操作
+        tauri::async_runtime::spawn_blocking(move || ecdict::lookup(&text))
+            .await
+            .map_err(|e| e.to_string())??
+            .ok_or_else(|| "未找到该单词".to_string())
+    }
+}
@@ -0,0 +1,362 @@
+use super::{
+    client, openai, param_num, param_str, read_json,
+    stream::{read_ndjson, StreamSink, Usage},
+    trim_quotes, Capabilities, Params, TranslationProvider,
+};
+use crate::config::get_config_with_default;
+use async_trait::async_trait;
+use log::{debug, warn};
+use serde_json::{json, Value};
+
+const NAME: &str = "local-llm";
+const DEFAULT_URL: &str = "http://127.0.0.1:11434";
+/// Ollama 默认5分钟后卸载模型，翻译请求间隔通常更长
+const DEFAULT_KEEP_ALIVE: &str = "30m";
+const DEFAULT_SYSTEM_PROMPT: &str = "You are a professional translation engine, please translate the text into a colloquial, professional, elegant and fluent content, without the style of machine translation. You must only translate the text content, never interpret it, and only output the translation.";
+const DEFAULT_PROMPT: &str = "Translate from {from} into {to}:\n\"\"\"\n{text}\n\"\"\"";
+
+/// 本地模型服务的接口类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Api {
+    /// Ollama /api/chat
+    OllamaChat,
+    /// Ollama /api/generate
+    OllamaGenerate,
+    /// llama.cpp server、vLLM 等 OpenAI 兼容接口
+    OpenAI,
+}
+
+impl Api {
+    fn from_params(params: &Params) -> Self {
+        match param_str(params, "api", "ollama-chat") {
+            "ollama-generate" => Api::OllamaGenerate,
+            "openai" => Api::OpenAI,
+            _ => Api::OllamaChat,
+        }
+    }
+}
+
+/// Ollama 接口地址，可填写服务根地址或 /api
+fn ollama_url(params: &Params, path: &str) -> String {
+    let base = param_str(params, "url", DEFAULT_URL).trim_end_matches('/');
+    let base = base.strip_suffix("/api").unwrap_or(base);
+    format!("{}/api/{}", base, path)
+}
+
+/// OpenAI 兼容接口地址，可填写服务根地址、/v1 或完整的 chat/completions 地址
+fn openai_url(params: &Params, path: &str) -> String {
+    let base = param_str(params, "url", DEFAULT_URL).trim_end_matches('/');
+    let base = base.strip_suffix("/chat/completions").unwrap_or(base);
+    let base = base.strip_suffix("/v1").unwrap_or(base);
+    format!("{}/v1/{}", base, path)
+}
+
+/// 替换提示词模板中的 {text} {from} {to} {glossary}，原文最后替换，避免原文中的占位符被替换
+fn fill_template(template: &str, text: &str, from: &str, to: &str, glossary: &str) -> String {
+    let from = if from == "Auto" {
+        "the original language"
+    } else {
+        from
+    };
+    let mut template = template.to_string();
+    if !glossary.is_empty() && !template.contains("{glossary}") {
+        template.push_str("\n\n{glossary}");
+    }
+    template
+        .replace("{from}", from)
+        .replace("{to}", to)
+        .replace("{glossary}", glossary)
+        .replace("{text}", text)
+}
+
+/// 模型保持加载的时长，纯数字为秒数，-1 为一直保持
+fn keep_alive(params: &Params) -> Value {
+    let value = param_str(params, "keepAlive", DEFAULT_KEEP_ALIVE);
+    value
+        .parse::<i64>()
+        .map_or_else(|_| json!(value), |n| json!(n))
+}
+
+fn ollama_options(params: &Params) -> Value {
+    let mut options = json!({ "temperature": param_num(params, "temperature").unwrap_or(0.0) });
+    if let Some(num_ctx) = param_num(params, "contextLength").filter(|n| *n > 0.0) {
+        options["num_ctx"] = json!(num_ctx as u64);
+    }
+    options
+}
+
+fn model(params: &Params) -> Result<&str, String> {
+    let model = param_str(params, "model", "");
+    if model.is_empty() {
+        return Err("Model is required".to_string());
+    }
+    Ok(model)
+}
+
+fn with_api_key(req: reqwest::RequestBuilder, params: &Params) -> reqwest::RequestBuilder {
+    let api_key = param_str(params, "apiKey", "");
+    if api_key.is_empty() {
+        req
+    } else {
+        req.bearer_auth(api_key)
+    }
+}
+
+fn request(
+    params: &Params,
+    system: &str,
+    prompt: &str,
+    stream: bool,
+) -> Result<reqwest::RequestBuilder, String> {
+    let model = model(params)?;
+    let messages = openai::prompt_messages(system, prompt);
+    let req = match Api::from_params(params) {
+        Api::OllamaChat => client().post(ollama_url(params, "chat")).json(&json!({
+            "model": model,
+            "messages": messages,
+            "stream": stream,
+            "keep_alive": keep_alive(params),
+            "options": ollama_options(params)
+        })),
+        Api::OllamaGenerate => client().post(ollama_url(params, "generate")).json(&json!({
+            "model": model,
+            "system": system,
+            "prompt": prompt,
+            "stream": stream,
+            "keep_alive": keep_alive(params),
+            "options": ollama_options(params)
+        })),
+        Api::OpenAI => {
+            let mut body = json!({
+                "model": model,
+                "messages": messages,
+                "temperature": param_num(params, "temperature").unwrap_or(0.0),
+                "stream": stream
+            });
+            if stream {
+                body["stream_options"] = json!({ "include_usage": true });
+            }
+            client()
+                .post(openai_url(params, "chat/completions"))
+                .json(&body)
+        }
+    };
+    Ok(with_api_key(req, params))
+}
+
+/// Ollama 返回内容，chat 接口在 message.content，generate 接口在 response
+fn ollama_content(data: &Value) -> Option<&str> {
+    data["message"]["content"]
+        .as_str()
+        .or_else(|| data["response"].as_str())
+}
+
+fn ollama_usage(data: &Value) -> Option<Usage> {
+    let prompt_tokens = data["prompt_eval_count"].as_u64();
+    let completion_tokens = data["eval_count"].as_u64();
+    if prompt_tokens.is_none() && completion_tokens.is_none() {
+        return None;
+    }
+    let prompt_tokens = prompt_tokens.unwrap_or_default();
+    let completion_tokens = completion_tokens.unwrap_or_default();
+    Some(Usage {
+        prompt_tokens,
+        completion_tokens,
+        total_tokens: prompt_tokens + completion_tokens,
+    })
+}
+
+/// 服务中可用的模型
+pub async fn models(params: &Params) -> Result<Vec<String>, String> {
+    let mut list: Vec<String> = match Api::from_params(params) {
+        Api::OpenAI => {
+            let req = with_api_key(client().get(openai_url(params, "models")), params);
+            let data = read_json(req.send().await).await?;
+            data["data"]
+                .as_array()
+                .ok_or_else(|| data.to_string())?
+                .iter()
+                .filter_map(|m| m["id"].as_str().map(String::from))
+                .collect()
+        }
+        _ => {
+            let data = read_json(client().get(ollama_url(params, "tags")).send().await).await?;
+            data["models"]
+                .as_array()
+                .ok_or_else(|| data.to_string())?
+                .iter()
+                .filter_map(|m| m["name"].as_str().map(String::from))
+                .collect()
+        }
+    };
+    list.sort();
+    Ok(list)
+}
+
+/// 预加载模型，避免首次翻译时等待模型加载。OpenAI 兼容服务启动时已加载模型，无需处理
+pub async fn warm_up(params: &Params) -> Result<(), String> {
+    if Api::from_params(params) == Api::OpenAI {
+        return Ok(());
+    }
+    let body = json!({ "model": model(params)?, "keep_alive": keep_alive(params) });
+    read_json(
+        client()
+            .post(ollama_url(params, "generate"))
+            .json(&body)
+            .send()
+            .await,
+    )
+    .await?;
+    Ok(())
+}
+
+/// 启动时预加载所有启用的本地大模型服务
+pub fn warm_up_services() {
+    let services = get_config_with_default("trans_services", Vec::new(), |v| v.as_array().cloned());
+    for service in services {
+        if service["name"] != NAME || !service["enable"].as_bool().unwrap_or(false) {
+            continue;
+        }
+        let params = service["params"].as_object().cloned().unwrap_or_default();
+        tauri::async_runtime::spawn(async move {
+            match warm_up(&params).await {
+                Ok(_) => debug!("warm up {} success", param_str(&params, "model", "")),
+                Err(e) => warn!("warm up {} error: {}", param_str(&params, "model", ""), e),
+            }
+        });
+    }
+}
+
+async fn complete_stream(
+    params: &Params,
+    system: &str,
+    prompt: &str,
+    sink: &mut StreamSink<'_>,
+) -> Result<(String, Option<Usage>), String> {
+    let req = request(params, system, prompt, true)?;
+    if Api::from_params(params) == Api::OpenAI {
+        return openai::chat_stream(req, sink).await;
+    }
+    let mut text = String::new();
+    let mut usage = None;
+    read_ndjson(req.send().await, sink.timeout, |data| {
+        if let Some(error) = data.get("error") {
+            return Err(error.to_string());
+        }
+        if let Some(delta) = ollama_content(data) {
+            text.push_str(delta);
+            sink.delta(delta);
+        }
+        if data["done"].as_bool().unwrap_or(false) {
+            usage = ollama_usage(data);
+            return Ok(true);
+        }
+        Ok(false)
+    })
+    .await?;
+    let text = trim_quotes(&text);
+    if text.is_empty() {
+        return Err("翻译结果为空".to_string());
+    }
+    Ok((text, usage))
+}
+
+pub struct LocalLLM;
+
+#[async_trait]
+impl TranslationProvider for LocalLLM {
+    fn name(&self) -> &'static str {
+        NAME
+    }
+
+    fn languages(&self) -> &'static [(&'static str, &'static str)] {
+        openai::LANGUAGES
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            translate: true,
+            stream: true,
+            prompt: true,
+            ..Default::default()
+        }
+    }
+
+    async fn translate(
+        &self,
+        params: &Params,
+        text: &str,
+        from: &str,
+        to: &str,
+    ) -> Result<String, String> {
+        let system = param_str(params, "systemPrompt", DEFAULT_SYSTEM_PROMPT);
+        let prompt = fill_template(
+            param_str(params, "prompt", DEFAULT_PROMPT),
+            text,
+            from,
+            to,
+            param_str(params, "glossary", ""),
+        );
+        let req = request(params, system, &prompt, false)?;
+        if Api::from_params(params) == Api::OpenAI {
+            return openai::chat(req).await;
+        }
+        let data = read_json(req.send().await).await?;
+        match ollama_content(&data) {
+            Some(content) if !content.trim().is_empty() => Ok(trim_quotes(content)),
+            _ => Err(data.to_string()),
+        }
+    }
+
+    async fn translate_stream(
+        &self,
+        params: &Params,
+        text: &str,
+        from: &str,
+        to: &str,
+        sink: &mut StreamSink<'_>,
+    ) -> Result<(String, Option<Usage>), String> {
+        let system = param_str(params, "systemPrompt", DEFAULT_SYSTEM_PROMPT);
+        let prompt = fill_template(
+            param_str(params, "prompt", DEFAULT_PROMPT),
+            text,
+            from,
+            to,
+            param_str(params, "glossary", ""),
+        );
+        complete_stream(params, system, &prompt, sink).await
+    }
+
+    async fn complete(
+        &self,
+        params: &Params,
+        system: &str,
+        prompt: &str,
+        sink: &mut StreamSink<'_>,
+    ) -> Result<(String, Option<Usage>), String> {
+        complete_stream(params, system, prompt, sink).await
+    }
+}
+
+/// 获取本地模型服务的模型列表
+#[tauri::command]
+pub async fn local_llm_models(params: Params) -> Result<Vec<String>, String> {
+    models(&params).await
+}
+
+/// 供前端插件调用，from to 为服务语种
+#[tauri::command]
+pub async fn local_llm_translate(
+    params: Params,
+    text: String,
+    from: String,
+    to: String,
+) -> Result<String, String> {
+    LocalLLM.translate(&params, &text, &from, &to).await
+}
+
+/// 修改服务配置后预加载模型
+#[tauri::command]
+pub async fn local_llm_warm_up(params: Params) -> Result<(), String> {
+    warm_up(&params).await
+}
@@ -0,0 +1,276 @@
+mod gemini;
+mod google_free;
+pub mod libre;
+mod local;
+pub mod local_llm;
+mod openai;
+mod orchestrator;
+mod stream;
+
+pub use orchestrator::{
+    cancel, complete, run_cli, translate_text, Completion, TranslateRequest, TranslateResponse,
+};
+pub use stream::{StreamSink, Usage};
+
+use crate::dictionary::DictResult;
+use async_trait::async_trait;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::sync::Arc;
+
+pub type Params = Map<String, Value>;
+
+/// 与前端 string | IDictResult 一致
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum TranslateOutput {
+    Text(String),
+    Dict(Box<DictResult>),
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct Capabilities {
+    pub detect: bool,
+    pub translate: bool,
+    pub dict: bool,
+    /// 文本翻译支持流式输出
+    pub stream: bool,
+    /// 支持自定义提示词，可用于润色、总结等改写模式
+    pub prompt: bool,
+}
+
+/// 翻译服务，name 与前端插件的 name 一致，params 为服务配置中的参数
+#[async_trait]
+pub trait TranslationProvider: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    /// 应用语种与服务语种的对应关系，与前端插件的 languages 一致
+    fn languages(&self) -> &'static [(&'static str, &'static str)];
+
+    fn capabilities(&self) -> Capabilities;
+
+    async fn detect(&self, _params: &Params, _text: &str) -> Result<String, String> {
+        Err("该服务不提供语种识别功能".to_string())
+    }
+
+    async fn translate(
+        &self,
+        _params: &Params,
+        _text: &str,
+        _from: &str,
+        _to: &str,
+    ) -> Result<String, String> {
+        Err("该服务不提供文本翻译功能".to_string())
+    }
+
+    async fn dict(
+        &self,
+        _params: &Params,
+        _text: &str,
+        _from: &str,
+        _to: &str,
+    ) -> Result<DictResult, String> {
+        Err("该服务不提供词典翻译功能".to_string())
+    }
+
+    /// 流式翻译，每收到一段内容输出一次，返回完整结果和token用量，默认等待完整结果后一次输出
+    async fn translate_stream(
+        &self,
+        params: &Params,
+        text: &str,
+        from: &str,
+        to: &str,
+        sink: &mut StreamSink<'_>,
+    ) -> Result<(String, Option<Usage>), String> {
+        let result = self.translate(params, text, from, to).await?;
+        sink.delta(&result);
+        Ok((result, None))
+    }
+
+    /// 使用自定义提示词调用大模型，流式输出结果，system 为空时不使用系统提示词
+    async fn complete(
+        &self,
+        _params: &Params,
+        _system: &str,
+        _prompt: &str,
+        _sink: &mut StreamSink<'_>,
+    ) -> Result<(String, Option<Usage>), String> {
+        Err("该服务不支持自定义提示词".to_string())
+    }
+
+    /// 应用语种转为服务语种
+    fn language(&self, lang: &str) -> Option<&'static str> {
+        self.languages()
+            .iter()
+            .find(|(k, _)| *k == lang)
+            .map(|(_, v)| *v)
+    }
+
+    /// 服务返回的语种转为应用语种，未知时为 en
+    fn app_language(&self, lang: &str) -> &'static str {
+        self.languages()
+            .iter()
+            .find(|(_, v)| *v == lang)
+            .map_or("en", |(k, _)| *k)
+    }
+}
+
+static PROVIDERS: Lazy<Vec<Arc<dyn TranslationProvider>>> = Lazy::new(|| {
+    vec![
+        Arc::new(google_free::GoogleFree),
+        Arc::new(openai::OpenAI),
+        Arc::new(gemini::GeminiPro),
+        Arc::new(local_llm::LocalLLM),
+        Arc::new(libre::LibreTranslate),
+        Arc::new(local::OpenCC),
+        Arc::new(local::ECDict),
+    ]
+});
+
+pub fn provider(name: &str) -> Option<Arc<dyn TranslationProvider>> {
+    PROVIDERS.iter().find(|p| p.name() == name).cloned()
+}
+
+static CLIENT: Lazy<reqwest::Client> = Lazy::new(|| {
+    reqwest::Client::builder()
+        .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36")
+        .build()
+        .unwrap_or_default()
+});
+
+fn client() -> &'static reqwest::Client {
+    &CLIENT
+}
+
+/// 读取响应的JSON，状态码错误时与前端插件的错误信息格式一致
+async fn read_json(res: Result<reqwest::Response, reqwest::Error>) -> Result<Value, String> {
+    let res = res.map_err(|e| e.to_string())?;
+    let status = res.status();
+    let text = res.text().await.map_err(|e| e.to_string())?;
+    if !status.is_success() {
+        return Err(format!(
+            "Http Request Error\nHttp Status: {}\n{}",
+            status.as_u16(),
+            text
+        ));
+    }
+    serde_json::from_str(&text).map_err(|e| format!("{}: {}", e, text))
+}
+
+/// 应用语种在大模型提示词中使用的英文名称
+pub fn language_name(lang: &str) -> &'static str {
+    openai::LANGUAGES
+        .iter()
+        .find(|(k, _)| *k == lang)
+        .map_or("English", |(_, v)| *v)
+}
+
+/// 字符串参数，未配置或为空时使用默认值
+fn param_str<'a>(params: &'a Params, key: &str, default: &'a str) -> &'a str {
+    match params.get(key).and_then(|v| v.as_str()) {
+        Some(s) if !s.trim().is_empty() => s.trim(),
+        _ => default,
+    }
+}
+
+fn param_bool(params: &Params, key: &str, default: bool) -> bool {
+    params.get(key).and_then(|v| v.as_bool()).unwrap_or(default)
+}
+
+/// 数值参数，界面中输入的字符串也可解析
+fn param_num(params: &Params, key: &str) -> Option<f64> {
+    match params.get(key)? {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => s.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+/// 解析JSON格式的提示词，替换其中所有字符串的 $text $from $to $glossary，
+/// 未使用 $glossary 时术语说明追加到包含 $text 的内容后
+fn fill_prompt(
+    prompt: &str,
+    text: &str,
+    from: &str,
+    to: &str,
+    glossary: &str,
+) -> Result<Value, String> {
+    fn fill(value: &mut Value, vars: &[(&str, &str)]) {
+        match value {
+            Value::String(s) => {
+                for (name, v) in vars {
+                    *s = s.replace(name, v);
+                }
+            }
+            Value::Array(list) => list.iter_mut().for_each(|v| fill(v, vars)),
+            Value::Object(map) => map.values_mut().for_each(|v| fill(v, vars)),
+            _ => {}
+        }
+    }
+    fn append_glossary(value: &mut Value) -> bool {
+        match value {
+            Value::String(s) if s.contains("$text") => {
+                s.push_str("\n\n$glossary");
+                true
+            }
+            Value::Array(list) => list.iter_mut().any(append_glossary),
+            Value::Object(map) => map.values_mut().any(append_glossary),
+            _ => false,
+        }
+    }
+    let mut value: Value = serde_json::from_str(prompt).map_err(|_| "Prompt is invalid")?;
+    if !value.is_array() {
+        return Err("Prompt is invalid".to_string());
+    }
+    if !glossary.is_empty() && !prompt.contains("$glossary") {
+        append_glossary(&mut value);
+    }
+    // 原文最后替换，避免原文中的变量名被替换
+    let vars = [
+        ("$from", from),
+        ("$to", to),
+        ("$glossary", glossary),
+        ("$text", text),
+    ];
+    fill(&mut value, &vars);
+    Ok(value)
+}
+
+/// 去掉大模型返回内容首尾的引号
+fn trim_quotes(text: &str) -> String {
+    let text = text.trim();
+    let text = text.strip_prefix('"').unwrap_or(text);
+    let text = text.strip_suffix('"').unwrap_or(text);
+    text.trim().to_string()
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ProviderInfo {
+    pub name: &'static str,
+    pub capabilities: Capabilities,
+}
+
+/// 后端已实现的翻译服务
+#[tauri::command]
+pub fn translate_providers() -> Result<Vec<ProviderInfo>, ()> {
+    Ok(PROVIDERS
+        .iter()
+        .map(|p| ProviderInfo {
+            name: p.name(),
+            capabilities: p.capabilities(),
+        })
+        .collect())
+}
+
+/// 按配置的翻译服务翻译文本，每个服务的结果通过 translator://service-result 事件返回
+#[tauri::command]
+pub async fn translate(request: TranslateRequest) -> Result<TranslateResponse, String> {
+    translate_text(request).await
+}
+
+/// 取消翻译请求，正在进行的请求会被中断
+#[tauri::command]
+pub fn translate_cancel(id: &str) -> Result<bool, ()> {
+    Ok(cancel(id))
+}
@@ -0,0 +1,231 @@
+use super::{
+    client, fill_prompt, param_str, read_json,
+    stream::{read_sse, StreamSink, Usage},
+    trim_quotes, Capabilities, Params, TranslationProvider,
+};
+use async_trait::async_trait;
+use serde_json::{json, Value};
+
+const DEFAULT_URL: &str = "https://api.openai.com/v1/chat/completions";
+const DEFAULT_MODEL: &str = "gpt-3.5-turbo";
+const DEFAULT_PROMPT: &str = r#"[
+	{
+		"role": "system",
+		"content": "You are a professional translation engine, please translate the text into a colloquial, professional, elegant and fluent content, without the style of machine translation. You must only translate the text content, never interpret it."
+	},
+	{
+		"role": "user",
+		"content": "Translate into $to:\n\"\"\"\n$text\n\"\"\""
+	}
+]"#;
+
+/// 大模型服务使用语种的英文名称
+pub(super) const LANGUAGES: &[(&str, &str)] = &[
+    ("auto", "Auto"),
+    ("zh_cn", "Simplified Chinese"),
+    ("zh_tw", "Traditional Chinese"),
+    ("yue", "Cantonese"),
+    ("ja", "Japanese"),
+    ("en", "English"),
+    ("ko", "Korean"),
+    ("fr", "French"),
+    ("es", "Spanish"),
+    ("ru", "Russian"),
+    ("de", "German"),
+    ("it", "Italian"),
+    ("tr", "Turkish"),
+    ("pt_pt", "Portuguese"),
+    ("pt_br", "Brazilian Portuguese"),
+    ("vi", "Vietnamese"),
+    ("id", "Indonesian"),
+    ("th", "Thai"),
+    ("ms", "Malay"),
+    ("ar", "Arabic"),
+    ("hi", "Hindi"),
+    ("mn_mo", "Mongolian"),
+    ("mn_cy", "Mongolian(Cyrillic)"),
+    ("km", "Khmer"),
+    ("nb_no", "Norwegian Bokmål"),
+    ("nn_no", "Norwegian Nynorsk"),
+    ("fa", "Persian"),
+    ("sv", "Swedish"),
+    ("pl", "Polish"),
+    ("nl", "Dutch"),
+];
+
+/// 构造 chat/completions 请求，OpenAI兼容的服务共用
+pub(super) fn chat_request(
+    params: &Params,
+    messages: Value,
+    default_url: &str,
+    default_model: &str,
+    stream: bool,
+) -> reqwest::RequestBuilder {
+    let mut body = json!({
+        "model": param_str(params, "model", default_model),
+        "temperature": 0,
+        "stream": stream,
+        "top_p": 1,
+        "frequency_penalty": 1,
+        "presence_penalty": 1,
+        "messages": messages
+    });
+    if stream {
+        // 最后一个数据块中返回token用量
+        body["stream_options"] = json!({ "include_usage": true });
+    }
+    let mut req = client()
+        .post(param_str(params, "url", default_url))
+        .json(&body);
+    let api_key = param_str(params, "apiKey", "");
+    if !api_key.is_empty() {
+        req = req.bearer_auth(api_key);
+    }
+    req
+}
+
+/// 系统提示词和用户提示词组成的消息，系统提示词为空时省略
+pub(super) fn prompt_messages(system: &str, prompt: &str) -> Value {
+    let mut messages = Vec::new();
+    if !system.trim().is_empty() {
+        messages.push(json!({ "role": "system", "content": system }));
+    }
+    messages.push(json!({ "role": "user", "content": prompt }));
+    Value::Array(messages)
+}
+
+fn parse_usage(data: &Value) -> Option<Usage> {
+    let usage = data.get("usage").filter(|u| u.is_object())?;
+    let get = |key: &str| usage[key].as_u64().unwrap_or_default();
+    Some(Usage {
+        prompt_tokens: get("prompt_tokens"),
+        completion_tokens: get("completion_tokens"),
+        total_tokens: get("total_tokens"),
+    })
+}
+
+pub(super) async fn chat(req: reqwest::RequestBuilder) -> Result<String, String> {
+    let data = read_json(req.send().await).await?;
+    match data["choices"][0]["message"]["content"].as_str() {
+        Some(content) if !content.trim().is_empty() => Ok(trim_quotes(content)),
+        _ => Err(data.to_string()),
+    }
+}
+
+/// 读取 stream: true 的SSE响应，以 [DONE] 结束
+pub(super) async fn chat_stream(
+    req: reqwest::RequestBuilder,
+    sink: &mut StreamSink<'_>,
+) -> Result<(String, Option<Usage>), String> {
+    let mut text = String::new();
+    let mut usage = None;
+    read_sse(req.send().await, sink.timeout, |data| {
+        if data.trim() == "[DONE]" {
+            return Ok(true);
+        }
+        let data: Value = serde_json::from_str(data).map_err(|e| format!("{}: {}", e, data))?;
+        if let Some(error) = data.get("error") {
+            return Err(error.to_string());
+        }
+        if let Some(delta) = data["choices"][0]["delta"]["content"].as_str() {
+            text.push_str(delta);
+            sink.delta(delta);
+        }
+        if let Some(u) = parse_usage(&data) {
+            usage = Some(u);
+        }
+        Ok(false)
+    })
+    .await?;
+    let text = trim_quotes(&text);
+    if text.is_empty() {
+        return Err("翻译结果为空".to_string());
+    }
+    Ok((text, usage))
+}
+
+pub struct OpenAI;
+
+#[async_trait]
+impl TranslationProvider for OpenAI {
+    fn name(&self) -> &'static str {
+        "openai"
+    }
+
+    fn languages(&self) -> &'static [(&'static str, &'static str)] {
+        LANGUAGES
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            translate: true,
+            stream: true,
+            prompt: true,
+            ..Default::default()
+        }
+    }
+
+    async fn translate(
+        &self,
+        params: &Params,
+        text: &str,
+        from: &str,
+        to: &str,
+    ) -> Result<String, String> {
+        if param_str(params, "apiKey", "").is_empty() {
+            return Err("Api Key is required".to_string());
+        }
+        let messages = fill_prompt(
+            param_str(params, "prompt", DEFAULT_PROMPT),
+            text,
+            from,
+            to,
+            param_str(params, "glossary", ""),
+        )?;
+        chat(chat_request(
+            params,
+            messages,
+            DEFAULT_URL,
+            DEFAULT_MODEL,
+            false,
+        ))
+        .await
+    }
+
+    async fn translate_stream(
+        &self,
+        params: &Params,
+        text: &str,
+        from: &str,
+        to: &str,
+        sink: &mut StreamSink<'_>,
+    ) -> Result<(String, Option<Usage>), String> {
+        if param_str(params, "apiKey", "").is_empty() {
+            return Err("Api Key is required".to_string());
+        }
+        let messages = fill_prompt(
+            param_str(params, "prompt", DEFAULT_PROMPT),
+            text,
+            from,
+            to,
+            param_str(params, "glossary", ""),
+        )?;
+        let req = chat_request(params, messages, DEFAULT_URL, DEFAULT_MODEL, true);
+        chat_stream(req, sink).await
+    }
+
+    async fn complete(
+        &self,
+        params: &Params,
+        system: &str,
+        prompt: &str,
+        sink: &mut StreamSink<'_>,
+    ) -> Result<(String, Option<Usage>), String> {
+        if param_str(params, "apiKey", "").is_empty() {
+            return Err("Api Key is required".to_string());
+        }
+        let messages = prompt_messages(system, prompt);
+        let req = chat_request(params, messages, DEFAULT_URL, DEFAULT_MODEL, true);
+        chat_stream(req, sink).await
+    }
+}
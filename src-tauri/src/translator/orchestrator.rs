@@ -0,0 +1,1026 @@
+use super::{
+    provider,
+    stream::{StreamSink, Usage},
+    Params, TranslateOutput, TranslationProvider,
+};
+use crate::{
+    config::{self, get_config_with_default, get_or_bool, get_or_string},
+    dictionary::{DictResult, Pronunciation},
+    global::{APP, BASE_PATH, IDENTIFIER},
+    glossary::{self, Prepared, Violation},
+    lang_detect, lemmatize, phonetic,
+    tm::{self, Match},
+    trans_cache,
+};
+use futures::{
+    future::{self, Either},
+    stream::FuturesUnordered,
+    FutureExt, StreamExt,
+};
+use log::{debug, warn};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    future::Future,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+use tauri::Manager;
+use tokio::sync::watch;
+
+pub const RESULT_EVENT: &str = "translator://service-result";
+pub const DONE_EVENT: &str = "translator://done";
+pub const PARTIAL_EVENT: &str = "translator://partial";
+pub const PARTIAL_DONE_EVENT: &str = "translator://partial-done";
+pub const MEMORY_EVENT: &str = "translator://memory";
+
+const DEFAULT_TIMEOUT: u64 = 5000;
+
+/// 与前端 IServiceConfig 一致
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ServiceConfig {
+    #[serde(default)]
+    id: Option<String>,
+    name: String,
+    #[serde(default)]
+    label: Option<String>,
+    #[serde(default)]
+    enable: bool,
+    #[serde(default)]
+    params: Params,
+    #[serde(default)]
+    timeout: Option<u64>,
+    #[serde(default)]
+    retry: Option<u32>,
+    #[serde(default)]
+    detect_verify: bool,
+    #[serde(default)]
+    trans_verify: bool,
+}
+
+struct Service {
+    key: String,
+    label: String,
+    config: ServiceConfig,
+    provider: Arc<dyn TranslationProvider>,
+    timeout: u64,
+    retry: u32,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TranslateMode {
+    /// 所有服务并发翻译
+    #[default]
+    All,
+    /// 所有服务并发翻译，取最快成功的结果，其余请求取消
+    Race,
+    /// 按顺序翻译，失败时使用下一个服务
+    Fallback,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TranslateRequest {
+    pub text: String,
+    /// 请求标识，事件中原样返回，为空时自动生成
+    #[serde(default)]
+    pub id: String,
+    #[serde(default)]
+    pub from: Option<String>,
+    #[serde(default)]
+    pub to: Option<String>,
+    /// 使用的服务 id 或名称，按顺序作为回退链，为空时使用所有启用的服务
+    #[serde(default)]
+    pub services: Vec<String>,
+    #[serde(default)]
+    pub mode: TranslateMode,
+    #[serde(default)]
+    pub only_dict: Option<bool>,
+    /// 支持流式输出的服务通过 translator://partial 事件推送增量内容，未设置时使用配置 trans_stream
+    #[serde(default)]
+    pub stream: Option<bool>,
+    /// 是否使用翻译缓存，未设置时使用配置 use_cache
+    #[serde(default)]
+    pub cache: Option<bool>,
+}
+
+/// 单个服务的翻译结果，通过 translator://service-result 事件推送
+#[derive(Debug, Clone, Serialize)]
+pub struct ServiceResult {
+    pub id: String,
+    /// 服务配置的 id，未设置时为服务名称
+    pub service: String,
+    pub name: String,
+    pub label: String,
+    pub from: String,
+    pub to: String,
+    pub ok: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<TranslateOutput>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// 译文中未使用术语表指定译法的术语
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub violations: Vec<Violation>,
+    /// 结果来自翻译缓存
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub cached: bool,
+    /// 耗时毫秒数
+    pub elapsed: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TranslateResponse {
+    pub id: String,
+    pub text: String,
+    pub detected: String,
+    /// 翻译记忆中精确及模糊匹配的句段
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub memory: Vec<Match>,
+    pub results: Vec<ServiceResult>,
+}
+
+/// 调用翻译服务前推送的翻译记忆匹配结果
+#[derive(Debug, Clone, Serialize)]
+struct Memory<'a> {
+    id: &'a str,
+    matches: &'a [Match],
+}
+
+/// 流式翻译的增量内容，attempt 变化时说明上一次调用失败后进行了重试
+#[derive(Debug, Clone, Serialize)]
+struct Partial<'a> {
+    id: &'a str,
+    service: &'a str,
+    attempt: u32,
+    delta: &'a str,
+}
+
+/// 流式翻译结束，包含完整结果和token用量
+#[derive(Debug, Clone, Serialize)]
+struct PartialDone<'a> {
+    id: &'a str,
+    service: &'a str,
+    ok: bool,
+    text: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    usage: Option<Usage>,
+    elapsed: u64,
+    cancelled: bool,
+}
+
+// 进行中的翻译请求，用于取消
+static RUNNING: Lazy<Mutex<HashMap<String, watch::Sender<bool>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// 请求结束时移除取消标记
+struct Running<'a>(&'a str);
+
+impl Drop for Running<'_> {
+    fn drop(&mut self) {
+        RUNNING.lock().unwrap().remove(self.0);
+    }
+}
+
+/// 取消翻译请求，返回请求是否存在
+pub fn cancel(id: &str) -> bool {
+    match RUNNING.lock().unwrap().get(id) {
+        Some(tx) => tx.send(true).is_ok(),
+        None => false,
+    }
+}
+
+fn emit<S: Serialize + Clone>(event: &str, payload: S) {
+    if let Some(app) = APP.get() {
+        let _ = app.emit_all(event, payload);
+    }
+}
+
+/// 读取配置中启用的服务
+fn enabled_configs() -> Vec<ServiceConfig> {
+    let list = get_config_with_default("trans_services", Vec::new(), |v| v.as_array().cloned());
+    list.into_iter()
+        .filter_map(|v| serde_json::from_value::<ServiceConfig>(v).ok())
+        .filter(|c| c.enable)
+        .collect()
+}
+
+/// 读取配置中启用的、后端已实现的服务
+fn enabled_services() -> Vec<Service> {
+    let timeout = get_config_with_default("trans_timeout", DEFAULT_TIMEOUT, Value::as_u64);
+    let retry = get_config_with_default("trans_retry_count", 1, Value::as_u64) as u32;
+    enabled_configs()
+        .into_iter()
+        .filter_map(|config| {
+            let provider = provider(&config.name)?;
+            Some(Service {
+                key: config.id.clone().unwrap_or_else(|| config.name.clone()),
+                label: config.label.clone().unwrap_or_else(|| config.name.clone()),
+                timeout: config.timeout.filter(|t| *t > 0).unwrap_or(timeout),
+                retry: config.retry.filter(|r| *r > 0).unwrap_or(retry).max(1),
+                config,
+                provider,
+            })
+        })
+        .collect()
+}
+
+/// 用于翻译的服务，规则与前端 generateTransConfig 一致
+fn load_services(only_dict: bool) -> Vec<Service> {
+    enabled_services()
+        .into_iter()
+        .filter(|s| {
+            let caps = s.provider.capabilities();
+            caps.dict || (!only_dict && s.config.trans_verify)
+        })
+        .collect()
+}
+
+async fn with_timeout<T>(
+    ms: u64,
+    fut: impl Future<Output = Result<T, String>>,
+) -> Result<T, String> {
+    match tokio::time::timeout(Duration::from_millis(ms), fut).await {
+        Ok(res) => res.map_err(|e| format!("服务调用错误：{}", e)),
+        Err(_) => Err(format!("服务调用超时：{}ms", ms)),
+    }
+}
+
+async fn detect_one(service: &Service, text: &str) -> Result<String, String> {
+    let lang = with_timeout(
+        service.timeout,
+        service.provider.detect(&service.config.params, text),
+    )
+    .await?;
+    Ok(service.provider.app_language(&lang).to_string())
+}
+
+/// 按 detect_type 识别语种，策略与前端一致，失败时回退到本地识别
+async fn detect_language(text: &str, services: &[Service]) -> String {
+    let detect_type = get_or_string("detect_type", "concurrent".to_string());
+    let detects: Vec<&Service> = services
+        .iter()
+        .filter(|s| s.provider.capabilities().detect && s.config.detect_verify)
+        .collect();
+    if detect_type == "local" || detects.is_empty() {
+        return lang_detect::detect(text).to_string();
+    }
+    match detect_type.as_str() {
+        "order" => {
+            for service in detects {
+                if let Ok(lang) = detect_one(service, text).await {
+                    return lang;
+                }
+            }
+        }
+        "concurrent" => {
+            let list = detects.iter().map(|s| detect_one(s, text).boxed());
+            if let Ok((lang, _)) = future::select_ok(list).await {
+                return lang;
+            }
+        }
+        "concurrent_most" => {
+            let list = future::join_all(detects.iter().map(|s| detect_one(s, text))).await;
+            let mut count: Vec<(String, usize)> = Vec::new();
+            for lang in list.into_iter().flatten() {
+                match count.iter_mut().find(|(l, _)| *l == lang) {
+                    Some((_, n)) => *n += 1,
+                    None => count.push((lang, 1)),
+                }
+            }
+            // 数量相同时取先识别到的语种
+            if let Some((lang, _)) = count.into_iter().rev().max_by_key(|(_, n)| *n) {
+                return lang;
+            }
+        }
+        name => {
+            if let Some(service) = detects.iter().find(|s| s.config.name == name) {
+                if let Ok(lang) = detect_one(service, text).await {
+                    return lang;
+                }
+            }
+        }
+    }
+    lang_detect::detect(text).to_string()
+}
+
+/// 源语种、目标语种相同时切换到第二目标语种，规则与前端 retryTranslate 一致
+fn resolve_languages(
+    provider: &dyn TranslationProvider,
+    from: &str,
+    to: &str,
+    detected: &str,
+) -> Result<(String, String), String> {
+    let mut from = from.to_string();
+    let mut to = to.to_string();
+    if from == "auto" && provider.language("auto").is_none() {
+        from = detected.to_string();
+    }
+    if from == to || (from == "auto" && to == detected) {
+        let to2 = get_or_string("to2", "en".to_string());
+        if detected != to2 {
+            to = to2;
+        } else {
+            to = provider
+                .languages()
+                .iter()
+                .map(|(k, _)| *k)
+                .find(|k| *k != to && *k != "auto")
+                .ok_or_else(|| {
+                    format!(
+                        "当前设定源语种为: {}，检测语种为: {}, 未找到可用的目标语种",
+                        from, detected
+                    )
+                })?
+                .to_string();
+        }
+    }
+    if provider.language(&from).is_none() {
+        return Err(format!("不支持的源语种: {}", from));
+    }
+    if provider.language(&to).is_none() {
+        return Err(format!("不支持的目标语种: {}", to));
+    }
+    Ok((from, to))
+}
+
+async fn call_dict(
+    service: &Service,
+    word: &str,
+    from: &str,
+    to: &str,
+) -> Result<DictResult, String> {
+    with_timeout(
+        service.timeout,
+        service
+            .provider
+            .dict(&service.config.params, word, from, to),
+    )
+    .await
+}
+
+/// 单个英文单词查询失败时，依次使用还原后的原形重新查询，并补充本地生成的词形变化
+async fn dict_with_lemma(
+    service: &Service,
+    text: &str,
+    from: &str,
+    to: &str,
+) -> Result<DictResult, String> {
+    let err = match call_dict(service, text, from, to).await {
+        Ok(result) => return Ok(result),
+        Err(e) => e,
+    };
+    let word = text.trim();
+    if word.is_empty() || !word.chars().all(|c| c.is_ascii_alphabetic()) {
+        return Err(err);
+    }
+    let mut tried = vec![word.to_lowercase()];
+    for lemma in lemmatize::lemmatize_word(word) {
+        if tried.contains(&lemma.lemma) {
+            continue;
+        }
+        tried.push(lemma.lemma.clone());
+        if let Ok(mut result) = call_dict(service, &lemma.lemma, from, to).await {
+            if result.wfs.is_empty() {
+                result.wfs = lemmatize::inflect_by_traits(
+                    &lemma.lemma,
+                    result.explanations.iter().map(|e| e.trait_.as_str()),
+                );
+            }
+            return Ok(result);
+        }
+    }
+    Err(err)
+}
+
+/// 词典结果没有音标时，使用本地发音引擎补充英式、美式音标
+fn with_phonetic(mut result: DictResult) -> DictResult {
+    let text = result.text.trim();
+    if result.phonetic.is_some()
+        || result.pronunciations.iter().any(|p| !p.symbol.is_empty())
+        || !text.starts_with(|c: char| c.is_ascii_alphabetic())
+        || !text
+            .chars()
+            .all(|c| c.is_ascii_alphabetic() || matches!(c, '\'' | ' ' | '-'))
+    {
+        return result;
+    }
+    let phonetic = phonetic::phonetic_text(text);
+    if phonetic.us.is_empty() {
+        return result;
+    }
+    for (region, symbol) in [("美", phonetic.us), ("英", phonetic.uk)] {
+        result.pronunciations.push(Pronunciation {
+            region: region.to_string(),
+            symbol,
+            voice: None,
+        });
+    }
+    result
+}
+
+/// 有词典功能时优先查词，失败后回退到文本翻译，与前端 textConvert 一致。
+/// 文本翻译时使用术语表处理后的文本，大模型服务通过参数 glossary 注入术语说明
+async fn convert(
+    job: &Job<'_>,
+    service: &Service,
+    prepared: &Prepared,
+    from: &str,
+    to: &str,
+    attempt: u32,
+) -> Result<(TranslateOutput, Option<Usage>, Vec<Violation>), String> {
+    let caps = service.provider.capabilities();
+    let text = job.text;
+    if caps.dict {
+        match dict_with_lemma(service, text, from, to).await {
+            Ok(result) => {
+                let result = TranslateOutput::Dict(Box::new(with_phonetic(result)));
+                return Ok((result, None, Vec::new()));
+            }
+            Err(e) if job.only_dict || !caps.translate => {
+                return Err(format!("词典翻译失败：{}", e))
+            }
+            Err(_) => {}
+        }
+    } else if job.only_dict {
+        return Err("该服务不提供词典翻译功能".to_string());
+    }
+    let text = prepared.text.as_str();
+    let params = if caps.prompt && !prepared.prompt.is_empty() {
+        let mut params = service.config.params.clone();
+        params.insert("glossary".to_string(), Value::from(prepared.prompt.clone()));
+        Cow::Owned(params)
+    } else {
+        Cow::Borrowed(&service.config.params)
+    };
+    let params = params.as_ref();
+    let (result, usage) = if job.stream && caps.stream {
+        let mut on_delta = |delta: &str| {
+            emit(
+                PARTIAL_EVENT,
+                Partial {
+                    id: job.id,
+                    service: &service.key,
+                    attempt,
+                    delta,
+                },
+            )
+        };
+        let mut sink = StreamSink::new(service.timeout, &mut on_delta);
+        service
+            .provider
+            .translate_stream(params, text, from, to, &mut sink)
+            .await
+    } else {
+        with_timeout(
+            service.timeout,
+            service.provider.translate(params, text, from, to),
+        )
+        .await
+        .map(|r| (r, None))
+    }
+    .map_err(|e| format!("文本翻译失败：{}", e))?;
+    if result.is_empty() {
+        return Err("翻译结果为空".to_string());
+    }
+    let checked = glossary::restore(prepared, &result);
+    Ok((
+        TranslateOutput::Text(checked.text),
+        usage,
+        checked.violations,
+    ))
+}
+
+/// 等待取消信号，发送端关闭时不再返回
+async fn cancelled(mut rx: watch::Receiver<bool>) {
+    while !*rx.borrow() {
+        if rx.changed().await.is_err() {
+            future::pending::<()>().await;
+        }
+    }
+}
+
+/// 一次翻译请求中所有服务共用的参数
+struct Job<'a> {
+    id: &'a str,
+    text: &'a str,
+    from: &'a str,
+    to: &'a str,
+    detected: &'a str,
+    only_dict: bool,
+    stream: bool,
+    cache: bool,
+    cancel: watch::Receiver<bool>,
+}
+
+impl Job<'_> {
+    /// 调用单个服务，按重试次数重试，完成后推送结果事件
+    async fn run(&self, service: &Service) -> ServiceResult {
+        let start = Instant::now();
+        let mut result = ServiceResult {
+            id: self.id.to_string(),
+            service: service.key.clone(),
+            name: service.config.name.clone(),
+            label: service.label.clone(),
+            from: self.from.to_string(),
+            to: self.to.to_string(),
+            ok: false,
+            data: None,
+            error: None,
+            violations: Vec::new(),
+            cached: false,
+            elapsed: 0,
+        };
+        let mut usage = None;
+        let mut is_cancelled = false;
+        match resolve_languages(&*service.provider, self.from, self.to, self.detected) {
+            Err(e) => result.error = Some(e),
+            Ok((from, to)) => {
+                let from_code = service.provider.language(&from).unwrap_or_default();
+                let to_code = service.provider.language(&to).unwrap_or_default();
+                let source_lang = if from == "auto" { self.detected } else { &from };
+                let prepared =
+                    glossary::prepare_for(&service.config.name, self.text, source_lang, &to);
+                let fingerprint = trans_cache::fingerprint(
+                    &service.config.name,
+                    &Value::Object(service.config.params.clone()),
+                );
+                let terms_key = prepared.terms_key();
+                let cached = if self.cache {
+                    trans_cache::get(
+                        &service.key,
+                        &fingerprint,
+                        &terms_key,
+                        self.text,
+                        from_code,
+                        to_code,
+                    )
+                    .and_then(|v| serde_json::from_value::<TranslateOutput>(v).ok())
+                } else {
+                    None
+                };
+                if let Some(data) = cached {
+                    // 缓存中是还原后的译文，重新检查术语的译法
+                    if let TranslateOutput::Text(text) = &data {
+                        result.violations = glossary::restore(&prepared, text).violations;
+                    }
+                    result.ok = true;
+                    result.cached = true;
+                    result.data = Some(data);
+                }
+                let mut errors = Vec::new();
+                for i in (1..=service.retry).filter(|_| !result.ok) {
+                    // 取消时丢弃未完成的请求，同时中断HTTP连接
+                    let task = convert(self, service, &prepared, from_code, to_code, i);
+                    let res = match future::select(
+                        Box::pin(task),
+                        Box::pin(cancelled(self.cancel.clone())),
+                    )
+                    .await
+                    {
+                        Either::Left((res, _)) => res,
+                        Either::Right(_) => {
+                            is_cancelled = true;
+                            Err("翻译已取消".to_string())
+                        }
+                    };
+                    match res {
+                        Ok((data, u, violations)) => {
+                            if self.cache {
+                                if let Ok(value) = serde_json::to_value(&data) {
+                                    trans_cache::put(
+                                        &service.key,
+                                        &fingerprint,
+                                        &terms_key,
+                                        self.text,
+                                        from_code,
+                                        to_code,
+                                        &value,
+                                    );
+                                }
+                            }
+                            result.ok = true;
+                            result.data = Some(data);
+                            result.violations = violations;
+                            usage = u;
+                            break;
+                        }
+                        Err(e) => errors.push(format!("第{}次调用发生错误: {}", i, e)),
+                    }
+                    if is_cancelled {
+                        break;
+                    }
+                }
+                if !result.ok {
+                    warn!("translate {} error: {:?}", service.key, errors);
+                    result.error = Some(errors.join("\n"));
+                }
+                result.from = from;
+                result.to = to;
+            }
+        }
+        result.elapsed = start.elapsed().as_millis() as u64;
+        if self.stream && service.provider.capabilities().stream {
+            let text = match &result.data {
+                Some(TranslateOutput::Text(text)) => text.clone(),
+                _ => String::new(),
+            };
+            emit(
+                PARTIAL_DONE_EVENT,
+                PartialDone {
+                    id: self.id,
+                    service: &service.key,
+                    ok: result.ok,
+                    text,
+                    usage,
+                    elapsed: result.elapsed,
+                    cancelled: is_cancelled,
+                },
+            );
+        }
+        emit(RESULT_EVENT, result.clone());
+        result
+    }
+}
+
+/// 明确指定、但后端尚未实现的服务，translate 中作为失败的结果返回；未指定服务时跳过这些服务
+fn unported_services(keys: &[String]) -> Vec<ServiceConfig> {
+    enabled_configs()
+        .into_iter()
+        .filter(|c| c.trans_verify && provider(&c.name).is_none())
+        .filter(|c| {
+            keys.iter()
+                .any(|k| c.id.as_ref() == Some(k) || c.name == *k)
+        })
+        .collect()
+}
+
+/// 后端翻译入口，命令行、快捷键等不依赖窗口的场景可直接调用
+pub async fn translate_text(request: TranslateRequest) -> Result<TranslateResponse, String> {
+    let text = request.text.trim();
+    if text.is_empty() {
+        return Err("翻译内容为空".to_string());
+    }
+    let id = if request.id.is_empty() {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis().to_string())
+            .unwrap_or_default()
+    } else {
+        request.id.clone()
+    };
+    let only_dict = request
+        .only_dict
+        .unwrap_or_else(|| get_or_bool("only_dict", false));
+    let all = load_services(only_dict);
+    let services: Vec<&Service> = if request.services.is_empty() {
+        all.iter().collect()
+    } else {
+        request
+            .services
+            .iter()
+            .filter_map(|k| all.iter().find(|s| s.key == *k || s.config.name == *k))
+            .collect()
+    };
+    let unported = unported_services(&request.services);
+    if services.is_empty() && unported.is_empty() {
+        return Err("没有可用的翻译服务".to_string());
+    }
+    let from = request
+        .from
+        .filter(|s| !s.is_empty())
+        .unwrap_or_else(|| "auto".to_string());
+    let to = request
+        .to
+        .filter(|s| !s.is_empty())
+        .unwrap_or_else(|| get_or_string("to", "zh_cn".to_string()));
+    let stream = request
+        .stream
+        .unwrap_or_else(|| get_or_bool("trans_stream", false));
+    let cache = request.cache.unwrap_or_else(trans_cache::enabled);
+    let (tx, cancel) = watch::channel(false);
+    RUNNING.lock().unwrap().insert(id.clone(), tx);
+    let _guard = Running(&id);
+    let detected = if from == "auto" {
+        detect_language(text, &all).await
+    } else {
+        from.clone()
+    };
+    debug!("translate {} {} -> {} ({:?})", id, from, to, request.mode);
+    // 先查找翻译记忆，命中的结果在调用翻译服务前推送
+    let memory_to = if detected == to {
+        get_or_string("to2", "en".to_string())
+    } else {
+        to.clone()
+    };
+    let memory = tm::lookup_with_config(text, &detected, &memory_to);
+    if !memory.is_empty() {
+        emit(
+            MEMORY_EVENT,
+            Memory {
+                id: &id,
+                matches: &memory,
+            },
+        );
+    }
+
+    let job = Job {
+        id: &id,
+        text,
+        from: &from,
+        to: &to,
+        detected: &detected,
+        only_dict,
+        stream,
+        cache,
+        cancel,
+    };
+    let mut results = Vec::new();
+    for config in unported {
+        let result = ServiceResult {
+            id: id.clone(),
+            service: config.id.clone().unwrap_or_else(|| config.name.clone()),
+            label: config.label.clone().unwrap_or_else(|| config.name.clone()),
+            name: config.name,
+            from: from.clone(),
+            to: to.clone(),
+            ok: false,
+            data: None,
+            error: Some("该服务暂不支持后端翻译".to_string()),
+            violations: Vec::new(),
+            cached: false,
+            elapsed: 0,
+        };
+        emit(RESULT_EVENT, result.clone());
+        results.push(result);
+    }
+    match request.mode {
+        TranslateMode::All | TranslateMode::Race => {
+            let mut pending: FuturesUnordered<_> = services.iter().map(|s| job.run(s)).collect();
+            while let Some(result) = pending.next().await {
+                let ok = result.ok;
+                results.push(result);
+                // 竞速模式下丢弃未完成的请求
+                if ok && request.mode == TranslateMode::Race {
+                    break;
+                }
+            }
+        }
+        TranslateMode::Fallback => {
+            for service in &services {
+                let result = job.run(service).await;
+                let ok = result.ok;
+                results.push(result);
+                if ok || *job.cancel.borrow() {
+                    break;
+                }
+            }
+        }
+    }
+    let order: HashMap<&str, usize> = services
+        .iter()
+        .enumerate()
+        .map(|(i, s)| (s.key.as_str(), i))
+        .collect();
+    results.sort_by_key(|r| order.get(r.service.as_str()).copied().unwrap_or(usize::MAX));
+
+    let response = TranslateResponse {
+        id: id.clone(),
+        text: text.to_string(),
+        detected,
+        memory,
+        results,
+    };
+    emit(DONE_EVENT, response.clone());
+    Ok(response)
+}
+
+/// 自定义提示词的调用结果
+#[derive(Debug, Clone, Serialize)]
+pub struct Completion {
+    pub id: String,
+    pub service: String,
+    pub label: String,
+    pub text: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub usage: Option<Usage>,
+    pub elapsed: u64,
+}
+
+/// 使用自定义提示词调用大模型服务，service 为服务 id 或名称，为空时使用首个支持的服务。
+/// 增量内容与流式翻译一样通过 translator://partial 事件推送，可通过 cancel 取消
+pub async fn complete(
+    id: &str,
+    service: &str,
+    system: &str,
+    prompt: &str,
+) -> Result<Completion, String> {
+    let services = enabled_services();
+    let service = services
+        .iter()
+        .filter(|s| s.provider.capabilities().prompt)
+        .find(|s| service.is_empty() || s.key == service || s.config.name == service)
+        .ok_or_else(|| {
+            if service.is_empty() {
+                "没有可用的大模型服务".to_string()
+            } else {
+                format!("服务 {} 未启用或不支持自定义提示词", service)
+            }
+        })?;
+    let (tx, cancel) = watch::channel(false);
+    RUNNING.lock().unwrap().insert(id.to_string(), tx);
+    let _guard = Running(id);
+
+    let start = Instant::now();
+    let mut on_delta = |delta: &str| {
+        emit(
+            PARTIAL_EVENT,
+            Partial {
+                id,
+                service: &service.key,
+                attempt: 1,
+                delta,
+            },
+        )
+    };
+    let mut sink = StreamSink::new(service.timeout, &mut on_delta);
+    let task = service
+        .provider
+        .complete(&service.config.params, system, prompt, &mut sink);
+    let (result, is_cancelled) =
+        match future::select(Box::pin(task), Box::pin(cancelled(cancel))).await {
+            Either::Left((res, _)) => (res, false),
+            Either::Right(_) => (Err("已取消".to_string()), true),
+        };
+    let elapsed = start.elapsed().as_millis() as u64;
+    let (text, usage) = match &result {
+        Ok((text, usage)) => (text.clone(), usage.clone()),
+        Err(_) => (String::new(), None),
+    };
+    emit(
+        PARTIAL_DONE_EVENT,
+        PartialDone {
+            id,
+            service: &service.key,
+            ok: result.is_ok(),
+            text: text.clone(),
+            usage: usage.clone(),
+            elapsed,
+            cancelled: is_cancelled,
+        },
+    );
+    result?;
+    Ok(Completion {
+        id: id.to_string(),
+        service: service.key.clone(),
+        label: service.label.clone(),
+        text,
+        usage,
+        elapsed,
+    })
+}
+
+const CLI_USAGE: &str = "用法：tosa translate [选项] <文本>，未指定文本时从标准输入读取
+  --from <语种>                   源语种，默认自动识别
+  --to <语种>                     目标语种，默认使用配置
+  --service <id或名称>            使用的服务，可多次指定，默认使用所有启用的服务
+  --mode <all|race|fallback>      多个服务的调用方式，默认 all
+  --dict                          只使用词典翻译
+只有设置中标记为「后台翻译」的服务支持命令行翻译";
+
+fn parse_cli(args: &[String]) -> Result<TranslateRequest, String> {
+    let mut request = TranslateRequest {
+        text: String::new(),
+        id: String::new(),
+        from: None,
+        to: None,
+        services: Vec::new(),
+        mode: TranslateMode::All,
+        only_dict: None,
+        stream: Some(false),
+        cache: None,
+    };
+    let mut words = Vec::new();
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        if arg == "--dict" {
+            request.only_dict = Some(true);
+            continue;
+        }
+        if !arg.starts_with("--") {
+            words.push(arg.as_str());
+            continue;
+        }
+        let mut value = || {
+            iter.next()
+                .cloned()
+                .ok_or_else(|| format!("缺少参数 {} 的值", arg))
+        };
+        match arg.as_str() {
+            "--from" => request.from = Some(value()?),
+            "--to" => request.to = Some(value()?),
+            "--service" => request.services.push(value()?),
+            "--mode" => {
+                let mode = value()?;
+                request.mode = serde_json::from_value(Value::String(mode.clone()))
+                    .map_err(|_| format!("不支持的调用方式：{}", mode))?;
+            }
+            _ => return Err(format!("未知参数：{}", arg)),
+        }
+    }
+    request.text = words.join(" ");
+    if request.text.trim().is_empty() {
+        std::io::Read::read_to_string(&mut std::io::stdin(), &mut request.text)
+            .map_err(|e| format!("读取标准输入失败：{}", e))?;
+    }
+    Ok(request)
+}
+
+fn format_output(output: &TranslateOutput) -> String {
+    match output {
+        TranslateOutput::Text(text) => text.clone(),
+        TranslateOutput::Dict(dict) => dict
+            .explanations
+            .iter()
+            .map(|e| {
+                format!("{} {}", e.trait_, e.explains.join("；"))
+                    .trim()
+                    .to_string()
+            })
+            .collect::<Vec<String>>()
+            .join("\n"),
+    }
+}
+
+/// 命令行翻译：tosa translate [选项] <文本>，每个服务的结果输出一段，全部失败时返回 1
+pub fn run_cli(args: &[String]) -> i32 {
+    #[cfg(target_os = "windows")]
+    crate::export::attach_console();
+    let request = match parse_cli(args) {
+        Ok(request) => request,
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, CLI_USAGE);
+            return 2;
+        }
+    };
+    BASE_PATH.get_or_init(|| dirs::config_dir().unwrap().join(IDENTIFIER));
+    config::init_config();
+    let response = match tauri::async_runtime::block_on(translate_text(request)) {
+        Ok(response) => response,
+        Err(e) => {
+            eprintln!("翻译失败：{}", e);
+            return 1;
+        }
+    };
+    let mut ok = false;
+    for result in &response.results {
+        match (&result.data, &result.error) {
+            (Some(data), _) if result.ok => {
+                ok = true;
+                println!("[{}]\n{}\n", result.label, format_output(data));
+            }
+            (_, error) => eprintln!(
+                "[{}] 翻译失败：{}\n",
+                result.label,
+                error.as_deref().unwrap_or_default()
+            ),
+        }
+    }
+    if ok {
+        0
+    } else {
+        1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cli_arguments() {
+        let args: Vec<String> = [
+            "--to",
+            "en",
+            "--service",
+            "OpenAI",
+            "--mode",
+            "race",
+            "你好",
+            "世界",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+        let request = parse_cli(&args).unwrap();
+        assert_eq!(request.text, "你好 世界");
+        assert_eq!(request.to.as_deref(), Some("en"));
+        assert_eq!(request.services, ["OpenAI"]);
+        assert_eq!(request.mode, TranslateMode::Race);
+        assert!(parse_cli(&["--mode".to_string(), "any".to_string()]).is_err());
+    }
+}
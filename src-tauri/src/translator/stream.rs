@@ -0,0 +1,316 @@
+use futures::StreamExt;
+use serde::Serialize;
+use serde_json::Value;
+use std::time::Duration;
+
+/// 大模型返回的token用量，服务不返回时为空
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Usage {
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+    pub total_tokens: u64,
+}
+
+/// 流式翻译的输出，timeout 为两段数据之间的最长等待时间
+pub struct StreamSink<'a> {
+    pub timeout: u64,
+    on_delta: &'a mut (dyn FnMut(&str) + Send),
+}
+
+impl<'a> StreamSink<'a> {
+    pub fn new(timeout: u64, on_delta: &'a mut (dyn FnMut(&str) + Send)) -> Self {
+        StreamSink { timeout, on_delta }
+    }
+
+    pub fn delta(&mut self, text: &str) {
+        if !text.is_empty() {
+            (self.on_delta)(text);
+        }
+    }
+}
+
+/// SSE解析，按字节缓存，避免多字节字符被拆分到两个数据块中
+#[derive(Default)]
+struct SseParser {
+    buffer: Vec<u8>,
+    data: Vec<String>,
+}
+
+impl SseParser {
+    /// 返回已完整接收的事件的 data 内容，多行 data 以换行连接
+    fn push(&mut self, chunk: &[u8]) -> Vec<String> {
+        self.buffer.extend_from_slice(chunk);
+        let mut events = Vec::new();
+        while let Some(pos) = self.buffer.iter().position(|b| *b == b'\n') {
+            let line: Vec<u8> = self.buffer.drain(..=pos).collect();
+            let line = String::from_utf8_lossy(&line);
+            let line = line.trim_end_matches(['\n', '\r']);
+            if line.is_empty() {
+                if !self.data.is_empty() {
+                    events.push(self.data.join("\n"));
+                    self.data.clear();
+                }
+                continue;
+            }
+            // 以冒号开头的是注释，常用于保持连接
+            if line.starts_with(':') {
+                continue;
+            }
+            let (field, value) = match line.split_once(':') {
+                Some((f, v)) => (f, v.strip_prefix(' ').unwrap_or(v)),
+                None => (line, ""),
+            };
+            if field == "data" {
+                self.data.push(value.to_string());
+            }
+        }
+        events
+    }
+
+    /// 连接结束时未以空行结尾的事件
+    fn finish(&mut self) -> Option<String> {
+        self.push(b"\n\n").pop()
+    }
+}
+
+/// 逐块读取响应，on_chunk 返回 true 时提前结束并返回 true，超时或出错时中断连接
+async fn read_chunks(
+    res: Result<reqwest::Response, reqwest::Error>,
+    timeout: u64,
+    mut on_chunk: impl FnMut(&[u8]) -> Result<bool, String>,
+) -> Result<bool, String> {
+    let res = res.map_err(|e| e.to_string())?;
+    let status = res.status();
+    if !status.is_success() {
+        let text = res.text().await.unwrap_or_default();
+        return Err(format!(
+            "Http Request Error\nHttp Status: {}\n{}",
+            status.as_u16(),
+            text
+        ));
+    }
+    let mut stream = res.bytes_stream();
+    loop {
+        let chunk = match tokio::time::timeout(Duration::from_millis(timeout), stream.next()).await
+        {
+            Ok(Some(chunk)) => chunk.map_err(|e| e.to_string())?,
+            Ok(None) => return Ok(false),
+            Err(_) => return Err(format!("服务调用超时：{}ms", timeout)),
+        };
+        if on_chunk(&chunk)? {
+            return Ok(true);
+        }
+    }
+}
+
+/// 读取SSE响应，on_event 返回 true 时结束读取
+pub async fn read_sse(
+    res: Result<reqwest::Response, reqwest::Error>,
+    timeout: u64,
+    mut on_event: impl FnMut(&str) -> Result<bool, String>,
+) -> Result<(), String> {
+    let mut parser = SseParser::default();
+    let stopped = read_chunks(res, timeout, |chunk| {
+        for data in parser.push(chunk) {
+            if on_event(&data)? {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    })
+    .await?;
+    if !stopped {
+        if let Some(data) = parser.finish() {
+            on_event(&data)?;
+        }
+    }
+    Ok(())
+}
+
+/// 读取每行一个JSON的响应（Ollama 的流式输出），on_line 返回 true 时结束读取
+pub async fn read_ndjson(
+    res: Result<reqwest::Response, reqwest::Error>,
+    timeout: u64,
+    mut on_line: impl FnMut(&Value) -> Result<bool, String>,
+) -> Result<(), String> {
+    let mut buffer = Vec::new();
+    let mut parse = |line: &[u8]| -> Result<bool, String> {
+        let line = String::from_utf8_lossy(line);
+        let line = line.trim();
+        if line.is_empty() {
+            return Ok(false);
+        }
+        let value: Value = serde_json::from_str(line).map_err(|e| format!("{}: {}", e, line))?;
+        on_line(&value)
+    };
+    let stopped = read_chunks(res, timeout, |chunk| {
+        buffer.extend_from_slice(chunk);
+        while let Some(pos) = buffer.iter().position(|b| *b == b'\n') {
+            let line: Vec<u8> = buffer.drain(..=pos).collect();
+            if parse(&line)? {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    })
+    .await?;
+    if !stopped {
+        parse(&buffer)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::client, *};
+    use std::{
+        io::{Read, Write},
+        net::TcpListener,
+        thread::{self, JoinHandle},
+    };
+
+    /// 本地SSE服务，按块写入响应，每块之间暂停以便客户端分多次收到；
+    /// 写完后 hold 为 true 时保持连接并持续写入注释，返回客户端断开前是否已写完
+    fn serve(chunks: Vec<&'static [u8]>, hold: bool) -> (String, JoinHandle<bool>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        let handle = thread::spawn(move || {
+            let (mut socket, _) = listener.accept().unwrap();
+            // 读完请求，避免关闭连接时未读的数据导致连接被重置
+            let mut request = Vec::new();
+            let mut buf = [0u8; 1024];
+            loop {
+                let n = socket.read(&mut buf).unwrap();
+                request.extend_from_slice(&buf[..n]);
+                let text = String::from_utf8_lossy(&request).to_string();
+                if let Some(pos) = text.find("\r\n\r\n") {
+                    let length = text
+                        .lines()
+                        .find_map(|l| {
+                            l.to_lowercase()
+                                .strip_prefix("content-length:")
+                                .map(|v| v.trim().parse::<usize>().unwrap())
+                        })
+                        .unwrap_or(0);
+                    if request.len() >= pos + 4 + length {
+                        break;
+                    }
+                }
+                if n == 0 {
+                    break;
+                }
+            }
+            let head =
+                b"HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nConnection: close\r\n\r\n";
+            let mut write = |data: &[u8]| {
+                thread::sleep(Duration::from_millis(20));
+                socket.write_all(data).and_then(|_| socket.flush()).is_ok()
+            };
+            if !write(head) || !chunks.into_iter().all(&mut write) {
+                return false;
+            }
+            // 客户端取消后连接关闭，写入失败
+            if hold {
+                for _ in 0..250 {
+                    if !write(b": ping\n\n") {
+                        return false;
+                    }
+                }
+            }
+            true
+        });
+        (url, handle)
+    }
+
+    fn collect(chunks: &[&[u8]]) -> Vec<String> {
+        let mut parser = SseParser::default();
+        let mut events: Vec<String> = chunks.iter().flat_map(|c| parser.push(c)).collect();
+        events.extend(parser.finish());
+        events
+    }
+
+    #[test]
+    fn sse_parser_split_chunks() {
+        let data = "data: {\"t\":\"你好\"}\r\n\r\n: keep-alive\n\nevent: message\ndata: a\ndata: b\n\ndata:[DONE]";
+        let whole = collect(&[data.as_bytes()]);
+        assert_eq!(whole, vec!["{\"t\":\"你好\"}", "a\nb", "[DONE]"]);
+        // 按字节拆分，多字节字符被拆到不同的数据块
+        let bytes: Vec<&[u8]> = data.as_bytes().chunks(1).collect();
+        assert_eq!(collect(&bytes), whole);
+        let bytes: Vec<&[u8]> = data.as_bytes().chunks(7).collect();
+        assert_eq!(collect(&bytes), whole);
+    }
+
+    #[tokio::test]
+    async fn read_sse_stops_at_done() {
+        let (url, server) = serve(
+            vec![
+                b"data: {\"choices\":[{\"delta\":{\"content\":\"\xe4\xbd",
+                b"\xa0\"}}]}\n",
+                b"\ndata: {\"choices\":[{\"delta\":{\"content\":\"\xe5\xa5\xbd\"}}]}\n\n",
+                b"data: {\"choices\":[],\"usage\":{\"prompt_tokens\":3,\"completion_tokens\":2,\"total_tokens\":5}}\n\n",
+                b"data: [DONE]\n\n",
+                b"data: not json\n\n",
+            ],
+            false,
+        );
+        let mut deltas = Vec::new();
+        let mut on_delta = |d: &str| deltas.push(d.to_string());
+        let mut sink = StreamSink::new(2000, &mut on_delta);
+        let (text, usage) = super::super::openai::chat_stream(client().post(&url), &mut sink)
+            .await
+            .unwrap();
+        assert_eq!(text, "你好");
+        assert_eq!(deltas, vec!["你", "好"]);
+        assert_eq!(usage.unwrap().total_tokens, 5);
+        assert!(server.join().unwrap());
+    }
+
+    #[tokio::test]
+    async fn read_sse_gemini_chunks() {
+        let (url, server) = serve(
+            vec![
+                b"data: {\"candidates\":[{\"content\":{\"parts\":[{\"text\":\"Hel\"}]}}],",
+                b"\"usageMetadata\":{\"promptTokenCount\":4,\"candidatesTokenCount\":1,\"totalTokenCount\":5}}\r\n\r\n",
+                b"data: {\"candidates\":[{\"content\":{\"parts\":[{\"text\":\"lo\"},{\"text\":\"!\"}]}}],\"usageMetadata\":{\"promptTokenCount\":4,\"candidatesTokenCount\":3,\"totalTokenCount\":7}}",
+            ],
+            false,
+        );
+        let mut deltas = Vec::new();
+        let mut on_delta = |d: &str| deltas.push(d.to_string());
+        let mut sink = StreamSink::new(2000, &mut on_delta);
+        // 最后一个事件没有以空行结尾，连接关闭时仍然输出
+        let (text, usage) = super::super::gemini::generate_stream(client().post(&url), &mut sink)
+            .await
+            .unwrap();
+        assert_eq!(text, "Hello!");
+        assert_eq!(deltas, vec!["Hel", "lo!"]);
+        assert_eq!(usage.unwrap().completion_tokens, 3);
+        assert!(server.join().unwrap());
+    }
+
+    #[tokio::test]
+    async fn read_sse_cancel_closes_connection() {
+        let (url, server) = serve(vec![b"data: first\n\n"], true);
+        let mut events = Vec::new();
+        let read = read_sse(client().post(&url).send().await, 2000, |data| {
+            events.push(data.to_string());
+            Ok(false)
+        });
+        // 与编排器一样，取消时丢弃未完成的读取
+        let cancelled = tokio::time::timeout(Duration::from_millis(300), read).await;
+        assert!(cancelled.is_err());
+        assert_eq!(events, vec!["first"]);
+        assert!(!tokio::task::spawn_blocking(move || server.join().unwrap())
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn read_sse_timeout() {
+        let (url, server) = serve(vec![b"data: first\n\n"], true);
+        let res = read_sse(client().post(&url).send().await, 10, |_| Ok(false)).await;
+        assert_eq!(res, Err("服务调用超时：10ms".to_string()));
+        drop(server);
+    }
+}
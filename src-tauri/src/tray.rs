@@ -1,9 +1,23 @@
-use tauri::{AppHandle, CustomMenuItem, SystemTrayMenu, SystemTrayMenuItem};
+use tauri::{AppHandle, CustomMenuItem, SystemTrayMenu, SystemTrayMenuItem, SystemTraySubmenu};
 
-use crate::global::*;
+use crate::{
+    global::*,
+    prompt::{presets, PROMPT_PREFIX},
+};
 
 pub fn generate_tray(app_handle: AppHandle) {
     let tray_handle = app_handle.tray_handle();
+    // 对选中的文本执行提示词改写
+    let prompt_menu =
+        presets()
+            .into_iter()
+            .filter(|p| p.tray)
+            .fold(SystemTrayMenu::new(), |menu, p| {
+                menu.add_item(CustomMenuItem::new(
+                    format!("{}{}", PROMPT_PREFIX, p.id),
+                    p.name,
+                ))
+            });
     let tray_menu = SystemTrayMenu::new()
         .add_item(CustomMenuItem::new(
             SHOW_TRANSLATOR.to_string(),
@@ -17,6 +31,7 @@ pub fn generate_tray(app_handle: AppHandle) {
             SCREENSHOT_RECOGNIZER.to_string(),
             "图片识别",
         ))
+        .add_submenu(SystemTraySubmenu::new("文本改写", prompt_menu))
         .add_native_item(SystemTrayMenuItem::Separator)
         .add_item(CustomMenuItem::new("setting".to_string(), "设置"))
         .add_native_item(SystemTrayMenuItem::Separator)
@@ -0,0 +1,273 @@
+use crate::{
+    global::BASE_PATH,
+    lemmatize,
+    tokenize::{self, TokenKind},
+};
+use flate2::read::GzDecoder;
+use log::warn;
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    fs,
+    io::Read,
+    path::PathBuf,
+};
+
+pub static VOCAB_DIR_NAME: &str = "vocab";
+
+// 内置词表由 data/vocab.py 生成，每行格式为 单词\tCEFR等级\t标签（逗号分隔，如 cet4,ox3000），用户词表格式相同
+static BUNDLED: &[u8] = include_bytes!("../data/vocab.tsv.gz");
+
+// 英语每分钟阅读词数、中日韩每分钟阅读字数，用于估算阅读时间
+const WORDS_PER_MINUTE: f64 = 238.0;
+const CJK_CHARS_PER_MINUTE: f64 = 300.0;
+
+#[derive(Debug, Clone, Default)]
+struct Entry {
+    level: Option<String>,
+    tags: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TaggedWord {
+    pub text: String,
+    /// 开始字节位置
+    pub start: usize,
+    /// 结束字节位置（不包含）
+    pub end: usize,
+    /// 开始字符位置
+    pub char_start: usize,
+    /// 结束字符位置（不包含）
+    pub char_end: usize,
+    pub lemma: String,
+    /// CEFR等级 A1~C2，词表中不存在时为None
+    pub level: Option<String>,
+    /// 所属词表，如 cet4、ox3000
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct TextStats {
+    pub word_count: usize,
+    pub unique_lemmas: usize,
+    pub sentence_count: usize,
+    pub syllable_count: usize,
+    /// Flesch易读性分数，越高越容易，没有英文单词时为None
+    pub flesch: Option<f64>,
+    /// 预计阅读时间（秒）
+    pub reading_seconds: u64,
+    /// 中日韩字符总数
+    pub cjk_chars: usize,
+    pub han_chars: usize,
+    pub kana_chars: usize,
+    pub hangul_chars: usize,
+    /// 各等级单词数，未收录的单词计入 unknown
+    pub levels: BTreeMap<String, usize>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct VocabLevels {
+    pub words: Vec<TaggedWord>,
+    pub stats: TextStats,
+}
+
+fn parse_into(content: &str, table: &mut HashMap<String, Entry>) {
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let mut fields = line.split('\t');
+        let word = match fields.next() {
+            Some(w) if !w.trim().is_empty() => w.trim().to_lowercase(),
+            _ => continue,
+        };
+        let entry = table.entry(word).or_default();
+        // 用户词表中留空的列保留内置值
+        if let Some(level) = fields.next().map(|l| l.trim().to_uppercase()) {
+            if !level.is_empty() {
+                entry.level = Some(level);
+            }
+        }
+        if let Some(tags) = fields.next() {
+            for tag in tags.split(',').map(|t| t.trim().to_lowercase()) {
+                if !tag.is_empty() && !entry.tags.contains(&tag) {
+                    entry.tags.push(tag);
+                }
+            }
+        }
+    }
+}
+
+fn vocab_dir() -> Option<PathBuf> {
+    BASE_PATH.get().map(|p| p.join(VOCAB_DIR_NAME))
+}
+
+/// 先加载内置词表，再加载配置目录下vocab中的tsv文件，用户词表可覆盖、补充内置词表
+static TABLE: Lazy<HashMap<String, Entry>> = Lazy::new(|| {
+    let mut table = HashMap::new();
+    let mut content = String::new();
+    match GzDecoder::new(BUNDLED).read_to_string(&mut content) {
+        Ok(_) => parse_into(&content, &mut table),
+        Err(e) => warn!("解压内置词表失败：{}", e),
+    }
+    if let Some(Ok(dir)) = vocab_dir().map(fs::read_dir) {
+        for path in dir.filter_map(|e| e.ok().map(|e| e.path())) {
+            if !path.is_file() || path.extension().map_or(true, |e| e != "tsv") {
+                continue;
+            }
+            if let Ok(content) = fs::read_to_string(&path) {
+                parse_into(&content, &mut table);
+            }
+        }
+    }
+    table
+});
+
+/// 估算英文单词的音节数：统计元音组，去掉词尾不发音的e
+fn count_syllables(word: &str) -> usize {
+    let word = word.to_lowercase();
+    let chars: Vec<char> = word.chars().filter(|c| c.is_ascii_alphabetic()).collect();
+    if chars.is_empty() {
+        return 0;
+    }
+    let is_vowel = |c: char| "aeiouy".contains(c);
+    let mut count = 0;
+    let mut prev_vowel = false;
+    for c in &chars {
+        let vowel = is_vowel(*c);
+        if vowel && !prev_vowel {
+            count += 1;
+        }
+        prev_vowel = vowel;
+    }
+    let len = chars.len();
+    if len > 2 && chars[len - 1] == 'e' && !is_vowel(chars[len - 2]) {
+        // 以辅音+le结尾时e仍构成音节，如 table
+        if chars[len - 2] != 'l' || is_vowel(chars[len - 3]) {
+            count -= 1;
+        }
+    }
+    count.max(1)
+}
+
+fn count_chars(text: &str, stats: &mut TextStats) {
+    for c in text.chars() {
+        match c as u32 {
+            0x3400..=0x4DBF | 0x4E00..=0x9FFF | 0xF900..=0xFAFF => stats.han_chars += 1,
+            0x3041..=0x309F | 0x30A1..=0x30FF | 0xFF66..=0xFF9F => stats.kana_chars += 1,
+            0xAC00..=0xD7A3 | 0x1100..=0x11FF | 0x3130..=0x318F => stats.hangul_chars += 1,
+            _ => {}
+        }
+    }
+    stats.cjk_chars = stats.han_chars + stats.kana_chars + stats.hangul_chars;
+}
+
+fn lookup(word: &str) -> (String, Option<&'static Entry>) {
+    let lower = word.to_lowercase();
+    let lemmas = lemmatize::lemmatize_word(&lower);
+    // 按可能性依次查找候选原形，都不在词表中时再用词形本身，如 unprecedented
+    for lemma in &lemmas {
+        if let Some((key, entry)) = TABLE.get_key_value(&lemma.lemma) {
+            return (key.clone(), Some(entry));
+        }
+    }
+    match TABLE.get(&lower) {
+        Some(entry) => (lower, Some(entry)),
+        None => (lemmas.into_iter().next().map_or(lower, |l| l.lemma), None),
+    }
+}
+
+/// 为文本中的英文单词标注难度等级，同时统计文本信息
+pub fn tag_text(text: &str) -> VocabLevels {
+    let mut stats = TextStats::default();
+    let mut words = Vec::new();
+    let mut lemmas = HashSet::new();
+    let mut sentence_open = false;
+    for token in tokenize::tokenize_text(text, None) {
+        match token.kind {
+            TokenKind::Punctuation => {
+                if sentence_open && token.text.chars().any(|c| ".!?。！？".contains(c)) {
+                    stats.sentence_count += 1;
+                    sentence_open = false;
+                }
+                continue;
+            }
+            TokenKind::Whitespace => continue,
+            _ => sentence_open = true,
+        }
+        if token.kind != TokenKind::Word
+            || token.lang != "en"
+            || !token
+                .text
+                .chars()
+                .all(|c| c.is_ascii_alphabetic() || c == '\'' || c == '-')
+        {
+            continue;
+        }
+        stats.word_count += 1;
+        stats.syllable_count += count_syllables(&token.text);
+        let (lemma, entry) = lookup(&token.text);
+        lemmas.insert(lemma.clone());
+        let level = entry.and_then(|e| e.level.clone());
+        *stats
+            .levels
+            .entry(level.clone().unwrap_or_else(|| "unknown".to_string()))
+            .or_insert(0) += 1;
+        words.push(TaggedWord {
+            text: token.text,
+            start: token.start,
+            end: token.end,
+            char_start: token.char_start,
+            char_end: token.char_end,
+            lemma,
+            level,
+            tags: entry.map(|e| e.tags.clone()).unwrap_or_default(),
+        });
+    }
+    if sentence_open {
+        stats.sentence_count += 1;
+    }
+    stats.unique_lemmas = lemmas.len();
+    count_chars(text, &mut stats);
+    if stats.word_count > 0 {
+        let words = stats.word_count as f64;
+        let sentences = stats.sentence_count.max(1) as f64;
+        let score =
+            206.835 - 1.015 * (words / sentences) - 84.6 * (stats.syllable_count as f64 / words);
+        stats.flesch = Some((score * 10.0).round() / 10.0);
+    }
+    let minutes =
+        stats.word_count as f64 / WORDS_PER_MINUTE + stats.cjk_chars as f64 / CJK_CHARS_PER_MINUTE;
+    stats.reading_seconds = (minutes * 60.0).ceil() as u64;
+    VocabLevels { words, stats }
+}
+
+#[tauri::command(async)]
+pub fn vocab_levels(text: &str) -> Result<VocabLevels, ()> {
+    Ok(tag_text(text))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inflected_sentence() {
+        let result = tag_text("She looked at the buses. They were reading and eating!");
+        let lemma = |text: &str| {
+            let word = result.words.iter().find(|w| w.text == text).unwrap();
+            (word.lemma.as_str(), word.level.as_deref())
+        };
+        assert_eq!(lemma("looked"), ("look", Some("A1")));
+        assert_eq!(lemma("reading"), ("read", Some("A1")));
+        assert_eq!(lemma("eating"), ("eat", Some("A1")));
+        assert_eq!(lemma("were").0, "be");
+        assert_eq!(lemma("buses"), ("bus", Some("A1")));
+        assert_eq!(result.stats.word_count, 10);
+        assert_eq!(result.stats.unique_lemmas, 10);
+        assert_eq!(result.stats.sentence_count, 2);
+        assert!(!result.stats.levels.contains_key("unknown"));
+    }
+}
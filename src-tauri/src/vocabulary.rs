@@ -0,0 +1,551 @@
+use crate::{
+    config::{get_config_with_default, get_or_bool},
+    global::{APP, BASE_PATH},
+};
+use log::{debug, info, warn};
+use once_cell::sync::Lazy;
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use serde::Serialize;
+use serde_json::Value;
+use std::{
+    path::PathBuf,
+    sync::Mutex,
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+const VOCABULARY_FILE_NAME: &str = "vocabulary.db";
+/// 默认查询3次后自动加入生词本
+const DEFAULT_AUTO_STAR: i64 = 3;
+/// 默认每天20点提醒复习
+const DEFAULT_NOTIFY_HOUR: i64 = 20;
+/// SM-2 的最低难度系数
+const MIN_EASE: f64 = 1.3;
+/// 语境句子的最大长度
+const MAX_CONTEXT_CHARS: usize = 300;
+const DAY_MILLIS: f64 = 24.0 * 60.0 * 60.0 * 1000.0;
+/// 检查是否需要提醒复习的间隔
+const NOTIFY_CHECK_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+static CONNECTION: Lazy<Mutex<Option<Connection>>> = Lazy::new(|| Mutex::new(None));
+/// 最近一次划词翻译的文本及来源应用
+static SOURCE: Lazy<Mutex<Option<(String, String)>>> = Lazy::new(|| Mutex::new(None));
+
+/// 生词，语种为Tosa的语种代码，interval 为复习间隔（天）
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Word {
+    pub id: i64,
+    pub word: String,
+    pub from: String,
+    pub to: String,
+    pub phonetic: String,
+    /// 释义，每个词性一行
+    pub definition: String,
+    /// 词典服务返回的完整结果
+    pub result: Value,
+    /// 查词时所在的句子
+    pub context: String,
+    pub source_app: String,
+    pub lookups: i64,
+    pub starred: bool,
+    pub created: i64,
+    pub updated: i64,
+    pub due: i64,
+    pub interval: f64,
+    pub ease: f64,
+    pub reps: i64,
+    pub lapses: i64,
+    pub reviewed: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Page {
+    pub total: i64,
+    pub items: Vec<Word>,
+}
+
+const WORD_COLUMNS: &str = "id, word, from_lang, to_lang, phonetic, definition, result, context, source_app, \
+                            lookups, starred, created, updated, due, interval, ease, reps, lapses, reviewed";
+
+fn db_path() -> PathBuf {
+    BASE_PATH.get().unwrap().join(VOCABULARY_FILE_NAME)
+}
+
+fn now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or_default()
+}
+
+fn open() -> rusqlite::Result<Connection> {
+    let conn = Connection::open(db_path())?;
+    conn.execute_batch(
+        "PRAGMA journal_mode = WAL;
+         PRAGMA synchronous = NORMAL;
+         CREATE TABLE IF NOT EXISTS words (
+             id INTEGER PRIMARY KEY AUTOINCREMENT,
+             word TEXT NOT NULL,
+             normalized TEXT NOT NULL,
+             from_lang TEXT NOT NULL,
+             to_lang TEXT NOT NULL,
+             phonetic TEXT NOT NULL DEFAULT '',
+             definition TEXT NOT NULL DEFAULT '',
+             result TEXT NOT NULL DEFAULT 'null',
+             context TEXT NOT NULL DEFAULT '',
+             source_app TEXT NOT NULL DEFAULT '',
+             lookups INTEGER NOT NULL DEFAULT 0,
+             starred INTEGER NOT NULL DEFAULT 0,
+             created INTEGER NOT NULL,
+             updated INTEGER NOT NULL,
+             due INTEGER NOT NULL DEFAULT 0,
+             interval REAL NOT NULL DEFAULT 0,
+             ease REAL NOT NULL DEFAULT 2.5,
+             reps INTEGER NOT NULL DEFAULT 0,
+             lapses INTEGER NOT NULL DEFAULT 0,
+             reviewed INTEGER NOT NULL DEFAULT 0,
+             -- 已自动加入过或手动加入、移出过生词本，之后不再自动加入
+             star_decided INTEGER NOT NULL DEFAULT 0,
+             UNIQUE(normalized, from_lang)
+         );
+         CREATE INDEX IF NOT EXISTS words_due ON words(starred, due);
+         CREATE INDEX IF NOT EXISTS words_updated ON words(updated);
+         CREATE TABLE IF NOT EXISTS meta (key TEXT PRIMARY KEY, value TEXT NOT NULL);",
+    )?;
+    info!("vocabulary opened: {:?}", db_path());
+    Ok(conn)
+}
+
+/// 使用数据库连接执行操作，首次使用时打开数据库
+fn with_conn<T>(f: impl FnOnce(&mut Connection) -> rusqlite::Result<T>) -> Result<T, String> {
+    let mut guard = CONNECTION.lock().unwrap();
+    if guard.is_none() {
+        *guard = Some(open().map_err(|e| format!("打开生词本失败：{}", e))?);
+    }
+    f(guard.as_mut().unwrap()).map_err(|e| format!("生词本操作失败：{}", e))
+}
+
+fn word(row: &Row) -> rusqlite::Result<Word> {
+    let result: String = row.get(6)?;
+    Ok(Word {
+        id: row.get(0)?,
+        word: row.get(1)?,
+        from: row.get(2)?,
+        to: row.get(3)?,
+        phonetic: row.get(4)?,
+        definition: row.get(5)?,
+        result: serde_json::from_str(&result).unwrap_or(Value::Null),
+        context: row.get(7)?,
+        source_app: row.get(8)?,
+        lookups: row.get(9)?,
+        starred: row.get(10)?,
+        created: row.get(11)?,
+        updated: row.get(12)?,
+        due: row.get(13)?,
+        interval: row.get(14)?,
+        ease: row.get(15)?,
+        reps: row.get(16)?,
+        lapses: row.get(17)?,
+        reviewed: row.get(18)?,
+    })
+}
+
+fn get(conn: &Connection, id: i64) -> rusqlite::Result<Option<Word>> {
+    conn.query_row(
+        &format!("SELECT {} FROM words WHERE id = ?1", WORD_COLUMNS),
+        [id],
+        word,
+    )
+    .optional()
+}
+
+fn normalize(text: &str) -> String {
+    text.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+/// 词典结果中的音标，优先使用 phonetic 字段
+pub fn phonetic(result: &Value) -> String {
+    result
+        .get("phonetic")
+        .and_then(Value::as_str)
+        .filter(|s| !s.is_empty())
+        .or_else(|| {
+            result
+                .get("pronunciations")?
+                .as_array()?
+                .iter()
+                .filter_map(|p| p.get("symbol")?.as_str())
+                .find(|s| !s.is_empty())
+        })
+        .unwrap_or_default()
+        .to_string()
+}
+
+/// 词典结果中的释义，每个词性一行，没有释义时使用翻译结果
+pub fn definition(result: &Value) -> String {
+    let lines: Vec<String> = result
+        .get("explanations")
+        .and_then(Value::as_array)
+        .map(|list| {
+            list.iter()
+                .filter_map(|item| {
+                    let explains: Vec<&str> = item
+                        .get("explains")?
+                        .as_array()?
+                        .iter()
+                        .filter_map(Value::as_str)
+                        .collect();
+                    if explains.is_empty() {
+                        return None;
+                    }
+                    let tr = item
+                        .get("trait")
+                        .and_then(Value::as_str)
+                        .unwrap_or_default();
+                    Some(format!("{} {}", tr, explains.join("；")).trim().to_string())
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    if lines.is_empty() {
+        return result
+            .get("text")
+            .or(Some(result))
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string();
+    }
+    lines.join("\n")
+}
+
+/// 从上下文中取出包含单词的句子
+pub fn extract_sentence(context: &str, word: &str) -> String {
+    let word = word.trim().to_lowercase();
+    if word.is_empty() {
+        return String::new();
+    }
+    let mut sentences = Vec::new();
+    let mut current = String::new();
+    for c in context.chars() {
+        if c == '\n' || c == '\r' {
+            sentences.push(std::mem::take(&mut current));
+            continue;
+        }
+        current.push(c);
+        if matches!(c, '.' | '!' | '?' | '。' | '！' | '？' | ';' | '；') {
+            sentences.push(std::mem::take(&mut current));
+        }
+    }
+    sentences.push(current);
+    sentences
+        .into_iter()
+        .map(|s| s.trim().to_string())
+        .find(|s| s.to_lowercase().contains(&word) && s.to_lowercase() != word)
+        .map(|s| s.chars().take(MAX_CONTEXT_CHARS).collect())
+        .unwrap_or_default()
+}
+
+/// 记录划词翻译的文本及来源应用，之后查询的单词在其中时作为来源
+pub fn remember_source(text: &str, app: String) {
+    *SOURCE.lock().unwrap() = Some((text.trim().to_lowercase(), app));
+}
+
+fn source_app(word: &str, context: &str) -> String {
+    let word = word.to_lowercase();
+    let context = context.to_lowercase();
+    match SOURCE.lock().unwrap().as_ref() {
+        Some((text, app)) if *text == word || (!context.is_empty() && text.contains(&context)) => {
+            app.clone()
+        }
+        _ => String::new(),
+    }
+}
+
+/// 记录一次查词，查询次数达到 vocab_auto_star 时自动加入生词本，手动移出过的单词不再自动加入
+pub fn lookup(
+    word: &str,
+    from: &str,
+    to: &str,
+    result: &Value,
+    context: &str,
+) -> Result<Word, String> {
+    let normalized = normalize(word);
+    if normalized.is_empty() {
+        return Err("单词不能为空".to_string());
+    }
+    let context = extract_sentence(context, word);
+    let app = source_app(&normalized, &context);
+    let auto_star = get_config_with_default("vocab_auto_star", DEFAULT_AUTO_STAR, |v| v.as_i64());
+    let time = now();
+    with_conn(|conn| {
+        conn.execute(
+            "INSERT INTO words (word, normalized, from_lang, to_lang, phonetic, definition, result, context,
+                                source_app, lookups, created, updated)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, 1, ?10, ?10)
+             ON CONFLICT(normalized, from_lang) DO UPDATE SET
+                 word = excluded.word,
+                 to_lang = excluded.to_lang,
+                 phonetic = CASE WHEN excluded.phonetic = '' THEN phonetic ELSE excluded.phonetic END,
+                 definition = CASE WHEN excluded.definition = '' THEN definition ELSE excluded.definition END,
+                 result = excluded.result,
+                 context = CASE WHEN excluded.context = '' THEN context ELSE excluded.context END,
+                 source_app = CASE WHEN excluded.source_app = '' THEN source_app ELSE excluded.source_app END,
+                 lookups = lookups + 1,
+                 updated = excluded.updated",
+            params![
+                word.trim(),
+                normalized,
+                from,
+                to,
+                phonetic(result),
+                definition(result),
+                result.to_string(),
+                context,
+                app,
+                time
+            ],
+        )?;
+        let id: i64 = conn.query_row(
+            "SELECT id FROM words WHERE normalized = ?1 AND from_lang = ?2",
+            params![normalized, from],
+            |r| r.get(0),
+        )?;
+        if auto_star > 0 {
+            let n = conn.execute(
+                "UPDATE words SET starred = 1, star_decided = 1, due = ?2
+                 WHERE id = ?1 AND starred = 0 AND star_decided = 0 AND lookups >= ?3",
+                params![id, time, auto_star],
+            )?;
+            if n > 0 {
+                debug!("vocabulary auto starred: {}", word);
+            }
+        }
+        Ok(get(conn, id)?.unwrap())
+    })
+}
+
+/// 加入或移出生词本，加入时立即可以复习
+pub fn set_starred(id: i64, starred: bool) -> Result<Option<Word>, String> {
+    with_conn(|conn| {
+        conn.execute(
+            "UPDATE words SET starred = ?2, star_decided = 1, due = CASE WHEN ?2 THEN ?3 ELSE due END
+             WHERE id = ?1 AND starred != ?2",
+            params![id, starred, now()],
+        )?;
+        get(conn, id)
+    })
+}
+
+/// filter 为 all、starred 或 due，待复习的按到期时间排序
+pub fn list(filter: &str, keyword: &str, offset: i64, limit: i64) -> Result<Page, String> {
+    let like = format!(
+        "%{}%",
+        keyword.trim().replace('%', "\\%").replace('_', "\\_")
+    );
+    let condition = "(?2 = 'all' OR (starred = 1 AND (?2 = 'starred' OR due <= ?3))) \
+                     AND (word LIKE ?1 ESCAPE '\\' OR definition LIKE ?1 ESCAPE '\\' OR context LIKE ?1 ESCAPE '\\')";
+    let time = now();
+    with_conn(|conn| {
+        let total = conn.query_row(
+            &format!("SELECT COUNT(*) FROM words WHERE {}", condition),
+            params![like, filter, time],
+            |r| r.get(0),
+        )?;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM words WHERE {} ORDER BY CASE WHEN ?2 = 'due' THEN due ELSE -updated END, id DESC \
+             LIMIT ?4 OFFSET ?5",
+            WORD_COLUMNS, condition
+        ))?;
+        let items = stmt
+            .query_map(params![like, filter, time, limit, offset], word)?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(Page { total, items })
+    })
+}
+
+/// 导出使用的单词，按加入时间排序，时间范围为毫秒时间戳 [since, until)，语种为空时不限
+pub fn query(
+    since: i64,
+    until: i64,
+    from: &str,
+    to: &str,
+    starred_only: bool,
+) -> Result<Vec<Word>, String> {
+    with_conn(|conn| {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM words WHERE created >= ?1 AND created < ?2 AND (?3 = '' OR from_lang = ?3)
+             AND (?4 = '' OR to_lang = ?4) AND (?5 = 0 OR starred = 1) ORDER BY created, id",
+            WORD_COLUMNS
+        ))?;
+        let items = stmt
+            .query_map(params![since, until, from, to, starred_only], word)?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(items)
+    })
+}
+
+pub fn due_count() -> Result<i64, String> {
+    with_conn(|conn| {
+        conn.query_row(
+            "SELECT COUNT(*) FROM words WHERE starred = 1 AND due <= ?1",
+            [now()],
+            |r| r.get(0),
+        )
+    })
+}
+
+/// SM-2 算法，grade 为回忆质量（0 - 5），低于3时重新开始且不改变难度系数
+fn schedule(word: &mut Word, grade: u8, time: i64) {
+    let q = grade.min(5) as f64;
+    if grade < 3 {
+        word.reps = 0;
+        word.interval = 1.0;
+        word.lapses += 1;
+    } else {
+        word.interval = match word.reps {
+            0 => 1.0,
+            1 => 6.0,
+            _ => (word.interval * word.ease).round(),
+        };
+        word.reps += 1;
+        word.ease = (word.ease + 0.1 - (5.0 - q) * (0.08 + (5.0 - q) * 0.02)).max(MIN_EASE);
+    }
+    word.reviewed = time;
+    word.due = time + (word.interval * DAY_MILLIS) as i64;
+}
+
+pub fn review(id: i64, grade: u8) -> Result<Word, String> {
+    let time = now();
+    with_conn(|conn| {
+        let Some(mut item) = get(conn, id)? else {
+            return Ok(None);
+        };
+        schedule(&mut item, grade, time);
+        conn.execute(
+            "UPDATE words SET due = ?2, interval = ?3, ease = ?4, reps = ?5, lapses = ?6, reviewed = ?7 WHERE id = ?1",
+            params![
+                id,
+                item.due,
+                item.interval,
+                item.ease,
+                item.reps,
+                item.lapses,
+                item.reviewed
+            ],
+        )?;
+        Ok(Some(item))
+    })?
+    .ok_or_else(|| "生词不存在".to_string())
+}
+
+pub fn remove(ids: &[i64]) -> Result<usize, String> {
+    with_conn(|conn| {
+        let tx = conn.transaction()?;
+        let mut count = 0;
+        for id in ids {
+            count += tx.execute("DELETE FROM words WHERE id = ?1", [id])?;
+        }
+        tx.commit()?;
+        Ok(count)
+    })
+}
+
+/// 到达提醒时间且今天还未提醒时返回待复习的数量，日期和小时使用本地时间
+fn pending_notification() -> Result<Option<i64>, String> {
+    let hour = get_config_with_default("vocab_notify_hour", DEFAULT_NOTIFY_HOUR, |v| v.as_i64());
+    let count = due_count()?;
+    with_conn(|conn| {
+        let (today, current): (String, i64) = conn.query_row(
+            "SELECT date('now', 'localtime'), CAST(strftime('%H', 'now', 'localtime') AS INTEGER)",
+            [],
+            |r| Ok((r.get(0)?, r.get(1)?)),
+        )?;
+        let notified: Option<String> = conn
+            .query_row("SELECT value FROM meta WHERE key = 'notified'", [], |r| {
+                r.get(0)
+            })
+            .optional()?;
+        if current < hour || notified.as_deref() == Some(today.as_str()) || count == 0 {
+            return Ok(None);
+        }
+        conn.execute(
+            "INSERT OR REPLACE INTO meta (key, value) VALUES ('notified', ?1)",
+            [today],
+        )?;
+        Ok(Some(count))
+    })
+}
+
+fn notify(count: i64) {
+    let Some(app) = APP.get() else {
+        return;
+    };
+    let res = tauri::api::notification::Notification::new(&app.config().tauri.bundle.identifier)
+        .title("生词复习")
+        .body(format!("今天有 {} 个生词需要复习", count))
+        .show();
+    if let Err(e) = res {
+        warn!("vocabulary notification error: {}", e);
+    }
+}
+
+/// 后台定时检查，开启 vocab_notify 时每天提醒一次复习
+pub fn init() {
+    thread::spawn(|| loop {
+        if get_or_bool("vocab_notify", false) {
+            match pending_notification() {
+                Ok(Some(count)) => notify(count),
+                Ok(None) => {}
+                Err(e) => warn!("{}", e),
+            }
+        }
+        thread::sleep(NOTIFY_CHECK_INTERVAL);
+    });
+}
+
+/// 记录查词，context 为查词时所在的文本，返回生词信息
+#[tauri::command]
+pub fn vocab_lookup(
+    word: String,
+    from: String,
+    to: String,
+    result: Value,
+    context: String,
+) -> Result<Word, String> {
+    lookup(&word, &from, &to, &result, &context)
+}
+
+#[tauri::command]
+pub fn vocab_star(id: i64, starred: bool) -> Result<Option<Word>, String> {
+    set_starred(id, starred)
+}
+
+#[tauri::command]
+pub fn vocab_list(
+    filter: String,
+    keyword: String,
+    offset: i64,
+    limit: i64,
+) -> Result<Page, String> {
+    list(&filter, &keyword, offset, limit)
+}
+
+#[tauri::command]
+pub fn vocab_due_count() -> Result<i64, String> {
+    due_count()
+}
+
+/// 提交复习结果，grade 为回忆质量（0 - 5）
+#[tauri::command]
+pub fn vocab_review(id: i64, grade: u8) -> Result<Word, String> {
+    review(id, grade)
+}
+
+#[tauri::command]
+pub fn vocab_remove(ids: Vec<i64>) -> Result<usize, String> {
+    remove(&ids)
+}
@@ -0,0 +1,142 @@
+// 简繁转换数据，格式参考OpenCC，只收录常用字词
+
+/// 简→繁 单字对照，每组两个字：简体在前、繁体在后；一简对多繁时取最常用的繁体，其余交给词组处理
+pub const S2T_CHARS: &str = "
+万萬 与與 丑醜 专專 业業 丛叢 东東 丝絲 丢丟 两兩 严嚴 丧喪 个個 丰豐 临臨 为為 丽麗 举舉 么麼 义義
+乌烏 乐樂 乔喬 习習 乡鄉 书書 买買 乱亂 争爭 于於 亏虧 云雲 亚亞 产產 亩畝 亲親 亵褻 亿億 仅僅 从從
+仑崙 仓倉 仆僕 仪儀 们們 价價 众眾 优優 伙夥 会會 伛傴 伞傘 伟偉 传傳 伤傷 伦倫 伪偽 体體 余餘 佣傭
+侠俠 侣侶 侥僥 侦偵 侧側 侨僑 侬儂 俦儔 俨儼 俩倆 俭儉 债債 倾傾 偻僂 偾僨 偿償 傥儻 傧儐 储儲 儿兒
+兑兌 党黨 兰蘭 关關 兴興 兹茲 养養 兽獸 内內 冈岡 册冊 写寫 军軍 农農 冯馮 冲衝 决決 况況 冻凍 净淨
+凉涼 减減 凑湊 凛凜 几幾 凤鳳 凭憑 凯凱 击擊 凿鑿 划劃 刘劉 则則 刚剛 创創 删刪 别別 刹剎 刽劊 剀剴
+剂劑 剐剮 剑劍 剥剝 剧劇 劝勸 办辦 务務 动動 励勵 劲勁 劳勞 势勢 勋勳 匀勻 匮匱 区區 医醫 华華 协協
+单單 卖賣 卢盧 卤滷 卫衛 却卻 厂廠 厅廳 历歷 厉厲 压壓 厌厭 厕廁 厢廂 厦廈 厨廚 厩廄 厮廝 县縣 参參
+双雙 发發 变變 叙敘 叠疊 叶葉 号號 叹嘆 叽嘰 吓嚇 吕呂 吗嗎 吨噸 听聽 启啟 吴吳 呐吶 呓囈 呕嘔 呖嚦
+呗唄 员員 呛嗆 呜嗚 咏詠 咙嚨 咛嚀 响響 哑啞 哒噠 哔嗶 哗嘩 哟喲 唠嘮 唤喚 啧嘖 啬嗇 啸嘯 喷噴 喽嘍
+嗳噯 嘘噓 嘤嚶 嘱囑 噜嚕 嚣囂 团團 园園 囱囪 围圍 囵圇 国國 图圖 圆圓 圣聖 场場 坏壞 块塊 坚堅 坛壇
+坝壩 坞塢 坟墳 坠墜 垄壟 垒壘 垦墾 垫墊 埚堝 堑塹 堕墮 墙牆 壮壯 声聲 壳殼 壶壺 处處 备備 复復 够夠
+头頭 夸誇 夹夾 夺奪 奋奮 奖獎 奥奧 妆妝 妇婦 妈媽 妩嫵 妪嫗 姗姍 娄婁 娅婭 娆嬈 娇嬌 娱娛 娴嫻 婴嬰
+婵嬋 婶嬸 嫔嬪 嬷嬤 孙孫 学學 孪孿 宁寧 宝寶 实實 宠寵 审審 宪憲 宫宮 宽寬 宾賓 寝寢 对對 寻尋 导導
+寿壽 将將 尔爾 尘塵 尝嘗 尧堯 尴尷 尸屍 尽盡 层層 屉屜 届屆 属屬 屡屢 屿嶼 岁歲 岂豈 岖嶇 岗崗 岚嵐
+岛島 岭嶺 峡峽 峤嶠 峥崢 峦巒 崭嶄 嵘嶸 巅巔 巩鞏 币幣 帅帥 师師 帐帳 帘簾 帜幟 带帶 帧幀 帮幫 帼幗
+幂冪 并並 广廣 庄莊 庆慶 庐廬 库庫 应應 庙廟 庞龐 废廢 廪廩 开開 异異 弃棄 张張 弥彌 弯彎 弹彈 强強
+归歸 当當 录錄 彦彥 彻徹 径徑 忆憶 忏懺 忧憂 怀懷 态態 怂慫 怅悵 怆愴 怜憐 总總 恋戀 恳懇 恶惡 恸慟
+恺愷 恻惻 恼惱 悦悅 悬懸 悭慳 悯憫 惊驚 惧懼 惨慘 惩懲 惫憊 惬愜 惭慚 惮憚 惯慣 愠慍 愤憤 愿願 慑懾
+懑懣 懒懶 戏戲 战戰 户戶 扑撲 执執 扩擴 扪捫 扫掃 扬揚 扰擾 抚撫 抛拋 抠摳 抡掄 抢搶 护護 报報 担擔
+拟擬 拢攏 拣揀 拥擁 拦攔 拧擰 拨撥 择擇 挂掛 挚摯 挛攣 挞撻 挟挾 挠撓 挡擋 挣掙 挤擠 挥揮 捞撈 损損
+捡撿 换換 捣搗 据據 掳擄 掷擲 掸撣 掺摻 揽攬 搀攙 搁擱 搂摟 搅攪 携攜 摄攝 摆擺 摇搖 摊攤 撑撐 撵攆
+撷擷 撺攛 擞擻 攒攢 敌敵 敛斂 数數 斋齋 斓斕 斩斬 断斷 无無 旧舊 时時 旷曠 昙曇 昼晝 显顯 晋晉 晒曬
+晓曉 晔曄 晕暈 晖暉 暂暫 暧曖 术術 机機 杀殺 杂雜 权權 条條 来來 杨楊 杰傑 极極 构構 枢樞 枣棗 枪槍
+枫楓 枭梟 柜櫃 柠檸 栅柵 标標 栈棧 栋棟 栏欄 树樹 栖棲 样樣 桥橋 桦樺 桨槳 桩樁 梦夢 检檢 椭橢 楼樓
+榄欖 榈櫚 榉櫸 槛檻 槟檳 横橫 樱櫻 橱櫥 橹櫓 欢歡 欧歐 歼殲 殁歿 殇殤 残殘 殒殞 殓殮 殡殯 殴毆 毁毀
+毕畢 毙斃 毡氈 气氣 氢氫 汇匯 汉漢 汤湯 汹洶 沟溝 没沒 沥瀝 沦淪 沧滄 沪滬 泞濘 泪淚 泸瀘 泻瀉 泼潑
+泽澤 泾涇 洁潔 洒灑 洼窪 浃浹 浅淺 浆漿 浇澆 浊濁 测測 济濟 浏瀏 浑渾 浓濃 涛濤 涝澇 涟漣 涡渦 涣渙
+涤滌 润潤 涧澗 涨漲 涩澀 涂塗 渊淵 渍漬 渐漸 渔漁 渗滲 温溫 湾灣 湿濕 溃潰 溅濺 滚滾 滞滯 满滿 滤濾
+滥濫 滨濱 滩灘 潇瀟 潜潛 澜瀾 濒瀕 灭滅 灯燈 灵靈 灾災 灿燦 炉爐 炖燉 点點 炼煉 炽熾 烁爍 烂爛 烛燭
+烟煙 烦煩 烧燒 烫燙 烬燼 热熱 焕煥 焖燜 爱愛 爷爺 牍牘 牵牽 牺犧 犊犢 状狀 犹猶 狈狽 狞獰 独獨 狭狹
+狮獅 狰猙 狱獄 猎獵 猕獼 猪豬 猫貓 献獻 獭獺 玛瑪 玮瑋 环環 现現 玺璽 珑瓏 琐瑣 琼瓊 瑶瑤 璎瓔 电電
+画畫 畅暢 疗療 疟瘧 疮瘡 疯瘋 痒癢 痨癆 痪瘓 瘫癱 瘾癮 癣癬 癫癲 皑皚 皱皺 盏盞 盐鹽 监監 盖蓋 盗盜
+盘盤 着著 睁睜 睑瞼 瞒瞞 瞩矚 矫矯 矶磯 矿礦 码碼 砖磚 砚硯 砺礪 砾礫 础礎 硕碩 确確 碍礙 碱鹼 礼禮
+祢禰 祯禎 祷禱 祸禍 禀稟 禄祿 禅禪 离離 秃禿 秆稈 种種 积積 称稱 秽穢 税稅 稳穩 穷窮 窃竊 窍竅 窑窯
+窜竄 窝窩 窥窺 竖豎 竞競 笃篤 笋筍 笔筆 笺箋 笼籠 筑築 筛篩 筝箏 筹籌 签簽 简簡 箩籮 箫簫 篓簍 篮籃
+篱籬 籁籟 类類 粤粵 粪糞 粮糧 紧緊 纠糾 红紅 纤纖 约約 级級 纪紀 纬緯 纯純 纱紗 纲綱 纳納 纵縱 纷紛
+纸紙 纹紋 纺紡 纽紐 线線 练練 组組 绅紳 细細 织織 终終 绊絆 绍紹 绎繹 经經 绑綁 绒絨 结結 绕繞 绘繪
+给給 绚絢 络絡 绝絕 绞絞 统統 绢絹 绣繡 继繼 绩績 绪緒 续續 绮綺 绯緋 绰綽 绳繩 维維 绵綿 绷繃 绸綢
+综綜 绽綻 绿綠 缀綴 缅緬 缆纜 缉緝 缎緞 缓緩 缔締 缕縷 编編 缘緣 缚縛 缝縫 缠纏 缤繽 缥縹 缨纓 缩縮
+缭繚 缮繕 缴繳 罂罌 网網 罗羅 罚罰 罢罷 羁羈 翘翹 耸聳 耻恥 聂聶 聋聾 职職 联聯 聪聰 肃肅 肠腸 肤膚
+肾腎 肿腫 胀脹 胁脅 胆膽 胜勝 胧朧 胶膠 脉脈 脏髒 脐臍 脑腦 脓膿 脚腳 脱脫 脸臉 腊臘 腻膩 腾騰 舆輿
+舰艦 舱艙 艰艱 艳豔 艺藝 节節 芦蘆 苇葦 苍蒼 茎莖 茧繭 荆荊 荐薦 荚莢 荞蕎 荟薈 荡蕩 荣榮 荤葷 荧熒
+荫蔭 药藥 莱萊 莲蓮 获獲 莹瑩 莺鶯 萝蘿 萤螢 营營 萧蕭 萨薩 葱蔥 蒋蔣 蓝藍 蓦驀 蔷薔 蔼藹 蕴蘊 藓蘚
+苏蘇 虏虜 虑慮 虚虛 虫蟲 虽雖 虾蝦 蚀蝕 蚁蟻 蚂螞 蚕蠶 蛊蠱 蛮蠻 蛰蟄 蜗蝸 蜡蠟 蝇蠅 蝉蟬 蝎蠍 衅釁
+衔銜 补補 衬襯 袄襖 袜襪 袭襲 装裝 裆襠 裤褲 褛褸 褴襤 见見 观觀 规規 觅覓 视視 览覽 觉覺 觊覬 觐覲
+觑覷 触觸 誉譽 计計 订訂 认認 讥譏 讨討 让讓 训訓 议議 讯訊 记記 讲講 讳諱 讶訝 许許 讹訛 论論 讼訟
+讽諷 设設 访訪 诀訣 证證 评評 诅詛 识識 诈詐 诉訴 诊診 词詞 译譯 试試 诗詩 诚誠 话話 诞誕 询詢 该該
+详詳 诧詫 诫誡 诬誣 语語 误誤 诱誘 诲誨 说說 诵誦 请請 诸諸 诺諾 读讀 诽誹 课課 谁誰 调調 谅諒 谈談
+谊誼 谋謀 谍諜 谎謊 谐諧 谓謂 谚諺 谜謎 谢謝 谣謠 谦謙 谨謹 谬謬 谱譜 谴譴 贝貝 贞貞 负負 贡貢 财財
+责責 贤賢 败敗 账賬 货貨 质質 贩販 贪貪 贫貧 贬貶 购購 贮貯 贯貫 贱賤 贴貼 贵貴 贷貸 贸貿 费費 贺賀
+贼賊 贾賈 贿賄 赁賃 赂賂 赃贓 资資 赈賑 赊賒 赋賦 赌賭 赎贖 赏賞 赐賜 赔賠 赖賴 赘贅 赚賺 赛賽 赞贊
+赠贈 赡贍 赢贏 赵趙 赶趕 趋趨 跃躍 践踐 跷蹺 踊踴 踪蹤 蹑躡 躯軀 车車 轧軋 轨軌 轩軒 转轉 轮輪 软軟
+轰轟 轴軸 轻輕 载載 轿轎 较較 辅輔 辆輛 辈輩 辉輝 辐輻 辑輯 输輸 辖轄 辗輾 辙轍 辞辭 辩辯 辫辮 边邊
+辽遼 达達 迁遷 过過 迈邁 运運 还還 这這 进進 远遠 违違 连連 迟遲 迹跡 适適 选選 逊遜 递遞 逻邏 遗遺
+遥遙 邓鄧 邮郵 邹鄒 邻鄰 郁鬱 郑鄭 酝醞 酱醬 酿釀 释釋 鉴鑒 针針 钉釘 钓釣 钙鈣 钝鈍 钞鈔 钟鐘 钠鈉
+钢鋼 钥鑰 钦欽 钧鈞 钩鉤 钮鈕 钱錢 钳鉗 钻鑽 铁鐵 铃鈴 铅鉛 铜銅 铝鋁 铭銘 铲鏟 银銀 铸鑄 铺鋪 链鏈
+销銷 锁鎖 锂鋰 锅鍋 锈鏽 锋鋒 锌鋅 锐銳 错錯 锚錨 锡錫 锣鑼 锤錘 锥錐 锦錦 键鍵 锯鋸 锻鍛 镀鍍 镁鎂
+镇鎮 镍鎳 镜鏡 镰鐮 镶鑲 长長 门門 闪閃 闭閉 问問 闯闖 闰閏 闲閑 间間 闷悶 闸閘 闹鬧 闺閨 闻聞 阀閥
+阁閣 阅閱 阎閻 阐闡 阔闊 队隊 阳陽 阴陰 阵陣 阶階 际際 陆陸 陇隴 陈陳 陕陝 陨隕 险險 随隨 隐隱 隶隸
+难難 雏雛 雳靂 雾霧 霁霽 霭靄 靓靚 静靜 韦韋 韧韌 韩韓 韵韻 页頁 顶頂 顷頃 项項 顺順 须須 顽頑 顾顧
+顿頓 颁頒 颂頌 预預 颅顱 领領 颇頗 颈頸 颊頰 频頻 颓頹 颖穎 颗顆 题題 颜顏 额額 颠顛 颤顫 风風 飘飄
+飞飛 饥飢 饭飯 饮飲 饰飾 饱飽 饲飼 饵餌 饶饒 饺餃 饼餅 饿餓 馅餡 馆館 馈饋 馋饞 馒饅 马馬 驭馭 驮馱
+驯馴 驰馳 驱驅 驳駁 驴驢 驶駛 驹駒 驻駐 驼駝 驾駕 骂罵 骄驕 骆駱 骇駭 验驗 骏駿 骑騎 骗騙 骚騷 骤驟
+髅髏 鬓鬢 鱼魚 鲁魯 鲍鮑 鲜鮮 鲤鯉 鲨鯊 鲸鯨 鳄鱷 鳞鱗 鸟鳥 鸡雞 鸣鳴 鸥鷗 鸦鴉 鸭鴨 鸯鴦 鸳鴛 鸽鴿
+鸿鴻 鹅鵝 鹊鵲 鹏鵬 鹤鶴 鹦鸚 鹰鷹 麦麥 黄黃 黾黽 齐齊 齿齒 龄齡 龙龍 龚龔 龛龕 龟龜 准準 干幹 里裏
+";
+
+/// 简→繁 词组，用于处理一简对多繁的字，如“发”在“头发”中应为“髮”
+pub const S2T_PHRASES: &str = "
+头发:頭髮 理发:理髮 发型:髮型 白发:白髮 短发:短髮 长发:長髮 假发:假髮 毛发:毛髮 染发:染髮 洗发:洗髮 护发:護髮 卷发:捲髮
+然后:然後 以后:以後 之后:之後 最后:最後 后来:後來 后面:後面 前后:前後 背后:背後 后果:後果 随后:隨後 今后:今後 后悔:後悔
+落后:落後 后台:後台 后端:後端 后者:後者 此后:此後 先后:先後 稍后:稍後 后天:後天 后续:後續 后退:後退 往后:往後 后方:後方
+后代:後代 后期:後期 后门:後門 后卫:後衛 后顾:後顧 后裔:後裔 后援:後援 后勤:後勤 午后:午後 饭后:飯後 事后:事後 日后:日後
+干净:乾淨 干燥:乾燥 饼干:餅乾 干杯:乾杯 干脆:乾脆 干旱:乾旱 干货:乾貨 晒干:曬乾 烘干:烘乾 风干:風乾 干果:乾果 干洗:乾洗
+若干:若干 干扰:干擾 干涉:干涉 干预:干預 相干:相干 天干:天干 干戈:干戈
+公里:公里 千里:千里 英里:英里 里程:里程 邻里:鄰里 故里:故里 乡里:鄉里
+面条:麵條 方便面:方便麵 面粉:麵粉 面包:麵包 拉面:拉麵 炒面:炒麵 汤面:湯麵 凉面:涼麵 面食:麵食 面团:麵糰
+一只:一隻 两只:兩隻 几只:幾隻 这只:這隻 那只:那隻 每只:每隻 船只:船隻
+关系:關係 没关系:沒關係 联系:聯繫 维系:維繫
+制造:製造 制作:製作 制品:製品 复制:複製 制图:製圖 研制:研製 印制:印製 定制:定製 绘制:繪製 录制:錄製 缝制:縫製 自制:自製
+复杂:複雜 重复:重複 复数:複數 复合:複合 回复:回覆 答复:答覆 反复:反覆 复印:複印 复述:複述 复核:複核 复选:複選
+放松:放鬆 轻松:輕鬆 松散:鬆散 松弛:鬆弛 宽松:寬鬆 松开:鬆開 蓬松:蓬鬆 松懈:鬆懈 松动:鬆動
+规范:規範 范围:範圍 模范:模範 示范:示範 范例:範例 范畴:範疇 典范:典範 防范:防範 范本:範本 范式:範式
+日历:日曆 历法:曆法 农历:農曆 阳历:陽曆 阴历:陰曆 挂历:掛曆
+钟情:鍾情 钟爱:鍾愛
+批准:批准 准许:准許 准予:准予 不准:不准
+手表:手錶 钟表:鐘錶 表带:錶帶 怀表:懷錶 电表:電錶 水表:水錶
+特征:特徵 征求:徵求 象征:象徵 征收:徵收 征兆:徵兆 征集:徵集 征税:徵稅 征询:徵詢 征信:徵信 征文:徵文 征召:徵召
+旅游:旅遊 游戏:遊戲 游客:遊客 游览:遊覽 游行:遊行 游乐:遊樂 导游:導遊 周游:周遊 郊游:郊遊 游玩:遊玩 网游:網遊 手游:手遊
+占用:佔用 占据:佔據 占领:佔領 占有:佔有 占比:佔比 霸占:霸佔 侵占:侵佔 独占:獨佔 抢占:搶佔
+战斗:戰鬥 奋斗:奮鬥 斗争:鬥爭 格斗:格鬥 争斗:爭鬥 打斗:打鬥 决斗:決鬥 搏斗:搏鬥 斗志:鬥志 斗殴:鬥毆
+收获:收穫 词汇:詞彙 汇编:彙編 汇总:彙總 萝卜:蘿蔔
+朴素:樸素 简朴:簡樸 纯朴:純樸 朴实:樸實
+冲洗:沖洗 冲泡:沖泡 冲澡:沖澡 冲水:沖水 冲淡:沖淡
+细致:細緻 精致:精緻 别致:別緻 雅致:雅緻
+凶手:兇手 凶猛:兇猛 凶恶:兇惡 凶狠:兇狠
+秋千:鞦韆 胡须:鬍鬚 胡子:鬍子 标签:標籤 书签:書籤 抽签:抽籤
+卷起:捲起 席卷:席捲 卷入:捲入 饭团:飯糰
+茶几:茶几 几乎:幾乎 于是:於是
+";
+
+/// 繁→简 词组，用于处理繁体中一字多义、转换为简体时需要保留原字的情况
+pub const T2S_PHRASES: &str = "
+乾隆:乾隆 乾坤:乾坤 乾元:乾元
+著名:著名 顯著:显著 著作:著作 名著:名著 著稱:著称 原著:原著 專著:专著 論著:论著 卓著:卓著 土著:土著 編著:编著 著述:著述
+答覆:答复 回覆:回复 反覆:反复 覆蓋:覆盖 顛覆:颠覆 覆滅:覆灭 覆沒:覆没
+";
+
+/// 繁→简 单字补充：只出现在词组中的繁体字（如“髮”、“後”）以及各地区的异体字
+pub const T2S_EXTRA_CHARS: &str = "
+髮发 後后 乾干 麵面 麪面 隻只 係系 繫系 製制 複复 鬆松 範范 曆历 鍾钟 錶表 徵征 遊游 佔占 鬥斗 穫获
+彙汇 蔔卜 樸朴 沖冲 緻致 兇凶 鞦秋 韆千 鬍胡 鬚须 籤签 捲卷 糰团 著着 裡里 衆众 啓启 綫线 衞卫 銹锈 僞伪
+爲为 峯峰 眞真 爭争 牀床 鉅巨 餵喂 羣群 冑胄 敎教 竝并 兗兖 裏里
+";
+
+/// 台湾地区用字
+pub const TW_VARIANTS: &str = "裏裡 眞真 羣群 峯峰";
+
+/// 香港地区用字
+pub const HK_VARIANTS: &str = "裡裏 眾衆 啟啓 線綫 麵麪 衛衞 鏽銹 偽僞";
+
+/// 大陆与台湾的常用词汇差异（简体→台湾正体）
+pub const S2TW_PHRASES: &str = "
+软件:軟體 硬件:硬體 信息:資訊 内存:記憶體 网络:網路 程序:程式 默认:預設 视频:影片 鼠标:滑鼠 打印:列印 打印机:印表機
+服务器:伺服器 数据库:資料庫 文件:檔案 文件夹:資料夾 硬盘:硬碟 光盘:光碟 屏幕:螢幕 激光:雷射 短信:簡訊
+博客:部落格 源代码:原始碼 代码:程式碼 接口:介面 菜单:選單 支持:支援 质量:品質 项目:專案 登录:登入 注销:登出 用户:使用者
+优化:最佳化 兼容:相容 在线:線上 离线:離線 摄像头:攝影機 字符:字元 字符串:字串 变量:變數 函数:函式 对象:物件 数组:陣列
+线程:執行緒 高级:進階 操作系统:作業系統 移动电话:行動電話 出租车:計程車 自行车:腳踏車
+公交车:公車 地铁:捷運 土豆:馬鈴薯 方便面:泡麵 信号:訊號 链接:連結
+互联网:網際網路 宽带:寬頻 模块:模組 插件:外掛程式 缓存:快取 通过:透過 发布:發佈 视图:檢視 设置:設定
+";
+
+/// 大陆与香港的常用词汇差异（简体→香港繁体）
+pub const S2HK_PHRASES: &str = "
+信息:資訊 程序:程式 鼠标:滑鼠 出租车:的士 公交车:巴士
+冰淇淋:雪糕 土豆:薯仔 服务器:伺服器 自行车:單車 短信:短訊
+";
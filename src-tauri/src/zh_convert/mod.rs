@@ -0,0 +1,197 @@
+mod data;
+
+use once_cell::sync::Lazy;
+use std::collections::{HashMap, HashSet};
+
+type CharMap = HashMap<char, char>;
+
+struct PhraseMap {
+    map: HashMap<String, String>,
+    // 词组的最大字数，用于最长匹配
+    max_len: usize,
+}
+
+fn parse_chars(data: &str, reverse: bool, map: &mut CharMap) {
+    for pair in data.split_whitespace() {
+        let mut it = pair.chars();
+        if let (Some(a), Some(b)) = (it.next(), it.next()) {
+            let (k, v) = if reverse { (b, a) } else { (a, b) };
+            map.entry(k).or_insert(v);
+        }
+    }
+}
+
+fn parse_phrases(data: &str, reverse: bool, phrases: &mut PhraseMap) {
+    for pair in data.split_whitespace() {
+        if let Some((a, b)) = pair.split_once(':') {
+            let (k, v) = if reverse { (b, a) } else { (a, b) };
+            phrases.max_len = phrases.max_len.max(k.chars().count());
+            phrases
+                .map
+                .entry(k.to_string())
+                .or_insert_with(|| v.to_string());
+        }
+    }
+}
+
+fn char_map(list: &[(&str, bool)]) -> CharMap {
+    let mut map = CharMap::new();
+    for (data, reverse) in list {
+        parse_chars(data, *reverse, &mut map);
+    }
+    map
+}
+
+fn phrase_map(list: &[(&str, bool)]) -> PhraseMap {
+    let mut phrases = PhraseMap {
+        map: HashMap::new(),
+        max_len: 0,
+    };
+    for (data, reverse) in list {
+        parse_phrases(data, *reverse, &mut phrases);
+    }
+    phrases
+}
+
+static S2T_CHARS: Lazy<CharMap> = Lazy::new(|| char_map(&[(data::S2T_CHARS, false)]));
+static T2S_CHARS: Lazy<CharMap> =
+    Lazy::new(|| char_map(&[(data::S2T_CHARS, true), (data::T2S_EXTRA_CHARS, false)]));
+static TW_VARIANTS: Lazy<CharMap> = Lazy::new(|| char_map(&[(data::TW_VARIANTS, false)]));
+static HK_VARIANTS: Lazy<CharMap> = Lazy::new(|| char_map(&[(data::HK_VARIANTS, false)]));
+
+static S2T_PHRASES: Lazy<PhraseMap> = Lazy::new(|| phrase_map(&[(data::S2T_PHRASES, false)]));
+static T2S_PHRASES: Lazy<PhraseMap> =
+    Lazy::new(|| phrase_map(&[(data::T2S_PHRASES, false), (data::S2T_PHRASES, true)]));
+static S2TW_PHRASES: Lazy<PhraseMap> = Lazy::new(|| phrase_map(&[(data::S2TW_PHRASES, false)]));
+static TW2S_PHRASES: Lazy<PhraseMap> = Lazy::new(|| phrase_map(&[(data::S2TW_PHRASES, true)]));
+static S2HK_PHRASES: Lazy<PhraseMap> = Lazy::new(|| phrase_map(&[(data::S2HK_PHRASES, false)]));
+static HK2S_PHRASES: Lazy<PhraseMap> = Lazy::new(|| phrase_map(&[(data::S2HK_PHRASES, true)]));
+
+// 在繁体文本中同样常见的简体字（如“干涉”、“公里”），不能作为简体的判断依据
+const AMBIGUOUS_SIMPLIFIED: &str = "丑丰么于云仆伙余冲几准干里郁涂尸";
+// 在简体文本中同样常见的繁体字（如“著名”、“乾隆”）
+const AMBIGUOUS_TRADITIONAL: &str = "著乾";
+
+static SIMPLIFIED_MARKERS: Lazy<HashSet<char>> = Lazy::new(|| {
+    S2T_CHARS
+        .keys()
+        .copied()
+        .filter(|c| !AMBIGUOUS_SIMPLIFIED.contains(*c))
+        .collect()
+});
+static TRADITIONAL_MARKERS: Lazy<HashSet<char>> = Lazy::new(|| {
+    T2S_CHARS
+        .keys()
+        .copied()
+        .filter(|c| !AMBIGUOUS_TRADITIONAL.contains(*c))
+        .collect()
+});
+
+/// 按OpenCC的方式转换：先按词组最长匹配，未匹配的再逐字转换，最后替换地区用字
+pub struct Converter {
+    phrases: Vec<&'static PhraseMap>,
+    chars: Option<&'static CharMap>,
+    variants: Option<&'static CharMap>,
+}
+
+impl Converter {
+    /// 支持的转换模式，带p后缀的会同时转换地区常用词汇
+    pub fn from_mode(mode: &str) -> Option<Converter> {
+        let (phrases, chars, variants): (Vec<&'static PhraseMap>, _, _) = match mode {
+            "s2t" => (vec![&*S2T_PHRASES], Some(&*S2T_CHARS), None),
+            "s2tw" => (vec![&*S2T_PHRASES], Some(&*S2T_CHARS), Some(&*TW_VARIANTS)),
+            "s2twp" => (
+                vec![&*S2TW_PHRASES, &*S2T_PHRASES],
+                Some(&*S2T_CHARS),
+                Some(&*TW_VARIANTS),
+            ),
+            "s2hk" => (vec![&*S2T_PHRASES], Some(&*S2T_CHARS), Some(&*HK_VARIANTS)),
+            "s2hkp" => (
+                vec![&*S2HK_PHRASES, &*S2T_PHRASES],
+                Some(&*S2T_CHARS),
+                Some(&*HK_VARIANTS),
+            ),
+            "t2s" | "tw2s" | "hk2s" => (vec![&*T2S_PHRASES], Some(&*T2S_CHARS), None),
+            "tw2sp" => (vec![&*TW2S_PHRASES, &*T2S_PHRASES], Some(&*T2S_CHARS), None),
+            "hk2sp" => (vec![&*HK2S_PHRASES, &*T2S_PHRASES], Some(&*T2S_CHARS), None),
+            "t2tw" => (vec![], None, Some(&*TW_VARIANTS)),
+            "t2hk" => (vec![], None, Some(&*HK_VARIANTS)),
+            _ => return None,
+        };
+        Some(Converter {
+            phrases,
+            chars,
+            variants,
+        })
+    }
+
+    fn match_phrase(&self, chars: &[char]) -> Option<(usize, &'static str)> {
+        let max_len = self
+            .phrases
+            .iter()
+            .map(|p| p.max_len)
+            .max()
+            .unwrap_or(0)
+            .min(chars.len());
+        for len in (2..=max_len).rev() {
+            let key: String = chars[..len].iter().collect();
+            for phrases in &self.phrases {
+                if let Some(v) = phrases.map.get(&key) {
+                    return Some((len, v.as_str()));
+                }
+            }
+        }
+        None
+    }
+
+    pub fn convert(&self, text: &str) -> String {
+        let chars: Vec<char> = text.chars().collect();
+        let mut out = String::with_capacity(text.len());
+        let mut i = 0;
+        while i < chars.len() {
+            let c = chars[i];
+            if !c.is_ascii() {
+                if let Some((len, v)) = self.match_phrase(&chars[i..]) {
+                    out.push_str(v);
+                    i += len;
+                    continue;
+                }
+            }
+            out.push(self.chars.and_then(|m| m.get(&c)).copied().unwrap_or(c));
+            i += 1;
+        }
+        match self.variants {
+            Some(variants) => out
+                .chars()
+                .map(|c| variants.get(&c).copied().unwrap_or(c))
+                .collect(),
+            None => out,
+        }
+    }
+}
+
+/// 根据简体、繁体特征字的数量判断中文文本是简体还是繁体
+pub fn detect_variant(text: &str) -> &'static str {
+    let mut simplified = 0;
+    let mut traditional = 0;
+    for c in text.chars() {
+        if SIMPLIFIED_MARKERS.contains(&c) {
+            simplified += 1;
+        } else if TRADITIONAL_MARKERS.contains(&c) {
+            traditional += 1;
+        }
+    }
+    if traditional > simplified {
+        "zh_tw"
+    } else {
+        "zh_cn"
+    }
+}
+
+#[tauri::command]
+pub fn zh_convert(text: &str, mode: &str) -> Result<String, String> {
+    match Converter::from_mode(mode) {
+        Some(converter) => Ok(converter.convert(text)),
+        None => Err(format!("unsupported convert mode: {}", mode)),
+    }
+}
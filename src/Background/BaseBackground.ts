@@ -46,6 +46,11 @@ export interface IResponse<T> {
 	data: T
 }
 
+export interface IFileFilter {
+	name: string
+	extensions: string[]
+}
+
 export abstract class BaseBackground {
 	public abstract label: string
 	public abstract scaleFactor: number
@@ -73,6 +78,10 @@ export abstract class BaseBackground {
 
 	public abstract messageBox(message: string, options?: { title?: string, type?: 'info' | 'error' }): Promise<void>
 	public abstract ask(message: string, options?: { title?: string, type?: 'info' | 'warning' | 'error', okLabel?: string, cancelLabel?: string }): Promise<boolean>
+	/** 选择文件，取消时返回空字符串 */
+	public abstract openFileDialog(options?: { title?: string, filters?: IFileFilter[] }): Promise<string>
+	/** 选择保存的路径，取消时返回空字符串 */
+	public abstract saveFileDialog(options?: { title?: string, defaultPath?: string, filters?: IFileFilter[] }): Promise<string>
 
 	public abstract readClipboardText(): Promise<string>
 	public abstract writeClipboardText(content: string): Promise<boolean>
@@ -1,4 +1,4 @@
-import { BaseBackground, IEvent, ILogger, IMenuOptions, IRequestOptions, IResponse } from './BaseBackground'
+import { BaseBackground, IEvent, IFileFilter, ILogger, IMenuOptions, IRequestOptions, IResponse } from './BaseBackground'
 
 export class Electron extends BaseBackground {
     public label: string;
@@ -54,6 +54,12 @@ export class Electron extends BaseBackground {
     public ask(message: string, options?: { title?: string; type?: 'info' | 'error' | 'warning'; okLabel?: string; cancelLabel?: string; }): Promise<boolean> {
         throw new Error('Method not implemented.');
     }
+    public openFileDialog(options?: { title?: string; filters?: IFileFilter[]; }): Promise<string> {
+        throw new Error('Method not implemented.');
+    }
+    public saveFileDialog(options?: { title?: string; defaultPath?: string; filters?: IFileFilter[]; }): Promise<string> {
+        throw new Error('Method not implemented.');
+    }
     public readClipboardText(): Promise<string> {
         throw new Error('Method not implemented.');
     }
@@ -1,6 +1,7 @@
 import {
 	BaseBackground,
 	handlerLoggerMsg,
+	IFileFilter,
 	IMenuOptions,
 	IRequestOptions,
 	IResponse,
@@ -116,6 +117,15 @@ export class Tauri extends BaseBackground {
 		return await dialog.confirm(message, { title: options?.title, type: options.type, okLabel: options.okLabel, cancelLabel: options.cancelLabel }).catch(() => false)
 	}
 
+	async openFileDialog(options?: { title?: string, filters?: IFileFilter[] }): Promise<string> {
+		const path = await dialog.open({ title: options?.title, filters: options?.filters, multiple: false }).catch(() => null)
+		return typeof path === 'string' ? path : ''
+	}
+
+	async saveFileDialog(options?: { title?: string, defaultPath?: string, filters?: IFileFilter[] }): Promise<string> {
+		return await dialog.save({ title: options?.title, defaultPath: options?.defaultPath, filters: options?.filters }).catch(() => null) || ''
+	}
+
 	async readClipboardText() {
 		return await clipboard.readText()
 	}
@@ -4,10 +4,11 @@ import {
 	IRequestOptions,
 	ResponseType,
 	IResponse,
-	UnlistenFn
+	UnlistenFn,
+	IFileFilter
 } from './BaseBackground.ts'
 
-export type { IMenuItem, IMenuOptions, IRequestOptions, ResponseType, IResponse, UnlistenFn }
+export type { IMenuItem, IMenuOptions, IRequestOptions, ResponseType, IResponse, UnlistenFn, IFileFilter }
 
 
 let base: BaseBackground
@@ -120,6 +121,14 @@ export function ask(message: string, options?: {
 	return base.ask(message, options)
 }
 
+export function openFileDialog(options?: { title?: string, filters?: IFileFilter[] }) {
+	return base.openFileDialog(options)
+}
+
+export function saveFileDialog(options?: { title?: string, defaultPath?: string, filters?: IFileFilter[] }) {
+	return base.saveFileDialog(options)
+}
+
 export function readClipboardText() {
 	return base.readClipboardText()
 }
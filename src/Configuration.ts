@@ -1,5 +1,5 @@
 import { reactive, watch, UnwrapNestedRefs } from 'vue'
-import { IOcrServiceConfig, IServiceConfig, ITransServiceConfig } from './types'
+import { IOcrServiceConfig, IPromptPreset, IServiceConfig, ITransServiceConfig } from './types'
 import { debug } from './Logger.ts'
 import { plugins as TransPlugins } from './Plugins/Translator'
 import { plugins as OcrPlugins } from './Plugins/OCR'
@@ -21,6 +21,10 @@ export class Configuration {
 	public selection_translate = ''
 	/** 截图识别快捷键 */
 	public screenshot_recognizer = ''
+	/** 变量命名快捷键 */
+	public naming = ''
+	/** 变量命名缩写词典，格式为 单词:缩写 */
+	public naming_abbreviations: string[] = []
 
 	/** OCR类型 */
 	public ocr_type: OcrType = 'round'
@@ -31,6 +35,8 @@ export class Configuration {
 
 	/** 语言检测服务 */
 	public detect_type: DetectType = 'concurrent'
+	/** 本地语种识别后端 */
+	public lang_detect_backend: 'whichlang' | 'lingua' | string = 'whichlang'
 	/** 默认的目标语种 */
 	public to: string = 'zh_cn'
 	/** 第二目标语种 */
@@ -38,6 +44,10 @@ export class Configuration {
 
 	/** 是否只使用词典服务 */
 	public only_dict = false
+	/** 是否合并多个词典服务的结果 */
+	public merge_dict = false
+	/** 合并词典结果时的服务优先级，未列出的服务按服务列表顺序排在后面 */
+	public dict_merge_priority: string[] = []
 	/** 是否自动清空内容 */
 	public auto_clear = false
 	/** 自动复制内容到剪切板 */
@@ -49,6 +59,16 @@ export class Configuration {
 	public trans_timeout = 5000
 	/** 默认的翻译服务重试次数 */
 	public trans_retry_count = 1
+	/** 大模型翻译服务逐段输出结果 */
+	public trans_stream = false
+	/** 提示词改写的自定义预设，id 与内置预设相同时覆盖内置预设 */
+	public prompt_presets: IPromptPreset[] = []
+	/** 提示词改写默认使用的大模型服务，为空时使用第一个可用的服务 */
+	public prompt_service = ''
+	/** 翻译前查找翻译记忆 */
+	public tm_enable = true
+	/** 翻译记忆的最低匹配度（0 - 100） */
+	public tm_threshold = 75
 	/** 默认的OCR服务超时时间 */
 	public ocr_timeout = 5000
 	/** 默认的OCR服务重试次数 */
@@ -65,9 +85,20 @@ export class Configuration {
 	public cache_max_count = 0
 	/** 是否使用缓存 */
 	public use_cache = true
+	/** 翻译缓存有效期(小时) */
+	public trans_cache_ttl = 168
+	/** 翻译缓存最大占用空间(MB) */
+	public trans_cache_size = 32
 	/** 清理缓存时，是否保留词典结果 */
 	public reserve_word = false
 
+	/** 查询多少次后自动加入生词本，为0时不自动加入 */
+	public vocab_auto_star = 3
+	/** 是否每天提醒复习生词 */
+	public vocab_notify = false
+	/** 每天提醒复习的时间(时) */
+	public vocab_notify_hour = 20
+
 	/** 是否开启ahk功能 */
 	public enable_ahk = false
 
@@ -0,0 +1,15 @@
+import { emit } from '../../Background'
+import { IBaseSelectionTranslator } from '../../types'
+
+export const Naming: IBaseSelectionTranslator = {
+	name: 'naming',
+	label: '变量命名',
+	icon: 'code',
+	description: '将选中的短语转换为变量名，选择后替换选中内容',
+	//	只处理较短的短语
+	Verify: text => {
+		const v = text.trim()
+		return !!v && v.length <= 50 && !v.includes('\n')
+	},
+	Invoke: async text => emit('translator://naming', text)
+}
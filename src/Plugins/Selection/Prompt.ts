@@ -0,0 +1,16 @@
+import { emit } from '../../Background'
+import { IBaseSelectionTranslator, IPromptPreset } from '../../types'
+
+/** 提示词改写预设对应的划词操作名称前缀，与托盘菜单、快捷键一致 */
+export const PromptPrefix = 'prompt:'
+
+export function createPromptPlugin(preset: IPromptPreset): IBaseSelectionTranslator {
+	return {
+		name: PromptPrefix + preset.id,
+		label: preset.name,
+		icon: 'prompt',
+		description: '使用大模型按「' + preset.name + '」预设改写选中的文本，结果显示在翻译窗口中',
+		Verify: text => !!text.trim(),
+		Invoke: async text => emit('translator://prompt', { preset: preset.id, name: preset.name, text })
+	}
+}
@@ -1,9 +1,20 @@
-import { IBaseSelectionTranslator } from '../../types'
+import { invoke } from '../../Background'
+import { IBaseSelectionTranslator, IPromptPreset } from '../../types'
 import {Translate} from './Translate'
 import {Copy} from './Copy'
 import {Link} from './Link'
 import {Baidu} from './Baidu'
 import {Bing} from './Bing'
 import {Google} from './Google'
+import {Naming} from './Naming'
+import { createPromptPlugin, PromptPrefix } from './Prompt'
 
-export const plugins: IBaseSelectionTranslator[] = [Translate, Copy, Link, Baidu, Bing, Google]
+export const plugins: IBaseSelectionTranslator[] = [Translate, Copy, Link, Baidu, Bing, Google, Naming]
+
+/** 重新加载提示词改写预设对应的划词操作 */
+export async function loadPromptPlugins() {
+	const presets = await invoke<IPromptPreset[]>('prompt_presets')
+	const list = plugins.filter(x => !x.name.startsWith(PromptPrefix))
+	list.push(...presets.filter(x => x.selection).map(createPromptPlugin))
+	plugins.splice(0, plugins.length, ...list)
+}
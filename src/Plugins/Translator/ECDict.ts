@@ -0,0 +1,18 @@
+import { invoke } from '../../Background'
+import { IBaseTransService, IDictResult } from '../../types'
+
+export const ECDict: IBaseTransService = {
+	name: 'ecdict',
+	label: '本地英汉词典',
+	icon: '/icon/icon.svg',
+	explain: '基于ECDICT的离线英汉词典，需先下载ECDICT发布的stardict.db，重命名为ecdict.db放入配置目录的dictionaries文件夹。不需要联网，仅支持英文单词查询，未收录的词形会自动还原为原形后查询',
+	languages: {
+		en: 'en',
+		zh_cn: 'zh_cn'
+	},
+	ui: [],
+	async Dict(_params: Record<string, any>, text: string, from: string, to: string): Promise<IDictResult> {
+		if (from !== 'en' || to !== 'zh_cn') throw new Error('仅支持英文查询中文释义')
+		return await invoke<IDictResult>('ecdict_lookup', { text })
+	}
+}
@@ -1,5 +1,6 @@
 import { fetch } from '../../Background'
 import { IBaseTransService } from '../../types'
+import { fillPrompt } from '../../Utils'
 
 const DefaultURL = 'https://generativelanguage.googleapis.com/v1beta/models/gemini-pro:generateContent'
 const DefaultPrompt = JSON.stringify([
@@ -49,11 +50,11 @@ export const GeminiPro: IBaseTransService = {
 		{ name: 'url', label: '接口地址', type: 'input', default: DefaultURL, explain: 'Gemini Pro Api接口地址，若无必要请勿修改' },
 		{ name: 'apiKey', label: 'Api Key', type: 'password' },
 		{
-			name: 'prompt', label: '提示词', type: 'code', default: DefaultPrompt, explain: '通过自定义Prompt自定义AI行为, $text $from $to 将会被替换为 待翻译文本，源语言，目标语言，若无必要请勿修改'
+			name: 'prompt', label: '提示词', type: 'code', default: DefaultPrompt, explain: '通过自定义Prompt自定义AI行为, $text $from $to $glossary 将会被替换为 待翻译文本，源语言，目标语言，术语表，若无必要请勿修改'
 		}
 	],
 	async Translate(params: Record<string, any>,text: string, from: string, to: string): Promise<string> {
-		let { url, apiKey, prompt } = params
+		let { url, apiKey, prompt, glossary } = params
 		if (!apiKey) throw 'Api Key is required'
 		if (!url) url = DefaultURL
 		if (!prompt) prompt = DefaultPrompt
@@ -64,10 +65,7 @@ export const GeminiPro: IBaseTransService = {
 			promptList = promptList.map((item: Record<string, any>) => ({
 				...item,
 				parts: [{
-					text: item.parts[0].text
-						.replaceAll('$text', text)
-						.replaceAll('$from', from)
-						.replaceAll('$to', to)
+					text: fillPrompt(item.parts[0].text, text, from, to, glossary)
 				}]
 			}))
 		} catch (e) {
@@ -27,7 +27,10 @@ export const LanguageZh = {
 	"fa": "波斯语",
 	"sv": "瑞典语",
 	"pl": "波兰语",
-	"nl": "荷兰语"
+	"nl": "荷兰语",
+	"uk": "乌克兰语",
+	"el": "希腊语",
+	"he": "希伯来语"
 }
 
 export const LanguageList = Object.keys(LanguageZh)
\ No newline at end of file
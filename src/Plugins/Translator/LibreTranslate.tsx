@@ -0,0 +1,100 @@
+import { ref } from 'vue'
+import { invoke } from '../../Background'
+import { IBaseTransService } from '../../types'
+import { ElButton } from 'element-plus'
+import 'element-plus/es/components/button/style/css'
+
+const DefaultURL = 'http://127.0.0.1:5000'
+const DefaultCommand = 'libretranslate'
+const DefaultArgs = '--host 127.0.0.1 --port 5000'
+
+const running = ref(false)
+const loading = ref(false)
+const message = ref('')
+
+async function refreshState() {
+	running.value = await invoke<boolean>('engine_state')
+}
+
+refreshState().catch(() => {})
+
+async function startEngine(params: Record<string, any>) {
+	loading.value = true
+	message.value = ''
+	try {
+		await invoke('start_engine', { command: params.command || DefaultCommand, args: params.args ?? DefaultArgs })
+		// 查询语言包，同时等待引擎就绪
+		const languages = await invoke<{ code: string, targets: string[] }[]>('libre_languages', { params: { ...params, engine: true } })
+		message.value = '已安装的语言包：' + languages.map(x => `${ x.code } → ${ x.targets.filter(t => t !== x.code).join('/') }`).join('，')
+	} catch (e) {
+		message.value = String(e)
+	} finally {
+		loading.value = false
+		await refreshState()
+	}
+}
+
+async function stopEngine() {
+	await invoke('stop_engine')
+	message.value = ''
+	await refreshState()
+}
+
+export const LibreTranslate: IBaseTransService = {
+	name: 'libre-translate',
+	label: '离线翻译引擎',
+	icon: '/icon/custom.svg',
+	explain: '使用 LibreTranslate 兼容接口的本地翻译引擎（如基于 Argos 的 libretranslate），无需联网，没有直接的语言包时会经英文中转',
+	helpLink: 'https://github.com/LibreTranslate/LibreTranslate',
+	languages: {
+		auto: 'auto',
+		zh_cn: 'zh',
+		zh_tw: 'zt',
+		en: 'en',
+		ja: 'ja',
+		ko: 'ko',
+		fr: 'fr',
+		es: 'es',
+		ru: 'ru',
+		de: 'de',
+		it: 'it',
+		tr: 'tr',
+		pt_pt: 'pt',
+		pt_br: 'pt',
+		vi: 'vi',
+		id: 'id',
+		th: 'th',
+		ms: 'ms',
+		ar: 'ar',
+		hi: 'hi',
+		nb_no: 'nb',
+		fa: 'fa',
+		sv: 'sv',
+		pl: 'pl',
+		nl: 'nl'
+	},
+	ui: [
+		{ name: 'url', label: '接口地址', type: 'input', default: DefaultURL, explain: 'LibreTranslate 兼容接口的地址' },
+		{ name: 'apiKey', label: 'Api Key', type: 'password', explain: '服务未启用鉴权时无需填写' },
+		{ name: 'engine', label: '启动引擎', type: 'checkbox', default: false, explain: '由应用启动并守护本地引擎进程，意外退出时自动重启，应用退出时关闭' },
+		{ name: 'command', label: '启动命令', type: 'input', default: DefaultCommand },
+		{ name: 'args', label: '启动参数', type: 'input', default: DefaultArgs, explain: '监听地址需与接口地址一致' },
+		{
+			name: 'state', label: '引擎状态',
+			components: (params: Record<string, any>) => <div class="w-full">
+				<div class="flex items-center w-full">
+					<span class="mr-3">{ running.value ? '运行中' : '未运行' }</span>
+					<ElButton loading={ loading.value } onClick={ () => startEngine(params) }>{ running.value ? '检查' : '启动' }</ElButton>
+					<ElButton class="ml-2" disabled={ !running.value } onClick={ stopEngine }>停止</ElButton>
+				</div>
+				{ message.value && <div class="text-[var(--placeholder)] text-wrap">{ message.value }</div> }
+			</div>
+		}
+	],
+	async Detect(params: Record<string, any>, text: string): Promise<string> {
+		return await invoke<string>('libre_detect', { params, text })
+	},
+	async Translate(params: Record<string, any>, text: string, from: string, to: string): Promise<string> {
+		return await invoke<string>('libre_translate', { params, text, from, to })
+	}
+}
@@ -0,0 +1,117 @@
+import { ref } from 'vue'
+import { invoke } from '../../Background'
+import { IBaseTransService } from '../../types'
+import { ElButton, ElOption, ElSelect } from 'element-plus'
+import 'element-plus/es/components/button/style/css'
+import 'element-plus/es/components/select/style/css'
+import 'element-plus/es/components/option/style/css'
+
+const DefaultURL = 'http://127.0.0.1:11434'
+const DefaultSystemPrompt = 'You are a professional translation engine, please translate the text into a colloquial, professional, elegant and fluent content, without the style of machine translation. You must only translate the text content, never interpret it, and only output the translation.'
+const DefaultPrompt = 'Translate from {from} into {to}:\n"""\n{text}\n"""'
+
+const models = ref<string[]>([])
+const loading = ref(false)
+const message = ref('')
+
+async function loadModels(params: Record<string, any>) {
+	loading.value = true
+	message.value = ''
+	try {
+		models.value = await invoke<string[]>('local_llm_models', { params })
+		if (!models.value.length) message.value = '服务中没有可用的模型'
+	} catch (e) {
+		message.value = `获取模型列表失败：${ e }`
+	} finally {
+		loading.value = false
+	}
+}
+
+async function warmUp(params: Record<string, any>) {
+	loading.value = true
+	message.value = ''
+	try {
+		await invoke('local_llm_warm_up', { params })
+		message.value = '模型已加载'
+	} catch (e) {
+		message.value = `加载模型失败：${ e }`
+	} finally {
+		loading.value = false
+	}
+}
+
+export const LocalLLM: IBaseTransService = {
+	name: 'local-llm',
+	label: '本地大模型',
+	icon: '/icon/custom.svg',
+	explain: '使用本机或内网部署的大模型翻译，支持 Ollama 及 llama.cpp server、vLLM 等 OpenAI 兼容服务，文本不会发送到云端',
+	languages: {
+		auto: 'Auto',
+		zh_cn: 'Simplified Chinese',
+		zh_tw: 'Traditional Chinese',
+		yue: 'Cantonese',
+		ja: 'Japanese',
+		en: 'English',
+		ko: 'Korean',
+		fr: 'French',
+		es: 'Spanish',
+		ru: 'Russian',
+		de: 'German',
+		it: 'Italian',
+		tr: 'Turkish',
+		pt_pt: 'Portuguese',
+		pt_br: 'Brazilian Portuguese',
+		vi: 'Vietnamese',
+		id: 'Indonesian',
+		th: 'Thai',
+		ms: 'Malay',
+		ar: 'Arabic',
+		hi: 'Hindi',
+		mn_mo: 'Mongolian',
+		mn_cy: 'Mongolian(Cyrillic)',
+		km: 'Khmer',
+		nb_no: 'Norwegian Bokmål',
+		nn_no: 'Norwegian Nynorsk',
+		fa: 'Persian',
+		sv: 'Swedish',
+		pl: 'Polish',
+		nl: 'Dutch'
+	},
+	ui: [
+		{
+			name: 'api', label: '接口类型', type: 'select', default: 'ollama-chat',
+			dict: [
+				{ label: 'Ollama /api/chat', value: 'ollama-chat' },
+				{ label: 'Ollama /api/generate', value: 'ollama-generate' },
+				{ label: 'OpenAI 兼容接口', value: 'openai' }
+			]
+		},
+		{ name: 'url', label: '服务地址', type: 'input', default: DefaultURL, explain: 'Ollama 默认为 http://127.0.0.1:11434，OpenAI 兼容服务填写根地址或 /v1 地址' },
+		{ name: 'apiKey', label: 'Api Key', type: 'password', explain: '服务未启用鉴权时无需填写' },
+		{
+			name: 'model', label: '模型', type: 'input',
+			components: (params: Record<string, any>) => <div class="w-full">
+				<div class="flex w-full">
+					<ElSelect v-model={ params['model'] } class="flex-grow" filterable allow-create default-first-option
+						placeholder="选择或输入模型名称" onVisibleChange={ (visible: boolean) => visible && !models.value.length && loadModels(params) }>
+						{ models.value.map(x => <ElOption key={ x } label={ x } value={ x }/>) }
+					</ElSelect>
+					<ElButton class="ml-2" loading={ loading.value } onClick={ () => loadModels(params) }>刷新</ElButton>
+					<ElButton class="ml-2" disabled={ !params['model'] || loading.value } onClick={ () => warmUp(params) }>预加载</ElButton>
+				</div>
+				{ message.value && <div class="text-[var(--placeholder)] text-wrap">{ message.value }</div> }
+			</div>
+		},
+		{ name: 'systemPrompt', label: '系统提示词', type: 'textarea', default: DefaultSystemPrompt, minRows: 3, maxRows: 6 },
+		{
+			name: 'prompt', label: '提示词模板', type: 'textarea', default: DefaultPrompt, minRows: 3, maxRows: 8,
+			explain: '{text} 为原文，{from} 为原文语种，{to} 为目标语种，{glossary} 为术语表，未使用时追加到末尾'
+		},
+		{ name: 'temperature', label: '温度', type: 'input', default: '0', explain: '取值 0 - 2，越低翻译结果越稳定' },
+		{ name: 'contextLength', label: '上下文长度', type: 'input', explain: '仅 Ollama 有效，为空时使用模型默认值，翻译长文本时可适当调大' },
+		{ name: 'keepAlive', label: '保持加载', type: 'input', default: '30m', explain: '仅 Ollama 有效，翻译后模型保持加载的时长，如 30m、2h，-1 为一直保持' }
+	],
+	async Translate(params: Record<string, any>, text: string, from: string, to: string): Promise<string> {
+		return await invoke<string>('local_llm_translate', { params, text, from, to })
+	}
+}
@@ -1,5 +1,6 @@
 import { fetch } from '../../Background'
 import { IBaseTransService } from '../../types'
+import { fillPrompt } from '../../Utils'
 
 const DefaultURL = 'https://api.openai.com/v1/chat/completions'
 const DefaultModel = 'gpt-3.5-turbo'
@@ -51,7 +52,7 @@ export const OpenAI: IBaseTransService = {
 		{ name: 'prompt', label: '提示词', type: 'code', default: DefaultPrompt }
 	],
 	async Translate(params: Record<string, any>, text: string, from: string, to: string): Promise<string> {
-		let { url, apiKey, model, prompt } = params
+		let { url, apiKey, model, prompt, glossary } = params
 		if (!apiKey) throw new Error('Api Key is required')
 		if (!url) url = DefaultURL
 		if (!model) model = DefaultModel
@@ -62,10 +63,7 @@ export const OpenAI: IBaseTransService = {
 			promptList = JSON.parse(prompt).map((item: Record<string, any>) => {
 				return {
 					...item,
-					content: item.content
-						.replaceAll('$text', text)
-						.replaceAll('$from', from)
-						.replaceAll('$to', to)
+					content: fillPrompt(item.content, text, from, to, glossary)
 				}
 			})
 		} catch {
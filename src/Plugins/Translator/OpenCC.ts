@@ -0,0 +1,39 @@
+import { invoke } from '../../Background'
+import { IBaseTransService } from '../../types'
+
+export const OpenCC: IBaseTransService = {
+	name: 'opencc',
+	label: '简繁转换',
+	icon: '/icon/icon.svg',
+	explain: '本地简繁转换，不需要联网，仅支持简体中文与繁体中文之间的互相转换',
+	languages: {
+		zh_cn: 'zh_cn',
+		zh_tw: 'zh_tw'
+	},
+	ui: [
+		{
+			name: 'region', label: '繁体地区', type: 'select', default: 'tw',
+			dict: [
+				{ label: '通用繁体', value: 't' },
+				{ label: '台湾', value: 'tw' },
+				{ label: '香港', value: 'hk' }
+			]
+		},
+		{ name: 'phrase', label: '转换地区词汇', type: 'checkbox', default: true, explain: '如“软件”转换为“軟體”，仅对台湾、香港地区生效' }
+	],
+	async Translate(params: Record<string, any>, text: string, from: string, to: string): Promise<string> {
+		if (from === to) return text
+		const region = params.region || 'tw'
+		const phrase = params.phrase && region !== 't' ? 'p' : ''
+		// 转换方向只取决于目标语种，源语种在调用前已经过语种支持校验
+		let mode: string
+		if (to === 'zh_tw') {
+			mode = region === 't' ? 's2t' : `s2${ region }${ phrase }`
+		} else if (to === 'zh_cn') {
+			mode = region === 't' ? 't2s' : `${ region }2s${ phrase }`
+		} else {
+			throw new Error('仅支持简体中文与繁体中文之间的转换')
+		}
+		return await invoke<string>('zh_convert', { text, mode })
+	}
+}
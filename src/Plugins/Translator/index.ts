@@ -1,5 +1,5 @@
 import { invoke } from '../../Background'
-import { IBaseTransService, IDictResult, ITransServiceConfig } from '../../types'
+import { IBaseTransService, IDictResult, IGlossaryPrepared, ITransServiceConfig } from '../../types'
 
 export * from './Language'
 
@@ -11,13 +11,17 @@ import { Tencent } from './Tencent.tsx'
 import { Bing } from './Bing'
 import { OpenAI } from './OpenAI'
 import { GeminiPro } from './GeminiPro'
+import { LocalLLM } from './LocalLLM.tsx'
+import { LibreTranslate } from './LibreTranslate.tsx'
 import { DeepL } from './DeepL'
 import { AlibabaFree } from './AlibabaFree'
 import { caiyun } from './CaiYun'
+import { OpenCC } from './OpenCC'
+import { ECDict } from './ECDict'
 import { generateParams, racePromisesIgnoreErrors } from '../../Utils'
 import { DetectType } from '../../Configuration'
 
-export const plugins: IBaseTransService[] = [Youdao, Baidu, Tencent, Google, GoogleFree, Bing, OpenAI, GeminiPro, DeepL, caiyun, AlibabaFree]
+export const plugins: IBaseTransService[] = [Youdao, Baidu, Tencent, Google, GoogleFree, Bing, OpenAI, GeminiPro, LocalLLM, LibreTranslate, DeepL, caiyun, AlibabaFree, OpenCC, ECDict]
 
 function detectLanguage(service: IBaseTransService, lang: string) {
 	for (const key of Object.keys(service.languages)) {
@@ -110,13 +114,17 @@ function textConvertTimeout(
 	config: ITransServiceConfig,
 	text: string,
 	from: string,
-	to: string
+	to: string,
+	glossary = ''
 ): Promise<IDictResult | string> {
 	let service = config.service
 	const params = generateParams(
 		service.ui,
 		JSON.parse(JSON.stringify(config.params))
 	)
+	if (glossary) {
+		params.glossary = glossary
+	}
 	const time = config.timeout || 30 * 1000;
 	return new Promise((resolve, reject) => {
 		let isEnd = false
@@ -138,13 +146,62 @@ function textConvertTimeout(
 	})
 }
 
+/** 单个英文单词查询失败时，依次使用还原后的原形重新查询，并在结果缺少词形时补充本地生成的词形变化 */
+async function dictWithLemma(
+	config: ITransServiceConfig,
+	text: string,
+	from: string,
+	to: string
+): Promise<IDictResult | string> {
+	try {
+		return await textConvertTimeout('Dict', config, text, from, to)
+	} catch (e) {
+		const word = text.trim()
+		if (!/^[a-zA-Z]+$/.test(word)) throw e
+		const lemmas = await invoke<{ lemma: string, inflection: string }[]>('lemmatize', { word })
+		const tried = new Set([word.toLowerCase()])
+		for (const { lemma } of lemmas) {
+			if (tried.has(lemma)) continue
+			tried.add(lemma)
+			try {
+				const result = await textConvertTimeout('Dict', config, lemma, from, to)
+				if (typeof result !== 'string' && !result.wfs?.length) {
+					result.wfs = await invoke('inflect', { word: lemma, traits: result.explanations?.map(x => x.trait) ?? [] })
+				}
+				return result
+			} catch {}
+		}
+		throw e
+	}
+}
+
+// 词典结果没有音标时，使用本地发音引擎补充英式、美式音标
+async function withPhonetic(result: IDictResult | string): Promise<IDictResult | string> {
+	if (typeof result === 'string' || result.phonetic || result.pronunciations?.some(x => x.symbol)) return result
+	if (!/^[a-zA-Z][a-zA-Z' -]*$/.test(result.text.trim())) return result
+	try {
+		const { us, uk } = await invoke<{ us: string, uk: string }>('phonetic', { text: result.text })
+		result.pronunciations = [
+			{ region: '美', symbol: us, voice: '' },
+			{ region: '英', symbol: uk, voice: '' }
+		]
+	} catch {}
+	return result
+}
+
 export async function textConvert(
 	config: ITransServiceConfig,
 	text: string,
 	from: string,
 	to: string,
-	onlyDict: boolean
+	onlyDict: boolean,
+	glossary?: IGlossaryPrepared
 ): Promise<IDictResult | string> {
+	//	文本翻译使用术语表处理后的原文，未使用占位符的大模型服务通过参数 glossary 注入术语说明
+	const translate = () => textConvertTimeout(
+		'Translate', config, glossary?.text ?? text, from, to,
+		glossary?.terms.every(x => !x.placeholder) ? glossary.prompt : ''
+	)
 	if (!config.service) {
 		config.service = plugins.find(x => x.name === config.name)
 	}
@@ -153,7 +210,7 @@ export async function textConvert(
 	}
 	if (config.service?.Dict) {
 		try {
-			return textConvertTimeout('Dict', config, text, from, to)
+			return await withPhonetic(await dictWithLemma(config, text, from, to))
 		} catch (dictErr) {
 			if (onlyDict) {
 				throw new Error('词典翻译失败：' + dictErr)
@@ -162,7 +219,7 @@ export async function textConvert(
 				throw new Error('该服务不提供文本翻译功能')
 			}
 			try {
-				return textConvertTimeout('Translate', config, text, from, to)
+				return translate()
 			} catch (e) {
 				throw new Error('文本翻译失败：' + e)
 			}
@@ -174,6 +231,6 @@ export async function textConvert(
 		if (!config.service.Translate) {
 			throw new Error('该服务不提供文本翻译功能')
 		}
-		return textConvertTimeout('Translate', config, text, from, to)
+		return translate()
 	}
 }
@@ -11,7 +11,10 @@ import {
 } from '../Background'
 import { IConfiguration, configuration, generateTransConfig, generateOcrConfig } from '../Configuration.ts'
 import { detect, LanguageZh, textConvert, invokeLocalDetect } from '../Plugins/Translator'
-import { IDictResult, ITransServiceConfig } from '../types'
+import {
+	IDictResult, IGlossaryPrepared, IGlossaryViolation, IMergedDictResult, IPromptResult, IServiceResult, ITmMatch,
+	ITransServiceConfig, IVocabWord
+} from '../types'
 import { CacheHelper } from '../CacheHelper.ts'
 import { uuid } from '../Utils.ts'
 import TargetView from './TargetView.vue'
@@ -34,7 +37,25 @@ class Store {
 	/** 识别到的语种 */
 	public detect_language: Ref<string> = ref('')
 
+	/** 变量命名候选 */
+	public namingCandidates: Ref<{ style: string, value: string }[]> = ref([])
+	/** 提示词改写的结果 */
+	public promptResult: Ref<IPromptResult | null> = ref(null)
+	/** 多个词典服务合并后的结果 */
+	public mergedDict: Ref<IMergedDictResult | null> = ref(null)
+	/** 翻译记忆中匹配的句段 */
+	public memoryMatches: Ref<ITmMatch[]> = ref([])
+	/** 各服务译文中未按术语表翻译的术语，键为服务配置的 id */
+	public glossaryViolations: Ref<Record<string, IGlossaryViolation[]>> = ref({})
+
+	/** 当前查询的单词在生词本中的记录 */
+	public vocabWord: Ref<IVocabWord | null> = ref(null)
+	/** 最近一次翻译的非单词文本，作为之后查词的语境 */
+	private lastContext = ''
+
 	public serviceEl: Map<string, InstanceType<typeof TargetView>> = new Map()
+	/** 后端已实现的翻译服务 */
+	private backendServices: Promise<{ name: string }[]> | null = null
 
 	public gorupId: string = ''
 
@@ -42,10 +63,6 @@ class Store {
 
 	async init(conf: IConfiguration) {
 		this.cache = new CacheHelper('translate')
-		this.cache.setConfig(conf.cache_day, conf.cache_max_count, conf.reserve_word)
-		if (conf.enable_cache) {
-			await this.cache.start()
-		}
 		this.target.value = conf.to
 		const self = this
 
@@ -56,24 +73,10 @@ class Store {
 					setAlwaysOnTop(value)
 					return
 				case 'cache_day':
-					self.cache.max_day = value
-					return
 				case 'cache_max_count':
-					self.cache.max_count = value
-					return
 				case 'reserve_word':
-					self.cache.reserve_word = value
+					self.cache.clear().catch(() => {})
 					return
-				case 'enable_cache': {
-					if (value) {
-						self.cache.start().catch(() => {
-						})
-					} else {
-						self.cache.stop().catch(() => {
-						})
-					}
-					return
-				}
 			}
 		})
 
@@ -99,6 +102,32 @@ class Store {
 			await self.translate()
 		})
 
+		await listen<string>('translator://naming', async function(text) {
+			if (self.isTranslating.value || self.isRecogning.value) return
+			text = text?.trim()
+			if (!text) return
+			if (!await isVisible()) {
+				await invoke('show_trans_win', { focus: true })
+			}
+			await self.clear()
+			self.text.value = text
+			await self.naming(text)
+		})
+
+		type IPromptPayload = { preset: string, name: string, text: string, app?: string }
+		await listen<IPromptPayload>('translator://prompt', async function({ preset, name, text, app }) {
+			if (self.isTranslating.value || self.isRecogning.value) return
+			text = text?.trim()
+			if (!text) return
+			if (!await isVisible()) {
+				//	不获取焦点，保持来源应用为活动窗口
+				await invoke('show_trans_win', { focus: false })
+			}
+			await self.clear()
+			self.text.value = text
+			await self.prompt(preset, name, text, app)
+		})
+
 		window.addEventListener('blur', async () => {
 			try {
 				if (await invoke('active_window_is_self')) {
@@ -144,6 +173,15 @@ class Store {
 	async clear() {
 		this.text.value = ''
 		this.detect_language.value = ''
+		this.namingCandidates.value = []
+		this.mergedDict.value = null
+		this.glossaryViolations.value = {}
+		this.memoryMatches.value = []
+		this.vocabWord.value = null
+		if (this.promptResult.value?.loading) {
+			invoke('translate_cancel', { id: this.promptResult.value.id }).catch(() => {})
+		}
+		this.promptResult.value = null
 		for (const [_key, target] of this.serviceEl) {
 			await target.clear()
 		}
@@ -155,10 +193,18 @@ class Store {
 		if (this.src.value === 'auto') {
 			await this.language_detect(this.text.value)
 		}
+		//  先查找翻译记忆，命中的句段在调用翻译服务前显示
+		await this.lookupMemory()
 		const self = this
 		let total = this.serviceEl.size
 		this.gorupId = uuid()
 		let first = false
+		this.mergedDict.value = null
+		this.vocabWord.value = null
+		const configs = new Map(
+			configuration.trans_services.map(generateTransConfig).filter(Boolean).map(x => [x.id || x.name, x])
+		)
+		const dicts: { source: string, label: string, result: IDictResult }[] = []
 
 		function handleResult({ id, data }: { id: string, data: string | IDictResult }) {
 			if (!data) return
@@ -177,15 +223,144 @@ class Store {
 			total--
 			if (total === 0) {
 				self.isTranslating.value = false
+				self.mergeDict(dicts).catch(() => {})
+				self.recordVocab(dicts.map(x => x.result)).catch(() => {})
 			}
 		}
 
-		for (const el of this.serviceEl.values()) {
+		for (const [key, el] of this.serviceEl) {
 			if (!el?.translate) {
 				handleEnd()
 				continue
 			}
-			el.translate().then(handleResult).finally(handleEnd)
+			el.translate().then(res => {
+				const config = configs.get(key)
+				if (config && res?.data && typeof res.data !== 'string') {
+					dicts.push({ source: config.name, label: config.label, result: res.data })
+				}
+				handleResult(res)
+			}).finally(handleEnd)
+		}
+	}
+
+	/** 翻译记忆使用的语种对，源语种与目标语种相同时使用第二目标语种 */
+	private memoryLanguages() {
+		const from = this.src.value === 'auto' ? this.detect_language.value : this.src.value
+		const to = from === this.target.value ? configuration.to2 : this.target.value
+		return { from, to }
+	}
+
+	/** 按当前语种查找翻译记忆 */
+	async lookupMemory() {
+		this.memoryMatches.value = []
+		if (!configuration.tm_enable) return
+		const { from, to } = this.memoryLanguages()
+		try {
+			this.memoryMatches.value = await invoke<ITmMatch[]>('tm_lookup', { text: this.text.value, from, to })
+		} catch {}
+		if (this.memoryMatches.value.length) {
+			await this.resetSize()
+		}
+	}
+
+	/** 将译文存入翻译记忆 */
+	async saveMemory(target: string, origin: string) {
+		const { from, to } = this.memoryLanguages()
+		await invoke('tm_add', { source: this.text.value, target, from, to, origin })
+	}
+
+	/** 记录查词到生词本，没有词典结果时将原文作为之后查词的语境 */
+	async recordVocab(results: IDictResult[]) {
+		const text = this.text.value.trim()
+		if (!results.length) {
+			this.lastContext = text
+			return
+		}
+		const { from, to } = this.memoryLanguages()
+		const result = results.find(x => x.explanations?.length) || results[0]
+		this.vocabWord.value = await invoke<IVocabWord>('vocab_lookup', {
+			word: text, from, to, result, context: this.lastContext
+		})
+	}
+
+	/** 加入或移出生词本 */
+	async toggleVocab() {
+		const word = this.vocabWord.value
+		if (!word) return
+		this.vocabWord.value = await invoke<IVocabWord | null>('vocab_star', { id: word.id, starred: !word.starred })
+	}
+
+	/** 合并词典：两个以上的服务返回词典结果时，按优先级合并为一个词条 */
+	async mergeDict(dicts: { source: string, label: string, result: IDictResult }[]) {
+		if (!configuration.merge_dict || dicts.length < 2) return
+		const priority = [
+			...configuration.dict_merge_priority,
+			...configuration.trans_services.map(x => x.name)
+		]
+		this.mergedDict.value = await invoke<IMergedDictResult>('dict_merge', { results: dicts, priority })
+		await this.resetSize()
+	}
+
+	/** 变量命名：非英文先按顺序使用翻译服务翻译为英文，再生成各种命名风格的候选 */
+	async naming(text: string) {
+		let phrase = text
+		this.detect_language.value = await invokeLocalDetect(text)
+		if (this.detect_language.value !== 'en') {
+			this.isTranslating.value = true
+			const services = configuration.trans_services
+				.map(generateTransConfig)
+				.filter(x => x?.service?.Translate && x.service.languages['en'] !== undefined)
+			for (const config of services) {
+				try {
+					const result = await this.retryTranslate(uuid(), config, text, 'auto', 'en', true)
+					const str = typeof result === 'string' ? result : result?.text
+					if (str?.trim()) {
+						phrase = str.trim()
+						break
+					}
+				} catch {}
+			}
+			this.isTranslating.value = false
+		}
+		try {
+			this.namingCandidates.value = await invoke('naming', { text: phrase })
+		} catch {
+			this.namingCandidates.value = []
+		}
+		await this.resetSize()
+	}
+
+	/** 提示词改写：使用大模型服务按预设改写文本，结果通过 translator://partial 事件逐段返回 */
+	async prompt(preset: string, name: string, text: string, app?: string) {
+		const id = uuid()
+		this.isTranslating.value = true
+		this.promptResult.value = { id, preset, name: name || preset, text: '', service: '', loading: true, error: '' }
+		const self = this
+		const result = this.promptResult.value
+		const unlisten = await listen<{ id: string, delta: string }>('translator://partial', function(payload) {
+			if (payload.id === id && result === self.promptResult.value) {
+				result.text += payload.delta
+			}
+		})
+		await this.resetSize()
+		try {
+			const res = await invoke<{ label: string, text: string }>('prompt_run', {
+				request: { id, preset, text, app: app || null, to: this.target.value }
+			})
+			result.text = res.text
+			result.service = res.label
+			if (configuration.auto_copy) {
+				await this.copyResult(res.text)
+			}
+		} catch (e) {
+			result.error = String(e)
+		} finally {
+			unlisten()
+			result.loading = false
+			this.isTranslating.value = false
+		}
+		if (result === this.promptResult.value) {
+			await this.resetSize()
 		}
 	}
 
@@ -205,6 +380,11 @@ class Store {
 				}
 			}
 		}
+		const glossary = await this.prepareGlossary(service.name, text, from === 'auto' ? this.detect_language.value : from, to)
+		const key = config.id || service.name
+		delete this.glossaryViolations.value[key]
+		//  历史记录使用Tosa的语种代码，便于按语种对检索及导出
+		const lang = { from: from === 'auto' ? this.detect_language.value || from : from, to }
 		if (service.languages[from] === undefined) {
 			throw new Error('不支持的源语种: ' + LanguageZh[from])
 		} else {
@@ -218,10 +398,21 @@ class Store {
 		let only_dict = configuration.only_dict
 		//  是否使用缓存
 		if (configuration.use_cache && cache) {
-			const cache_string = this.cache.get(service.name, text, from, to)
-			if (cache_string) {
-				this.cache.hit(cache_string.id)
-				return cache_string.result
+			//  匹配到的术语是缓存键的一部分，术语表修改后不会命中旧的译文
+			const cached = await invoke<string | IDictResult | null>(
+				'trans_cache_get', { service: key, text, from, to, glossary: glossary || null }
+			).catch(() => null)
+			if (cached) {
+				if (glossary && typeof cached === 'string') {
+					const checked = await invoke<{ text: string, violations: IGlossaryViolation[] }>(
+						'glossary_restore', { prepared: glossary, text: cached }
+					).catch(() => null)
+					if (checked?.violations.length) {
+						this.glossaryViolations.value[key] = checked.violations
+					}
+				}
+				this.cache.get(service.name, text, lang.from, lang.to).then(r => r && this.cache.hit(r.id)).catch(() => {})
+				return cached
 			}
 		}
 		retry = retry || configuration.trans_retry_count || 1
@@ -229,13 +420,25 @@ class Store {
 		for (let i = 0; i < retry; i++) {
 			try {
 				const st = Date.now()
-				const result = await textConvert(config, text, from, to, only_dict)
+				let result = await textConvert(config, text, from, to, only_dict, glossary)
+				if (glossary && typeof result === 'string') {
+					const checked = await invoke<{ text: string, violations: IGlossaryViolation[] }>(
+						'glossary_restore', { prepared: glossary, text: result }
+					)
+					result = checked.text
+					if (checked.violations.length) {
+						this.glossaryViolations.value[key] = checked.violations
+					}
+				}
 				if (result) {
+					if (configuration.use_cache) {
+						invoke('trans_cache_put', { service: key, text, from, to, value: result, glossary: glossary || null }).catch(() => {})
+					}
 					if (configuration.enable_cache) {
 						this.cache.add(
 							group_id, service.name, label || service.label, this.text.value,
-							from, to, result, Date.now() - st
-						)
+							lang.from, lang.to, result, Date.now() - st
+						).catch(() => {})
 					}
 					return result
 				}
@@ -246,6 +449,73 @@ class Store {
 		throw new Error(retry_error_result.join('\n'))
 	}
 
+	/** 后端已实现的服务由后端翻译命令调用，其余服务仍使用前端插件 */
+	async isBackendService(config: ITransServiceConfig) {
+		if (!this.backendServices) {
+			this.backendServices = invoke<{ name: string }[]>('translate_providers')
+				.catch(() => [])
+		}
+		return (await this.backendServices).some(x => x.name === config.service.name)
+	}
+
+	/**
+	 * 通过后端翻译命令调用单个服务，超时、重试、术语表及翻译缓存均由后端处理。
+	 * 开启边生成边显示时，增量内容通过 translator://partial 事件传给 onDelta，重试时 reset 为 true
+	 */
+	async backendTranslate(
+		group_id: string, config: ITransServiceConfig, text: string, from: string, to: string, cache: boolean,
+		onDelta: (delta: string, reset: boolean) => void
+	) {
+		const id = uuid()
+		const key = config.id || config.service.name
+		delete this.glossaryViolations.value[key]
+		let attempt = 0
+		const unlisten = await listen<{ id: string, service: string, attempt: number, delta: string }>(
+			'translator://partial', function(payload) {
+				if (payload.id !== id || payload.service !== key) return
+				onDelta(payload.delta, payload.attempt !== attempt)
+				attempt = payload.attempt
+			}
+		)
+		let res: IServiceResult | undefined
+		try {
+			const response = await invoke<{ results: IServiceResult[] }>('translate', {
+				request: {
+					id, text, from, to, services: [key], stream: configuration.trans_stream,
+					cache: configuration.use_cache && cache, only_dict: configuration.only_dict
+				}
+			})
+			res = response.results[0]
+		} finally {
+			unlisten()
+		}
+		if (!res?.ok || !res.data) {
+			throw new Error(res?.error || '翻译结果为空')
+		}
+		if (res.violations?.length) {
+			this.glossaryViolations.value[key] = res.violations
+		}
+		if (configuration.enable_cache && !res.cached) {
+			const lang = this.memoryLanguages()
+			this.cache.add(
+				group_id, config.service.name, config.label || config.service.label, this.text.value,
+				lang.from, lang.to, res.data, res.elapsed
+			).catch(() => {})
+		}
+		return res.data
+	}
+
+	/** 匹配术语表，未命中术语时返回 undefined */
+	private async prepareGlossary(service: string, text: string, from: string, to: string) {
+		if (!from || from === 'auto') return undefined
+		try {
+			const prepared = await invoke<IGlossaryPrepared>('glossary_prepare', { service, text, from, to })
+			return prepared.terms.length ? prepared : undefined
+		} catch {
+			return undefined
+		}
+	}
+
 	public async ocrRecognize(base64: string, trans: boolean) {
 		if (this.isRecogning.value) return
 		if (!base64) {
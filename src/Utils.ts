@@ -5,6 +5,19 @@ export function uuid() {
 	return uuidv4()
 }
 
+/** 替换提示词中的 $text $from $to $glossary，未使用 $glossary 时术语说明追加到包含 $text 的内容后 */
+export function fillPrompt(content: string, text: string, from: string, to: string, glossary = '') {
+	if (glossary && content.includes('$text') && !content.includes('$glossary')) {
+		content += '\n\n$glossary'
+	}
+	//	原文最后替换，避免原文中的变量名被替换
+	return content
+		.replaceAll('$from', from)
+		.replaceAll('$to', to)
+		.replaceAll('$glossary', glossary)
+		.replaceAll('$text', text)
+}
+
 export function generateParams(uiconfigs: IUiConfig[], params: Record<string, any>) {
 	for (const ui of uiconfigs) {
 		if (params[ui.name] !== undefined) {
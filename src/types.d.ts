@@ -57,6 +57,129 @@ export interface IDictResult {
 	sentence?: string[]
 	wfs?: { name: string; value: string }[]
 	web?: { name: string; list: string[] }[]
+	/** 考试、词表标签，如 四级、雅思 */
+	tags?: string[]
+}
+
+/** 多个词典服务合并后的结果，provenance 中的下标与对应列表一致 */
+export interface IMergedDictResult extends IDictResult {
+	sources: string[]
+	provenance: {
+		pronunciations: string[][]
+		explanations: string[][][]
+		sentence: string[][]
+		wfs: string[][]
+		web: string[][]
+		tags: string[][]
+	}
+}
+
+/** 术语，from to 为语种代码，target 为空时保留原文 */
+export interface IGlossaryTerm {
+	source: string
+	target: string
+	from: string
+	to: string
+	caseSensitive: boolean
+	note: string
+}
+
+/** 术语表处理后的原文，prompt 为注入大模型提示词的术语说明 */
+export interface IGlossaryPrepared {
+	text: string
+	prompt: string
+	terms: { source: string, target: string, placeholder: string }[]
+}
+
+/** 译文中未使用指定译法的术语 */
+export interface IGlossaryViolation {
+	source: string
+	target: string
+}
+
+/** 翻译记忆中的句段，时间为毫秒时间戳 */
+export interface ITmSegment {
+	id: number
+	source: string
+	target: string
+	from: string
+	to: string
+	/** 来源，如翻译服务名称或导入的文件名 */
+	origin: string
+	created: number
+	updated: number
+}
+
+/** 翻译记忆的匹配结果，score 为匹配度，100 为精确匹配 */
+export interface ITmMatch extends ITmSegment {
+	score: number
+}
+
+/** 生词本中的单词，interval 为复习间隔（天），时间为毫秒时间戳 */
+export interface IVocabWord {
+	id: number
+	word: string
+	from: string
+	to: string
+	phonetic: string
+	/** 释义，每个词性一行 */
+	definition: string
+	result: string | IDictResult
+	/** 查词时所在的句子 */
+	context: string
+	sourceApp: string
+	lookups: number
+	starred: boolean
+	created: number
+	updated: number
+	due: number
+	interval: number
+	ease: number
+	reps: number
+	lapses: number
+	reviewed: number
+}
+
+/** 提示词改写预设，模板中可使用 {text} {from} {to} {app} {glossary} */
+export interface IPromptPreset {
+	id: string
+	name: string
+	/** 系统提示词 */
+	system: string
+	prompt: string
+	/** 使用的大模型服务 id 或名称，为空时使用默认服务 */
+	service: string
+	hotkey: string
+	/** 显示在托盘菜单中 */
+	tray: boolean
+	/** 可作为划词助手的操作 */
+	selection: boolean
+	builtin?: boolean
+}
+
+/** 提示词改写的结果，text 随流式输出逐段追加 */
+export interface IPromptResult {
+	id: string
+	preset: string
+	name: string
+	text: string
+	service: string
+	loading: boolean
+	error: string
+}
+
+/** 后端翻译命令中单个服务的结果 */
+export interface IServiceResult {
+	id: string
+	/** 服务配置的 id，未设置时为服务名称 */
+	service: string
+	label: string
+	ok: boolean
+	data?: string | IDictResult
+	error?: string
+	violations?: IGlossaryViolation[]
+	cached?: boolean
+	elapsed: number
 }
 
 export interface IBaseTransService extends IBaseService {